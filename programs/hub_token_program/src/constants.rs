@@ -1,9 +1,47 @@
 /// Program constants and seeds for PDA derivation
-use anchor_lang::prelude::*;
 
 /// Seed for PropertyState PDA derivation
 pub const PROPERTY_STATE_SEED: &[u8] = b"property";
 
+/// Seed for the ProgramConfig singleton PDA
+pub const PROGRAM_CONFIG_SEED: &[u8] = b"config";
+
+/// Seed for the program-wide IssuerRegistry PDA
+pub const ISSUER_REGISTRY_SEED: &[u8] = b"issuer_registry";
+
+/// Seed for RedemptionPool PDA (one per property)
+pub const REDEMPTION_POOL_SEED: &[u8] = b"redemption_pool";
+
+/// Seed for Offering PDA (one per property)
+pub const OFFERING_SEED: &[u8] = b"offering";
+
+/// Seed for the offering's escrow token account PDA: ["offering_escrow", offering]
+pub const OFFERING_ESCROW_SEED: &[u8] = b"offering_escrow";
+
+/// Seed for Subscription PDA: ["subscription", offering, investor]
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+
+/// Seed for HolderRewards PDA: ["holder_rewards", mint, owner]
+pub const HOLDER_REWARDS_SEED: &[u8] = b"holder_rewards";
+
+/// Seed for HolderLockup PDA: ["holder_lockup", mint, owner]
+pub const HOLDER_LOCKUP_SEED: &[u8] = b"holder_lockup";
+
+/// Seed for InvestorProfile PDA: ["investor_profile", wallet]
+pub const INVESTOR_PROFILE_SEED: &[u8] = b"investor_profile";
+
+/// Seed for RewardsVault PDA: ["rewards_vault", property_state]
+pub const REWARDS_VAULT_SEED: &[u8] = b"rewards_vault";
+
+/// Fixed-point scale of acc_revenue_per_token
+pub const REVENUE_PER_TOKEN_PRECISION: u128 = 1_000_000_000_000;
+
+/// Maximum merkle proof length for revenue entitlements (trees up to 2^32 holders)
+pub const MAX_MERKLE_PROOF_LEN: usize = 32;
+
+/// Maximum number of revenue epochs claimed in one claim_revenue_batch
+pub const MAX_BATCH_CLAIM_EPOCHS: usize = 24;
+
 /// Seed for PropertyVault PDA (future use for revenue distribution)
 pub const PROPERTY_VAULT_SEED: &[u8] = b"property_vault";
 
@@ -22,9 +60,20 @@ pub const MAX_PROPERTY_TYPE_LEN: usize = 100;
 /// Maximum length for metadata URI (IPFS/Arweave)
 pub const MAX_METADATA_URI_LEN: usize = 500;
 
-/// Hub Credential Program ID
-/// Custom KYC/Verifiable Credentials protocol for Hub Token
-pub const HUB_CREDENTIAL_PROGRAM_ID: Pubkey = pubkey!("FaJ4XGCLeu7eZiMjBEkANko3TRhpjns3cv6R1vZK94Wt");
+/// Maximum number of trusted issuers in the IssuerRegistry
+pub const MAX_TRUSTED_ISSUERS: usize = 32;
+
+/// Maximum number of issuers a property can restrict itself to
+pub const MAX_PROPERTY_ACCEPTED_ISSUERS: usize = 8;
+
+/// Maximum countries in each of a property's jurisdiction allow and deny lists
+pub const MAX_PROPERTY_JURISDICTIONS: usize = 16;
+
+/// Maximum length for a forced transfer case reference
+pub const MAX_CASE_REFERENCE_LEN: usize = 64;
+
+/// Seed for Hub Credential PDA derivation (owned by the Hub Credential program)
+pub const HUB_CREDENTIAL_SEED: &[u8] = b"credential";
 
 /// Minimum rental yield in basis points (0.01% = 1 bps)
 pub const MIN_RENTAL_YIELD_BPS: u16 = 0;
@@ -34,3 +83,10 @@ pub const MAX_RENTAL_YIELD_BPS: u16 = 10000;
 
 /// KYC grace period in seconds (7 days) for expiring credentials
 pub const KYC_GRACE_PERIOD_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Maximum configurable KYC grace period in seconds (30 days)
+pub const MAX_KYC_GRACE_PERIOD_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Time after an offering's window ends to settle it (30 days); subscriptions
+/// still unsettled afterwards can be refunded
+pub const OFFERING_SETTLEMENT_PERIOD_SECONDS: i64 = 30 * 24 * 60 * 60;
@@ -85,4 +85,196 @@ pub enum RwaError {
 
     #[msg("Credential suspended: contact support")]
     CredentialSuspended,
+
+    #[msg("Hub Credential program does not match the program config")]
+    InvalidCredentialProgram,
+
+    #[msg("Invalid KYC grace period: must be between 0 and 30 days")]
+    InvalidGracePeriod,
+
+    #[msg("Credential type not allowed for this property")]
+    CredentialTypeNotAllowed,
+
+    #[msg("Invalid credential type mask: must allow at least one known type")]
+    InvalidCredentialTypeMask,
+
+    // Issuer Registry Errors
+    #[msg("Credential issuer is not in the trusted issuer registry")]
+    IssuerNotTrusted,
+
+    #[msg("Credential issuer is suspended")]
+    IssuerSuspended,
+
+    #[msg("Credential issuer is not accepted for this property")]
+    IssuerNotAcceptedForProperty,
+
+    #[msg("Issuer is already registered")]
+    IssuerAlreadyRegistered,
+
+    #[msg("Issuer registry is full")]
+    IssuerRegistryFull,
+
+    #[msg("Too many accepted issuers for this property (max 8)")]
+    TooManyAcceptedIssuers,
+
+    // Transfer Hook Errors
+    #[msg("Transfer hook can only be invoked by Token-2022 during a transfer")]
+    NotTransferring,
+
+    #[msg("Account is not owned by the Token-2022 program")]
+    InvalidTokenProgram,
+
+    // No longer raised: the transfer hook keeps the specific credential error.
+    // Kept so later error codes do not shift.
+    #[msg("Sender KYC verification failed: source wallet Hub Credential invalid")]
+    SenderKycVerificationFailed,
+
+    #[msg("Recipient KYC verification failed: destination wallet Hub Credential invalid")]
+    RecipientKycVerificationFailed,
+
+    // Circuit Breaker Errors
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+
+    #[msg("Property token transfers are frozen")]
+    PropertyTransfersFrozen,
+
+    // Forced Transfer Errors
+    #[msg("Case reference too long (max 64 characters)")]
+    CaseReferenceTooLong,
+
+    #[msg("Source and destination token accounts must differ")]
+    SameSourceAndDestination,
+
+    // Supply Errors
+    #[msg("Circulating supply does not match the mint supply")]
+    SupplyMismatch,
+
+    // Redemption Errors
+    #[msg("Invalid redemption window: end must be after start")]
+    InvalidRedemptionWindow,
+
+    #[msg("Redemption window is not open")]
+    RedemptionWindowClosed,
+
+    #[msg("Redemption exceeds the pool's remaining token capacity")]
+    RedemptionCapExceeded,
+
+    #[msg("Redemption pool can only be closed after its window ends or capacity is used")]
+    RedemptionPoolStillOpen,
+
+    #[msg("Payment accounts do not match the pool's payment currency")]
+    InvalidPaymentAccounts,
+
+    // Revenue Entitlement Errors
+    #[msg("Invalid entitlement proof for this revenue epoch")]
+    InvalidEntitlementProof,
+
+    #[msg("No revenue to claim")]
+    NothingToClaim,
+
+    #[msg("Holder rewards account does not match the token account owner or mint")]
+    InvalidHolderRewards,
+
+    #[msg("Revenue epoch is already finalized")]
+    EpochAlreadyFinalized,
+
+    #[msg("Claim deadline must be in the future")]
+    InvalidClaimDeadline,
+
+    #[msg("Claim deadline has passed for this revenue epoch")]
+    ClaimDeadlinePassed,
+
+    #[msg("Claim deadline has not passed yet")]
+    ClaimDeadlineNotReached,
+
+    #[msg("Batch claim accounts do not match the epoch entitlements")]
+    InvalidBatchClaim,
+
+    #[msg("Revenue epoch already claimed")]
+    EpochAlreadyClaimed,
+
+    // Offering Errors
+    #[msg("Invalid offering parameters")]
+    InvalidOfferingParameters,
+
+    #[msg("Offering is not accepting subscriptions")]
+    OfferingNotOpen,
+
+    #[msg("Subscription exceeds the offering's hard cap")]
+    OfferingCapExceeded,
+
+    #[msg("Subscription is outside the offering's ticket limits")]
+    InvalidTicketSize,
+
+    #[msg("Offering can only be settled after its window ends or the hard cap is reached")]
+    OfferingStillOpen,
+
+    #[msg("Subscription is not refundable")]
+    SubscriptionNotRefundable,
+
+    #[msg("Settlement accounts do not match the offering's subscriptions")]
+    InvalidSettlementAccounts,
+
+    // Holding Limit Errors
+    #[msg("Holder would exceed the property's maximum tokens per holder")]
+    HoldingLimitExceeded,
+
+    #[msg("Property has reached its maximum number of holders")]
+    MaxHoldersExceeded,
+
+    // Lock-up Errors
+    #[msg("Transfer would take the holder below their locked token amount")]
+    TokensLocked,
+
+    #[msg("Invalid lock-up schedule")]
+    InvalidLockupSchedule,
+
+    #[msg("Lock-up can only be extended")]
+    LockupCannotBeShortened,
+
+    #[msg("Minting with a lock-up requires the investor lockup account")]
+    MissingLockupAccount,
+
+    #[msg("Holder lockup account does not match the holder")]
+    InvalidHolderLockup,
+
+    // Jurisdiction Errors
+    #[msg("Investor jurisdiction is not allowed for this property")]
+    JurisdictionNotAllowed,
+
+    #[msg("Country code must be two upper-case ISO 3166-1 alpha-2 letters")]
+    InvalidCountryCode,
+
+    #[msg("Too many jurisdictions in allow or deny list")]
+    TooManyJurisdictions,
+
+    #[msg("Investor profile account does not match the wallet")]
+    InvalidInvestorProfile,
+
+    // Revenue Claim Errors
+    #[msg("Claims would exceed the revenue deposited for this epoch")]
+    ClaimExceedsEpochRevenue,
+
+    // Offering Settlement Errors
+    #[msg("Offering settlement deadline has passed")]
+    SettlementDeadlinePassed,
+
+    #[msg("Subscriber's property token account is missing or frozen")]
+    SubscriberAccountUnavailable,
+
+    // Migration Errors
+    #[msg("PropertyState is not in the legacy layout migrate_property_state converts")]
+    PropertyStateNotMigratable,
+
+    // Revenue Reconciliation Errors
+    #[msg("Reconciliation accounts must be distinct token accounts of the holder for this mint")]
+    InvalidReconcileAccounts,
+
+    #[msg("Holder's token accounts hold no fewer tokens than recorded")]
+    NothingToReconcile,
+
+    // Investor Profile Errors
+    #[msg("Jurisdiction was recorded by another issuer; only it or the registry admin can change it")]
+    JurisdictionSetByOtherIssuer,
 }
@@ -1,6 +1,8 @@
 /// Events emitted by the RWA Tokenization Program for audit trails
 use anchor_lang::prelude::*;
 
+use crate::state::{OfferingStatus, PauseReason};
+
 #[event]
 pub struct PropertyInitialized {
     pub mint: Pubkey,
@@ -46,6 +48,242 @@ pub struct PropertyStatusChanged {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PropertyStateMigrated {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramConfigUpdated {
+    pub admin: Pubkey,
+    pub credential_program_id: Pubkey,
+    pub previous_credential_program_id: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolPaused {
+    pub admin: Pubkey,
+    pub reason: PauseReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolUnpaused {
+    pub admin: Pubkey,
+    pub reason: PauseReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PropertyTransfersFrozen {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub reason: PauseReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PropertyTransfersUnfrozen {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub reason: PauseReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderFrozen {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub holder: Pubkey,
+    /// Hash of the off-chain order or case file
+    pub reason_hash: [u8; 32],
+    pub frozen_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderThawed {
+    pub mint: Pubkey,
+    pub token_account: Pubkey,
+    pub holder: Pubkey,
+    /// Hash of the off-chain order or case file
+    pub reason_hash: [u8; 32],
+    pub thawed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForcedTransfer {
+    pub mint: Pubkey,
+    pub source: Pubkey,
+    pub source_owner: Pubkey,
+    pub destination: Pubkey,
+    pub destination_owner: Pubkey,
+    pub amount: u64,
+    pub destination_kyc_skipped: bool,
+    /// Court, estate or recovery case reference
+    pub case_reference: String,
+    pub executed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionPoolCreated {
+    pub mint: Pubkey,
+    pub redemption_pool: Pubkey,
+    pub payment_mint: Option<Pubkey>,
+    pub price_per_token: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub max_tokens: u64,
+    pub total_funded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensRedeemed {
+    pub mint: Pubkey,
+    pub redemption_pool: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub payout: u64,
+    pub circulating_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionPoolClosed {
+    pub mint: Pubkey,
+    pub redemption_pool: Pubkey,
+    pub tokens_redeemed: u64,
+    pub total_paid: u64,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferingCreated {
+    pub mint: Pubkey,
+    pub offering: Pubkey,
+    pub quote_mint: Pubkey,
+    pub price_per_token: u64,
+    pub min_raise: u64,
+    pub max_raise: u64,
+    pub min_ticket: u64,
+    pub max_ticket: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferingSubscribed {
+    pub mint: Pubkey,
+    pub offering: Pubkey,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub subscription_total: u64,
+    pub total_raised: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OfferingSettled {
+    pub mint: Pubkey,
+    pub offering: Pubkey,
+    pub status: OfferingStatus,
+    pub subscriptions_issued: u32,
+    pub subscriptions_rejected: u32,
+    pub tokens_issued: u64,
+    pub proceeds: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionRefunded {
+    pub offering: Pubkey,
+    pub investor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ComplianceAuthorityUpdated {
+    pub mint: Pubkey,
+    pub compliance_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SenderVerificationUpdated {
+    pub mint: Pubkey,
+    pub verify_sender: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowedCredentialTypesUpdated {
+    pub mint: Pubkey,
+    pub allowed_credential_types: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TrustedIssuerAdded {
+    pub issuer: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TrustedIssuerRemoved {
+    pub issuer: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TrustedIssuerSuspended {
+    pub issuer: Pubkey,
+    pub is_suspended: bool,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IssuerRegistryAdminUpdated {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AcceptedIssuersUpdated {
+    pub mint: Pubkey,
+    pub accepted_issuers: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JurisdictionsUpdated {
+    pub mint: Pubkey,
+    pub allowed_jurisdictions: Vec<[u8; 2]>,
+    pub denied_jurisdictions: Vec<[u8; 2]>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InvestorJurisdictionSet {
+    pub investor: Pubkey,
+    pub country: [u8; 2],
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
 /// Event emitted when Hub Credential verification succeeds
 #[event]
 pub struct HubCredentialVerified {
@@ -55,12 +293,70 @@ pub struct HubCredentialVerified {
     pub timestamp: i64,
 }
 
+/// Event emitted when a credential is accepted within its grace period
+#[event]
+pub struct CredentialInGracePeriod {
+    pub wallet: Pubkey,
+    pub credential: Pubkey,
+    pub mint: Pubkey,
+    pub expires_at: i64,
+    pub grace_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KycGracePeriodUpdated {
+    pub mint: Pubkey,
+    pub kyc_grace_period_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HoldingLimitsUpdated {
+    pub mint: Pubkey,
+    pub max_tokens_per_holder: u64,
+    pub max_holders: u32,
+    pub holder_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensLockedUp {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub locked_amount: u64,
+    pub lockup_until: i64,
+    pub vesting_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockupExtended {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub locked_amount: u64,
+    pub lockup_until: i64,
+    pub vesting_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockupReleased {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub released_amount: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct TransferKycVerified {
     pub mint: Pubkey,
     pub source: Pubkey,
+    pub source_owner: Pubkey,
     pub destination: Pubkey,
     pub destination_owner: Pubkey,
+    pub sender_verified: bool,
     pub amount: u64,
     pub timestamp: i64,
 }
@@ -71,3 +367,35 @@ pub struct ExtraAccountMetasInitialized {
     pub extra_account_meta_list: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct RevenueDistributed {
+    pub property: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub tracked_supply: u64,
+    pub acc_revenue_per_token: u128,
+    pub distributed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HolderRewardsReconciled {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub previous_balance: u64,
+    pub balance: u64,
+    pub tracked_supply: u64,
+    pub reconciled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevenueRewardsClaimed {
+    pub property: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
@@ -27,9 +27,30 @@ pub struct BurnPropertyTokens<'info> {
     pub mint: Box<InterfaceAccount<'info, Mint>>,
 
     /// Investor's token account
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = investor_token_account.owner == investor.key() @ RwaError::Unauthorized,
+        constraint = investor_token_account.mint == mint.key() @ RwaError::InvalidMint,
+    )]
     pub investor_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// Program config (protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Investor's revenue accounting. Until it is created the investor may
+    /// only burn from their associated token account.
+    /// CHECK: Address checked in handler against the investor
+    #[account(mut)]
+    pub investor_rewards: UncheckedAccount<'info>,
+
+    /// Investor's lock-up, only initialized for locked-up holders
+    /// CHECK: Address checked in handler against the investor
+    pub investor_lockup: UncheckedAccount<'info>,
+
     pub token_program: Program<'info, Token2022>,
 }
 
@@ -37,6 +58,12 @@ pub struct BurnPropertyTokens<'info> {
 pub fn handler(ctx: Context<BurnPropertyTokens>, amount: u64) -> Result<()> {
     let property_state = &mut ctx.accounts.property_state;
 
+    ctx.accounts.program_config.require_not_paused()?;
+    property_state.require_not_frozen()?;
+
+    // Account for tokens burned directly through Token-2022 before adjusting
+    property_state.reconcile_circulating_supply(ctx.accounts.mint.supply)?;
+
     // Burn tokens
     let cpi_context = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
@@ -49,8 +76,29 @@ pub fn handler(ctx: Context<BurnPropertyTokens>, amount: u64) -> Result<()> {
 
     token_2022::burn(cpi_context, amount)?;
 
+    // Settle accrued revenue at the old balance
+    let balance = property_state.apply_balance_change_to_holder(
+        &ctx.accounts.investor_rewards,
+        &ctx.accounts.investor.key(),
+        &ctx.accounts.investor_token_account.key(),
+        ctx.accounts.investor_token_account.amount,
+        false,
+        amount,
+    )?;
+
+    // Locked tokens cannot be burned
+    let locked = holder_locked_amount(
+        &ctx.accounts.investor_lockup,
+        &ctx.accounts.mint.key(),
+        &ctx.accounts.investor.key(),
+    )?;
+    require!(balance >= locked, RwaError::TokensLocked);
+
     // Update circulating supply
-    property_state.circulating_supply -= amount;
+    property_state.circulating_supply = property_state
+        .circulating_supply
+        .checked_sub(amount)
+        .ok_or(RwaError::MathOverflow)?;
     property_state.updated_at = Clock::get()?.unix_timestamp;
 
     // Emit tokens burned event
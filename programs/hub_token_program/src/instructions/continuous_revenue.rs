@@ -0,0 +1,372 @@
+/// Continuous revenue distribution with cumulative reward-per-token accounting
+///
+/// Each deposit raises the property's `acc_revenue_per_token` by
+/// `amount / tracked_supply`, the tokens recorded on HolderRewards accounts.
+/// Tokens of wallets without one earn nothing until it is created, so every
+/// deposited lamport can be claimed. A holder's HolderRewards records the
+/// balance it last settled at together with `reward_debt`; the transfer hook,
+/// mint, burn, redemption and forced transfers settle it before the balance
+/// changes, so `claim_all_revenue` pays everything owed in one instruction
+/// however many deposits happened since.
+///
+/// The lamports are held on the program-owned RewardsVault PDA.
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_2022::{
+        spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccountState},
+        ID as TOKEN_2022_PROGRAM_ID,
+    },
+    token_interface::Mint,
+};
+
+use crate::{constants::*, error::RwaError, events::*, state::*, utils::*};
+
+// ============================================================================
+// INITIALIZE HOLDER REWARDS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeHolderRewards<'info> {
+    /// Pays for the account (the holder or anyone sending them tokens)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Wallet the account tracks
+    /// CHECK: Only used as a seed
+    pub owner: UncheckedAccount<'info>,
+
+    /// PropertyState PDA (tracked supply)
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// HolderRewards PDA to create
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + HolderRewards::INIT_SPACE,
+        seeds = [HOLDER_REWARDS_SEED, mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub holder_rewards: Box<Account<'info, HolderRewards>>,
+
+    /// Owner's associated token account for the mint, which may not exist
+    /// CHECK: Address checked here, only read when created
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &owner.key(),
+            &mint.key(),
+            &TOKEN_2022_PROGRAM_ID,
+        ) @ RwaError::InvalidHolderRewards,
+    )]
+    pub owner_token_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the owner's HolderRewards
+///
+/// Transfers may reach a wallet before its HolderRewards exists, in which
+/// case the tokens sit in its associated token account earning nothing;
+/// they are tracked from here on.
+pub fn handler_initialize_holder_rewards(ctx: Context<InitializeHolderRewards>) -> Result<()> {
+    let balance = token_account_balance(&ctx.accounts.owner_token_account)?;
+    let holder_rewards = &mut ctx.accounts.holder_rewards;
+    ctx.accounts.property_state.start_tracking_holder(
+        holder_rewards,
+        ctx.accounts.owner.key(),
+        balance,
+        ctx.bumps.holder_rewards,
+    )?;
+
+    msg!(
+        "Initialized HolderRewards for {} on mint {} (balance: {})",
+        holder_rewards.owner,
+        holder_rewards.mint,
+        balance
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// DISTRIBUTE REVENUE
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct DistributeRevenue<'info> {
+    /// Property authority depositing revenue
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, property_state.mint.as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        constraint = property_state.is_active @ RwaError::PropertyNotActive,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// RewardsVault PDA, created on the first distribution
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RewardsVault::INIT_SPACE,
+        seeds = [REWARDS_VAULT_SEED, property_state.key().as_ref()],
+        bump,
+    )]
+    pub rewards_vault: Box<Account<'info, RewardsVault>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit revenue for all current holders pro rata to their balances
+pub fn handler_distribute_revenue(ctx: Context<DistributeRevenue>, amount: u64) -> Result<()> {
+    require!(amount > 0, RwaError::InvalidAmount);
+
+    let property_state = &mut ctx.accounts.property_state;
+    let tracked_supply = property_state.tracked_supply;
+    require!(tracked_supply > 0, RwaError::NoTokenHolders);
+
+    let increment = (amount as u128)
+        .checked_mul(REVENUE_PER_TOKEN_PRECISION)
+        .ok_or(RwaError::MathOverflow)?
+        / tracked_supply as u128;
+    require!(increment > 0, RwaError::ClaimTooSmall);
+
+    property_state.acc_revenue_per_token = property_state
+        .acc_revenue_per_token
+        .checked_add(increment)
+        .ok_or(RwaError::MathOverflow)?;
+    property_state.total_revenue_distributed = property_state
+        .total_revenue_distributed
+        .checked_add(amount)
+        .ok_or(RwaError::MathOverflow)?;
+    property_state.updated_at = Clock::get()?.unix_timestamp;
+
+    let rewards_vault = &mut ctx.accounts.rewards_vault;
+    rewards_vault.property_state = property_state.key();
+    rewards_vault.bump = ctx.bumps.rewards_vault;
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: rewards_vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(RevenueDistributed {
+        property: property_state.key(),
+        mint: property_state.mint,
+        amount,
+        tracked_supply,
+        acc_revenue_per_token: property_state.acc_revenue_per_token,
+        distributed_by: ctx.accounts.authority.key(),
+        timestamp: property_state.updated_at,
+    });
+
+    msg!(
+        "Revenue distributed: {} lamports over {} tokens",
+        amount,
+        tracked_supply
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// CLAIM ALL REVENUE
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ClaimAllRevenue<'info> {
+    /// Holder claiming their revenue
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        seeds = [PROPERTY_STATE_SEED, property_state.mint.as_ref()],
+        bump = property_state.bump,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// Holder's revenue accounting
+    #[account(
+        mut,
+        seeds = [HOLDER_REWARDS_SEED, property_state.mint.as_ref(), holder.key().as_ref()],
+        bump = holder_rewards.bump,
+    )]
+    pub holder_rewards: Box<Account<'info, HolderRewards>>,
+
+    /// RewardsVault PDA holding the distributed lamports
+    #[account(
+        mut,
+        seeds = [REWARDS_VAULT_SEED, property_state.key().as_ref()],
+        bump = rewards_vault.bump,
+    )]
+    pub rewards_vault: Box<Account<'info, RewardsVault>>,
+
+    /// Program config (protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Pay the holder all revenue accrued since their last claim
+pub fn handler_claim_all_revenue(ctx: Context<ClaimAllRevenue>) -> Result<()> {
+    ctx.accounts.program_config.require_not_paused()?;
+    ctx.accounts.property_state.require_not_frozen()?;
+
+    let holder_rewards = &mut ctx.accounts.holder_rewards;
+    holder_rewards.settle(ctx.accounts.property_state.acc_revenue_per_token)?;
+
+    let amount = holder_rewards.pending;
+    require!(amount > 0, RwaError::NothingToClaim);
+
+    // Settled amounts round down, so the vault never pays into its rent reserve
+    let vault = ctx.accounts.rewards_vault.to_account_info();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(vault.data_len());
+    require!(
+        vault.lamports().saturating_sub(amount) >= rent_exempt_minimum,
+        RwaError::InsufficientVaultBalance
+    );
+
+    holder_rewards.pending = 0;
+    holder_rewards.total_claimed = holder_rewards
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(RwaError::MathOverflow)?;
+
+    vault.sub_lamports(amount)?;
+    ctx.accounts.holder.add_lamports(amount)?;
+
+    emit!(RevenueRewardsClaimed {
+        property: ctx.accounts.property_state.key(),
+        holder: ctx.accounts.holder.key(),
+        amount,
+        balance: holder_rewards.balance,
+        total_claimed: holder_rewards.total_claimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Revenue claimed: {} lamports by {}",
+        amount,
+        ctx.accounts.holder.key()
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// RECONCILE HOLDER REWARDS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct ReconcileHolderRewards<'info> {
+    /// Property authority or compliance authority
+    #[account(
+        constraint = property_state.can_enforce_compliance(&authority.key()) @ RwaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// HolderRewards of the holder who burned tokens directly
+    #[account(
+        mut,
+        seeds = [HOLDER_REWARDS_SEED, mint.key().as_ref(), holder_rewards.owner.as_ref()],
+        bump = holder_rewards.bump,
+    )]
+    pub holder_rewards: Box<Account<'info, HolderRewards>>,
+}
+
+/// Lower a holder's recorded balance to what their token accounts still hold
+///
+/// Burns made directly through Token-2022 bypass this program, leaving the
+/// burned tokens on the holder's HolderRewards and in tracked_supply, where
+/// they keep earning a share of every distribution. Every one of the
+/// holder's token accounts for the mint must be passed in
+/// `remaining_accounts`; the balance can only go down. Revenue accrued
+/// before the reconciliation stays claimable.
+pub fn handler_reconcile_holder_rewards(ctx: Context<ReconcileHolderRewards>) -> Result<()> {
+    let mint = ctx.accounts.mint.key();
+    let owner = ctx.accounts.holder_rewards.owner;
+
+    let mut held: u64 = 0;
+    let mut counted: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for token_account in ctx.remaining_accounts {
+        require!(
+            token_account.owner == &TOKEN_2022_PROGRAM_ID && !counted.contains(token_account.key),
+            RwaError::InvalidReconcileAccounts
+        );
+        let data = token_account.try_borrow_data()?;
+        let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?.base;
+        require!(
+            account.mint == mint && account.owner == owner,
+            RwaError::InvalidReconcileAccounts
+        );
+        held = held
+            .checked_add(account.amount)
+            .ok_or(RwaError::MathOverflow)?;
+        counted.push(token_account.key());
+    }
+
+    let previous_balance = ctx.accounts.holder_rewards.balance;
+    require!(held < previous_balance, RwaError::NothingToReconcile);
+
+    let property_state = &mut ctx.accounts.property_state;
+    property_state.apply_holder_balance_change(
+        &mut ctx.accounts.holder_rewards,
+        false,
+        previous_balance - held,
+    )?;
+    property_state.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(HolderRewardsReconciled {
+        mint,
+        owner,
+        previous_balance,
+        balance: held,
+        tracked_supply: property_state.tracked_supply,
+        reconciled_by: ctx.accounts.authority.key(),
+        timestamp: property_state.updated_at,
+    });
+
+    msg!(
+        "HolderRewards of {} reconciled from {} to {} tokens",
+        owner,
+        previous_balance,
+        held
+    );
+
+    Ok(())
+}
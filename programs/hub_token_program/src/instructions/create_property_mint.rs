@@ -1,17 +1,24 @@
 /// Create a new property mint with TransferHook extension
 ///
 /// This instruction creates a Token-2022 mint with the TransferHook extension
-/// enabled, ensuring ALL transfers (including P2P) verify KYC compliance.
+/// enabled, ensuring ALL transfers (including P2P) verify KYC compliance, the
+/// PermanentDelegate extension for court-ordered recoveries, and on-chain
+/// TokenMetadata (via MetadataPointer) so wallets can display the property.
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use anchor_spl::token_2022::spl_token_2022::{
     extension::ExtensionType,
-    instruction::initialize_mint2,
+    instruction::{initialize_mint2, initialize_permanent_delegate},
     state::Mint as MintState,
 };
 use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::{
+    metadata_pointer_initialize, token_metadata_initialize, token_metadata_update_field,
+    spl_token_metadata_interface::state::Field,
+    MetadataPointerInitialize, TokenMetadataInitialize, TokenMetadataUpdateField,
+};
 
-use crate::{constants::*, error::RwaError, events::*, state::*, instructions::transfer_hook::*};
+use crate::{constants::*, error::RwaError, events::*, state::*, instructions::transfer_hook::*, utils::*};
 
 /// Accounts for creating a property mint with TransferHook
 #[derive(Accounts)]
@@ -44,12 +51,19 @@ pub struct CreatePropertyMint<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 4 + 4 + EXTRA_ACCOUNT_META_SIZE,
+        space = extra_account_meta_list_size()?,
         seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
         bump,
     )]
     pub extra_account_meta_list: UncheckedAccount<'info>,
 
+    /// Program config (Hub Credential program ID for the ExtraAccountMetaList)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
     pub token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
 }
@@ -81,13 +95,30 @@ pub fn handler(
     let property_state = &ctx.accounts.property_state;
 
     // Calculate space for mint with TransferHook extension
-    let extension_types = [ExtensionType::TransferHook];
+    // Token accounts for this mint get the TransferHookAccount extension,
+    // whose transferring flag lets the hook reject standalone invocations
+    let extension_types = [
+        ExtensionType::TransferHook,
+        ExtensionType::PermanentDelegate,
+        ExtensionType::MetadataPointer,
+    ];
     let mint_size = ExtensionType::try_calculate_account_len::<MintState>(&extension_types)
         .map_err(|_| RwaError::MathOverflow)?;
 
+    // TokenMetadata is variable-length: Token-2022 reallocs the mint when it is
+    // written, so fund the final size up front
+    let token_metadata = property_token_metadata(
+        &mint.key(),
+        &property_state.key(),
+        property_name.clone(),
+        property_symbol.clone(),
+        &property_details,
+    );
+    let metadata_size = token_metadata_len(&token_metadata)?;
+
     // Create mint account
     let rent = Rent::get()?;
-    let lamports = rent.minimum_balance(mint_size);
+    let lamports = rent.minimum_balance(mint_size + metadata_size);
 
     system_program::create_account(
         CpiContext::new(
@@ -118,12 +149,39 @@ pub fn handler(
         ],
     )?;
 
+    // Initialize PermanentDelegate extension (PropertyState PDA, used by force_transfer)
+    let init_delegate_ix = initialize_permanent_delegate(
+        &anchor_spl::token_2022::ID,
+        &mint.key(),
+        &property_state.key(),
+    )?;
+
+    anchor_lang::solana_program::program::invoke(
+        &init_delegate_ix,
+        &[
+            mint.to_account_info(),
+        ],
+    )?;
+
+    // Initialize MetadataPointer extension (metadata lives in the mint itself)
+    metadata_pointer_initialize(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            MetadataPointerInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: mint.to_account_info(),
+            },
+        ),
+        Some(property_state.key()),
+        Some(mint.key()),
+    )?;
+
     // Initialize mint
     let init_mint_ix = initialize_mint2(
         &anchor_spl::token_2022::ID,
         &mint.key(),
         &property_state.key(), // Mint authority = PropertyState PDA
-        None,                   // No freeze authority
+        Some(&property_state.key()), // Freeze authority = PropertyState PDA
         decimals,
     )?;
 
@@ -134,22 +192,83 @@ pub fn handler(
         ],
     )?;
 
+    // Initialize TokenMetadata, signed by the PropertyState PDA as mint authority
+    let mint_key = mint.key();
+    let seeds = &[
+        PROPERTY_STATE_SEED,
+        mint_key.as_ref(),
+        &[ctx.bumps.property_state],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: mint.to_account_info(),
+                update_authority: property_state.to_account_info(),
+                mint_authority: property_state.to_account_info(),
+                mint: mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        token_metadata.name.clone(),
+        token_metadata.symbol.clone(),
+        token_metadata.uri.clone(),
+    )?;
+
+    for (key, value) in &token_metadata.additional_metadata {
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: mint.to_account_info(),
+                    update_authority: property_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            Field::Key(key.clone()),
+            value.clone(),
+        )?;
+    }
+
     // Initialize PropertyState
     let property_state = &mut ctx.accounts.property_state;
     property_state.authority = authority.key();
     property_state.mint = mint.key();
+    property_state.compliance_authority = authority.key();
     property_state.property_name = property_name.clone();
     property_state.property_symbol = property_symbol.clone();
     property_state.total_supply = total_supply;
     property_state.circulating_supply = 0;
     property_state.details = property_details.clone();
     property_state.is_active = true;
+    property_state.transfers_frozen = false;
+    property_state.freeze_reason = PauseReason::Unspecified;
+    property_state.verify_sender = true;
+    property_state.kyc_grace_period_seconds = KYC_GRACE_PERIOD_SECONDS;
+    property_state.allowed_credential_types = HubCredentialType::ALL_MASK;
+    property_state.accepted_issuers = Vec::new();
+    property_state.allowed_jurisdictions = Vec::new();
+    property_state.denied_jurisdictions = Vec::new();
+    property_state.tracked_supply = 0;
+    property_state.acc_revenue_per_token = 0;
+    property_state.total_revenue_distributed = 0;
+    property_state.next_revenue_epoch = 0;
+    property_state.max_tokens_per_holder = 0;
+    property_state.max_holders = 0;
+    property_state.holder_count = 0;
     property_state.created_at = Clock::get()?.unix_timestamp;
     property_state.updated_at = Clock::get()?.unix_timestamp;
     property_state.bump = ctx.bumps.property_state;
 
     // Initialize ExtraAccountMetaList for Transfer Hook
-    initialize_extra_account_meta_list(&ctx.accounts.extra_account_meta_list, &mint.key())?;
+    write_extra_account_meta_list(
+        &ctx.accounts.extra_account_meta_list,
+        &ctx.accounts.program_config.credential_program_id,
+    )?;
 
     // Emit events
     emit!(PropertyInitialized {
@@ -175,48 +294,3 @@ pub fn handler(
 
     Ok(())
 }
-
-/// Helper function to initialize ExtraAccountMetaList
-fn initialize_extra_account_meta_list(
-    extra_account_meta_list: &AccountInfo,
-    mint: &Pubkey,
-) -> Result<()> {
-    let mut data = extra_account_meta_list.try_borrow_mut_data()?;
-
-    // Write Execute discriminator
-    data[0..8].copy_from_slice(&EXECUTE_DISCRIMINATOR);
-
-    // Write length (total size of the account list data: 4 + 35*1 = 39)
-    let list_length: u32 = 4 + EXTRA_ACCOUNT_META_SIZE as u32;
-    data[8..12].copy_from_slice(&list_length.to_le_bytes());
-
-    // Write count (1 extra account)
-    let count: u32 = 1;
-    data[12..16].copy_from_slice(&count.to_le_bytes());
-
-    // Write ExtraAccountMeta for destination attestation
-    // discriminator = 1 (PDA from external program - SAS)
-    data[16] = 1;
-
-    // address_config: seeds configuration for SAS attestation lookup
-    let mut address_config = [0u8; 32];
-    // Seed 1: Literal "attestation"
-    address_config[0] = 11; // length
-    address_config[1] = 0;  // seed type: Literal
-    address_config[2..13].copy_from_slice(b"attestation");
-    // Seed 2: AccountKey index 2 (destination token account)
-    address_config[13] = 1; // length
-    address_config[14] = 1; // seed type: AccountKey
-    address_config[15] = 2; // index
-
-    data[17..49].copy_from_slice(&address_config);
-
-    // is_signer = false
-    data[49] = 0;
-    // is_writable = false
-    data[50] = 0;
-
-    msg!("ExtraAccountMetaList initialized for mint: {}", mint);
-
-    Ok(())
-}
@@ -0,0 +1,243 @@
+/// Forced transfer / clawback through the PermanentDelegate extension
+///
+/// Recovers tokens after a lost key, an estate settlement or a legal seizure.
+/// The PropertyState PDA is both permanent delegate and mint authority, so the
+/// move is a delegated burn from the source plus a mint to the destination:
+/// a delegated transfer_checked would re-enter this program through the
+/// transfer hook, which the runtime rejects. Circulating supply is unchanged.
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, Burn, FreezeAccount, MintTo, ThawAccount, Token2022},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{constants::*, error::RwaError, events::*, state::*, utils::*};
+
+#[derive(Accounts)]
+pub struct ForceTransfer<'info> {
+    /// Property authority or compliance authority
+    #[account(
+        mut,
+        constraint = property_state.can_enforce_compliance(&signer.key()) @ RwaError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// PropertyState PDA (permanent delegate, mint and freeze authority)
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Token account the tokens are recovered from
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token account receiving the tokens
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+        constraint = destination_token_account.key() != source_token_account.key() @ RwaError::SameSourceAndDestination,
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Hub Credential of the destination owner (ignored when skip_destination_kyc is set)
+    /// CHECK: Verified using the Hub Credential program
+    pub destination_credential: UncheckedAccount<'info>,
+
+    /// Recorded jurisdiction of the destination owner (ignored when skip_destination_kyc is set)
+    /// CHECK: Address and data checked during credential verification
+    pub destination_profile: UncheckedAccount<'info>,
+
+    /// Program config (Hub Credential program ID, protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Trusted issuer registry
+    #[account(
+        seeds = [ISSUER_REGISTRY_SEED],
+        bump = issuer_registry.bump,
+    )]
+    pub issuer_registry: Box<Account<'info, IssuerRegistry>>,
+
+    /// Revenue accounting of the source owner. Until it is created only
+    /// their associated token account can be the source.
+    /// CHECK: Address checked in handler against the source token account owner
+    #[account(mut)]
+    pub source_rewards: UncheckedAccount<'info>,
+
+    /// Revenue accounting of the destination owner, created if needed
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + HolderRewards::INIT_SPACE,
+        seeds = [HOLDER_REWARDS_SEED, mint.key().as_ref(), destination_token_account.owner.as_ref()],
+        bump,
+    )]
+    pub destination_rewards: Box<Account<'info, HolderRewards>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for force_transfer instruction
+///
+/// Property transfer freezes do not apply (seizures usually follow one), and
+/// a frozen source account is thawed for the burn and frozen again afterwards.
+pub fn handler(
+    ctx: Context<ForceTransfer>,
+    amount: u64,
+    skip_destination_kyc: bool,
+    case_reference: String,
+) -> Result<()> {
+    require!(amount > 0, RwaError::InvalidAmount);
+    require!(
+        case_reference.len() <= MAX_CASE_REFERENCE_LEN,
+        RwaError::CaseReferenceTooLong
+    );
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let destination_owner = ctx.accounts.destination_token_account.owner;
+    if !skip_destination_kyc {
+        let standing = verify_hub_credential(
+            &ctx.accounts.destination_credential.to_account_info(),
+            &ctx.accounts.destination_profile.to_account_info(),
+            &destination_owner,
+            &ctx.accounts.program_config,
+            &ctx.accounts.property_state,
+            &ctx.accounts.issuer_registry,
+        )?;
+        require!(
+            standing == CredentialStanding::Valid,
+            RwaError::CredentialExpired
+        );
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        PROPERTY_STATE_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.property_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let mint = ctx.accounts.mint.to_account_info();
+    let source = ctx.accounts.source_token_account.to_account_info();
+    let property_state = ctx.accounts.property_state.to_account_info();
+    let source_was_frozen = ctx.accounts.source_token_account.is_frozen();
+
+    if source_was_frozen {
+        token_2022::thaw_account(CpiContext::new_with_signer(
+            token_program.clone(),
+            ThawAccount {
+                account: source.clone(),
+                mint: mint.clone(),
+                authority: property_state.clone(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    // Burn from the source as permanent delegate
+    token_2022::burn(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            Burn {
+                mint: mint.clone(),
+                from: source.clone(),
+                authority: property_state.clone(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    if source_was_frozen {
+        token_2022::freeze_account(CpiContext::new_with_signer(
+            token_program.clone(),
+            FreezeAccount {
+                account: source,
+                mint: mint.clone(),
+                authority: property_state.clone(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    // Mint the same amount to the destination as mint authority
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            token_program,
+            MintTo {
+                mint,
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: property_state,
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    // Settle accrued revenue at the old balances; accrual follows the tokens
+    // from now on. A source and destination with the same owner cancel out.
+    // The destination is held to the holding limits like any recipient.
+    if ctx.accounts.source_token_account.owner != destination_owner {
+        let property_state = &mut ctx.accounts.property_state;
+        property_state.apply_balance_change_to_holder(
+            &ctx.accounts.source_rewards,
+            &ctx.accounts.source_token_account.owner,
+            &ctx.accounts.source_token_account.key(),
+            ctx.accounts.source_token_account.amount,
+            false,
+            amount,
+        )?;
+        // A new account picks up tokens transferred in before it existed
+        let destination_rewards = &mut ctx.accounts.destination_rewards;
+        if destination_rewards.owner == Pubkey::default() {
+            property_state.start_tracking_holder(
+                destination_rewards,
+                destination_owner,
+                ctx.accounts.destination_token_account.amount,
+                ctx.bumps.destination_rewards,
+            )?;
+        }
+        property_state.apply_holder_balance_change(destination_rewards, true, amount)?;
+    }
+
+    emit!(ForcedTransfer {
+        mint: mint_key,
+        source: ctx.accounts.source_token_account.key(),
+        source_owner: ctx.accounts.source_token_account.owner,
+        destination: ctx.accounts.destination_token_account.key(),
+        destination_owner,
+        amount,
+        destination_kyc_skipped: skip_destination_kyc,
+        case_reference: case_reference.clone(),
+        executed_by: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Forced transfer of {} tokens from {} to {} (case: {})",
+        amount,
+        ctx.accounts.source_token_account.owner,
+        destination_owner,
+        case_reference
+    );
+
+    Ok(())
+}
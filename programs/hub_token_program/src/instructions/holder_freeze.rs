@@ -0,0 +1,171 @@
+/// Compliance freeze/thaw of individual holder token accounts
+///
+/// The PropertyState PDA is the mint's freeze authority, so the property
+/// authority or its compliance authority can freeze a court-ordered or
+/// sanctioned holder without touching anyone else.
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, FreezeAccount, ThawAccount, Token2022},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+// ============================================================================
+// FREEZE / THAW HOLDER
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateHolderFreeze<'info> {
+    /// Property authority or compliance authority
+    #[account(
+        constraint = property_state.can_enforce_compliance(&signer.key()) @ RwaError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    /// PropertyState PDA (freeze authority of the mint)
+    #[account(
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Holder token account to freeze or thaw
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Freeze a holder's token account
+///
+/// `reason_hash` is the hash of the off-chain order or case file.
+pub fn handler_freeze_holder(ctx: Context<UpdateHolderFreeze>, reason_hash: [u8; 32]) -> Result<()> {
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        PROPERTY_STATE_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.property_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_2022::freeze_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        FreezeAccount {
+            account: ctx.accounts.holder_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.property_state.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit!(HolderFrozen {
+        mint: mint_key,
+        token_account: ctx.accounts.holder_token_account.key(),
+        holder: ctx.accounts.holder_token_account.owner,
+        reason_hash,
+        frozen_by: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Frozen token account {} of holder {}",
+        ctx.accounts.holder_token_account.key(),
+        ctx.accounts.holder_token_account.owner
+    );
+
+    Ok(())
+}
+
+/// Thaw a previously frozen holder token account
+pub fn handler_thaw_holder(ctx: Context<UpdateHolderFreeze>, reason_hash: [u8; 32]) -> Result<()> {
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        PROPERTY_STATE_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.property_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_2022::thaw_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        ThawAccount {
+            account: ctx.accounts.holder_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: ctx.accounts.property_state.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit!(HolderThawed {
+        mint: mint_key,
+        token_account: ctx.accounts.holder_token_account.key(),
+        holder: ctx.accounts.holder_token_account.owner,
+        reason_hash,
+        thawed_by: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Thawed token account {} of holder {}",
+        ctx.accounts.holder_token_account.key(),
+        ctx.accounts.holder_token_account.owner
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// COMPLIANCE AUTHORITY
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SetComplianceAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Delegate holder freezes to a compliance officer (the property authority keeps the power too)
+pub fn handler_set_compliance_authority(
+    ctx: Context<SetComplianceAuthority>,
+    compliance_authority: Pubkey,
+) -> Result<()> {
+    let property_state = &mut ctx.accounts.property_state;
+
+    property_state.compliance_authority = compliance_authority;
+    property_state.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(ComplianceAuthorityUpdated {
+        mint: ctx.accounts.mint.key(),
+        compliance_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Property {} compliance authority: {}",
+        property_state.property_name,
+        compliance_authority
+    );
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+/// Trusted Issuer Registry - which Hub Credential issuers the program accepts
+///
+/// Credentials are only honoured when their issuer is registered here and not
+/// suspended. Properties can further narrow the set via set_accepted_issuers.
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+// ============================================================================
+// INITIALIZE REGISTRY
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeIssuerRegistry<'info> {
+    /// Protocol admin, becomes the registry admin (pays for the account)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program config naming the protocol admin
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ RwaError::Unauthorized,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// IssuerRegistry singleton PDA
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + IssuerRegistry::INIT_SPACE,
+        seeds = [ISSUER_REGISTRY_SEED],
+        bump,
+    )]
+    pub issuer_registry: Box<Account<'info, IssuerRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the issuer registry with the protocol admin as registry admin
+pub fn handler_initialize_issuer_registry(ctx: Context<InitializeIssuerRegistry>) -> Result<()> {
+    let issuer_registry = &mut ctx.accounts.issuer_registry;
+    issuer_registry.admin = ctx.accounts.admin.key();
+    issuer_registry.issuers = Vec::new();
+    issuer_registry.bump = ctx.bumps.issuer_registry;
+
+    msg!("Issuer registry initialized, admin: {}", issuer_registry.admin);
+
+    Ok(())
+}
+
+// ============================================================================
+// ADD / REMOVE / SUSPEND ISSUERS, ROTATE ADMIN
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateIssuerRegistry<'info> {
+    /// Registry admin
+    pub admin: Signer<'info>,
+
+    /// IssuerRegistry singleton PDA
+    #[account(
+        mut,
+        seeds = [ISSUER_REGISTRY_SEED],
+        bump = issuer_registry.bump,
+        has_one = admin @ RwaError::Unauthorized,
+    )]
+    pub issuer_registry: Box<Account<'info, IssuerRegistry>>,
+}
+
+/// Register a trusted issuer
+pub fn handler_add_trusted_issuer(ctx: Context<UpdateIssuerRegistry>, issuer: Pubkey) -> Result<()> {
+    let issuer_registry = &mut ctx.accounts.issuer_registry;
+
+    require!(
+        issuer_registry.find(&issuer).is_none(),
+        RwaError::IssuerAlreadyRegistered
+    );
+    require!(
+        issuer_registry.issuers.len() < MAX_TRUSTED_ISSUERS,
+        RwaError::IssuerRegistryFull
+    );
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    issuer_registry.issuers.push(TrustedIssuer {
+        issuer,
+        is_suspended: false,
+        added_at: timestamp,
+    });
+
+    emit!(TrustedIssuerAdded {
+        issuer,
+        admin: ctx.accounts.admin.key(),
+        timestamp,
+    });
+
+    msg!("Trusted issuer added: {}", issuer);
+
+    Ok(())
+}
+
+/// Remove an issuer from the registry
+pub fn handler_remove_trusted_issuer(ctx: Context<UpdateIssuerRegistry>, issuer: Pubkey) -> Result<()> {
+    let issuer_registry = &mut ctx.accounts.issuer_registry;
+
+    let len_before = issuer_registry.issuers.len();
+    issuer_registry.issuers.retain(|entry| entry.issuer != issuer);
+    require!(
+        issuer_registry.issuers.len() < len_before,
+        RwaError::IssuerNotTrusted
+    );
+
+    emit!(TrustedIssuerRemoved {
+        issuer,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Trusted issuer removed: {}", issuer);
+
+    Ok(())
+}
+
+/// Suspend or reinstate a registered issuer
+pub fn handler_suspend_trusted_issuer(
+    ctx: Context<UpdateIssuerRegistry>,
+    issuer: Pubkey,
+    is_suspended: bool,
+) -> Result<()> {
+    let entry = ctx
+        .accounts
+        .issuer_registry
+        .issuers
+        .iter_mut()
+        .find(|entry| entry.issuer == issuer)
+        .ok_or(RwaError::IssuerNotTrusted)?;
+    entry.is_suspended = is_suspended;
+
+    emit!(TrustedIssuerSuspended {
+        issuer,
+        is_suspended,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Trusted issuer {} {}",
+        issuer,
+        if is_suspended { "SUSPENDED" } else { "REINSTATED" }
+    );
+
+    Ok(())
+}
+
+/// Hand the registry over to a new admin
+///
+/// The registry admin is independent of the program config admin, so rotate
+/// it alongside update_config's new_admin to keep both with the same key.
+pub fn handler_set_registry_admin(ctx: Context<UpdateIssuerRegistry>, new_admin: Pubkey) -> Result<()> {
+    let issuer_registry = &mut ctx.accounts.issuer_registry;
+    let previous_admin = issuer_registry.admin;
+    issuer_registry.admin = new_admin;
+
+    emit!(IssuerRegistryAdminUpdated {
+        previous_admin,
+        new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Issuer registry admin changed from {} to {}", previous_admin, new_admin);
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+/// Extend or release holder lock-ups
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+#[derive(Accounts)]
+pub struct UpdateHolderLockup<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Holder's lock-up
+    #[account(
+        mut,
+        seeds = [HOLDER_LOCKUP_SEED, mint.key().as_ref(), holder_lockup.owner.as_ref()],
+        bump = holder_lockup.bump,
+    )]
+    pub holder_lockup: Box<Account<'info, HolderLockup>>,
+}
+
+// ============================================================================
+// EXTEND LOCKUP
+// ============================================================================
+
+/// Handler for extend_lockup instruction
+///
+/// Tokens still locked are re-locked under the later schedule; tokens that
+/// have already vested stay free.
+pub fn handler_extend_lockup(
+    ctx: Context<UpdateHolderLockup>,
+    lockup_until: i64,
+    vesting_end: i64,
+) -> Result<()> {
+    let holder_lockup = &mut ctx.accounts.holder_lockup;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        lockup_until >= holder_lockup.lockup_until && vesting_end >= holder_lockup.vesting_end,
+        RwaError::LockupCannotBeShortened
+    );
+    let schedule = LockupSchedule {
+        lockup_until,
+        vesting_end,
+    };
+    schedule.validate(now)?;
+    holder_lockup.add(0, schedule, now)?;
+
+    emit!(LockupExtended {
+        mint: holder_lockup.mint,
+        holder: holder_lockup.owner,
+        locked_amount: holder_lockup.amount,
+        lockup_until: holder_lockup.lockup_until,
+        vesting_end: holder_lockup.vesting_end,
+        timestamp: now,
+    });
+
+    msg!(
+        "Lock-up of {} extended: {} tokens until {}, vesting until {}",
+        holder_lockup.owner,
+        holder_lockup.amount,
+        holder_lockup.lockup_until,
+        holder_lockup.vesting_end
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// RELEASE LOCKUP
+// ============================================================================
+
+/// Handler for release_lockup instruction
+///
+/// Closes the lock-up, freeing every token it still held, and returns its
+/// rent to the authority.
+pub fn handler_release_lockup(ctx: Context<UpdateHolderLockup>) -> Result<()> {
+    let holder_lockup = &ctx.accounts.holder_lockup;
+    let now = Clock::get()?.unix_timestamp;
+    let released_amount = holder_lockup.locked_amount(now)?;
+
+    emit!(LockupReleased {
+        mint: holder_lockup.mint,
+        holder: holder_lockup.owner,
+        released_amount,
+        timestamp: now,
+    });
+
+    msg!(
+        "Lock-up of {} released: {} tokens unlocked",
+        holder_lockup.owner,
+        released_amount
+    );
+
+    ctx.accounts
+        .holder_lockup
+        .close(ctx.accounts.authority.to_account_info())
+}
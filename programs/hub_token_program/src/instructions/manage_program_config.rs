@@ -0,0 +1,170 @@
+/// Program Config - protocol-wide settings shared by every property
+///
+/// Stores the Hub Credential program ID, protocol admin, fee recipient and
+/// global pause flag in a singleton PDA so one binary serves every cluster.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+// ============================================================================
+// INITIALIZE CONFIG
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    /// Program upgrade authority, becomes the protocol admin (pays for the account)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// This program's ProgramData account, naming its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ RwaError::Unauthorized,
+    )]
+    pub program_data: Box<Account<'info, ProgramData>>,
+
+    /// ProgramConfig singleton PDA
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the program config with the program upgrade authority as protocol admin
+///
+/// Only the upgrade authority may create it, so nobody can front-run the
+/// deployment and point the config at a credential program they control.
+pub fn handler_initialize_config(
+    ctx: Context<InitializeConfig>,
+    credential_program_id: Pubkey,
+    fee_recipient: Pubkey,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.admin = ctx.accounts.admin.key();
+    program_config.credential_program_id = credential_program_id;
+    program_config.previous_credential_program_id = credential_program_id;
+    program_config.fee_recipient = fee_recipient;
+    program_config.is_paused = false;
+    program_config.pause_reason = PauseReason::Unspecified;
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+    program_config.bump = ctx.bumps.program_config;
+
+    emit_config_updated(program_config)?;
+
+    msg!("Program config initialized, admin: {}", program_config.admin);
+    msg!("Hub Credential Program: {}", credential_program_id);
+
+    Ok(())
+}
+
+// ============================================================================
+// UPDATE CONFIG
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// Current protocol admin
+    pub admin: Signer<'info>,
+
+    /// ProgramConfig singleton PDA
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ RwaError::Unauthorized,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+}
+
+/// Update any subset of the program config fields (pausing goes through set_protocol_pause)
+pub fn handler_update_config(
+    ctx: Context<UpdateConfig>,
+    new_admin: Option<Pubkey>,
+    credential_program_id: Option<Pubkey>,
+    fee_recipient: Option<Pubkey>,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+
+    if let Some(new_admin) = new_admin {
+        program_config.admin = new_admin;
+    }
+    if let Some(credential_program_id) = credential_program_id {
+        // The replaced program stays accepted until properties have rewritten
+        // their meta lists; passing the current program again closes that window
+        program_config.previous_credential_program_id = program_config.credential_program_id;
+        program_config.credential_program_id = credential_program_id;
+    }
+    if let Some(fee_recipient) = fee_recipient {
+        program_config.fee_recipient = fee_recipient;
+    }
+    program_config.updated_at = Clock::get()?.unix_timestamp;
+
+    emit_config_updated(program_config)?;
+
+    msg!("Program config updated by {}", ctx.accounts.admin.key());
+
+    Ok(())
+}
+
+// ============================================================================
+// SET PROTOCOL PAUSE
+// ============================================================================
+
+/// Pause or unpause the whole protocol (shares the admin check with UpdateConfig)
+pub fn handler_set_protocol_pause(
+    ctx: Context<UpdateConfig>,
+    is_paused: bool,
+    reason: PauseReason,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    program_config.is_paused = is_paused;
+    program_config.pause_reason = reason;
+    program_config.updated_at = timestamp;
+
+    let admin = ctx.accounts.admin.key();
+    if is_paused {
+        emit!(ProtocolPaused {
+            admin,
+            reason,
+            timestamp,
+        });
+    } else {
+        emit!(ProtocolUnpaused {
+            admin,
+            reason,
+            timestamp,
+        });
+    }
+
+    msg!(
+        "Protocol {} by {} (reason: {:?})",
+        if is_paused { "PAUSED" } else { "UNPAUSED" },
+        admin,
+        reason
+    );
+
+    Ok(())
+}
+
+fn emit_config_updated(program_config: &ProgramConfig) -> Result<()> {
+    emit!(ProgramConfigUpdated {
+        admin: program_config.admin,
+        credential_program_id: program_config.credential_program_id,
+        previous_credential_program_id: program_config.previous_credential_program_id,
+        fee_recipient: program_config.fee_recipient,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
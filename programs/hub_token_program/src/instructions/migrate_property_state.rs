@@ -0,0 +1,118 @@
+/// Bring a PropertyState created by the first release to the current layout
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::*, error::RwaError, events::*, state::*, utils::*};
+
+#[derive(Accounts)]
+pub struct MigratePropertyState<'info> {
+    /// Property authority recorded in the legacy account, pays for the growth
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA in the legacy layout
+    /// CHECK: Owner, discriminator and layout are checked in the handler
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub property_state: UncheckedAccount<'info>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for migrate_property_state instruction
+pub fn handler(ctx: Context<MigratePropertyState>) -> Result<()> {
+    let property_state = ctx.accounts.property_state.to_account_info();
+    require_keys_eq!(
+        *property_state.owner,
+        crate::ID,
+        RwaError::PropertyStateNotMigratable
+    );
+
+    let legacy = {
+        let data = property_state.try_borrow_data()?;
+        require!(
+            data.len() == 8 + LegacyPropertyState::INIT_SPACE
+                && data[..8] == PropertyState::DISCRIMINATOR,
+            RwaError::PropertyStateNotMigratable
+        );
+        LegacyPropertyState::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(
+        legacy.authority,
+        ctx.accounts.authority.key(),
+        RwaError::Unauthorized
+    );
+    require_keys_eq!(legacy.mint, ctx.accounts.mint.key(), RwaError::InvalidMint);
+
+    let size = 8 + PropertyState::INIT_SPACE;
+    let shortfall = Rent::get()?
+        .minimum_balance(size)
+        .saturating_sub(property_state.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: property_state.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    property_state.realloc(size, true)?;
+
+    // Appended fields get the defaults create_property_mint uses. No
+    // HolderRewards existed before, so holders are tracked from zero.
+    let migrated = PropertyState {
+        authority: legacy.authority,
+        mint: legacy.mint,
+        property_name: legacy.property_name,
+        property_symbol: legacy.property_symbol,
+        total_supply: legacy.total_supply,
+        circulating_supply: legacy.circulating_supply,
+        details: legacy.details,
+        is_active: legacy.is_active,
+        created_at: legacy.created_at,
+        updated_at: Clock::get()?.unix_timestamp,
+        bump: legacy.bump,
+        verify_sender: true,
+        allowed_credential_types: HubCredentialType::ALL_MASK,
+        accepted_issuers: Vec::new(),
+        kyc_grace_period_seconds: KYC_GRACE_PERIOD_SECONDS,
+        transfers_frozen: false,
+        freeze_reason: PauseReason::Unspecified,
+        compliance_authority: legacy.authority,
+        acc_revenue_per_token: 0,
+        total_revenue_distributed: 0,
+        next_revenue_epoch: 0,
+        max_tokens_per_holder: 0,
+        max_holders: 0,
+        holder_count: 0,
+        allowed_jurisdictions: Vec::new(),
+        denied_jurisdictions: Vec::new(),
+        tracked_supply: 0,
+    };
+    migrated.try_serialize(&mut &mut property_state.try_borrow_mut_data()?[..])?;
+
+    emit!(PropertyStateMigrated {
+        mint: migrated.mint,
+        authority: migrated.authority,
+        timestamp: migrated.updated_at,
+    });
+
+    msg!(
+        "Property {} migrated to the current PropertyState layout",
+        migrated.property_name
+    );
+
+    Ok(())
+}
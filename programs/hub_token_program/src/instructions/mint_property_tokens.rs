@@ -44,20 +44,68 @@ pub struct MintPropertyTokens<'info> {
 
     /// Hub Credential account for investor KYC
     /// CHECK: Will be verified using Hub Credential program
-    /// This account must be owned by HUB_CREDENTIAL_PROGRAM_ID and contain valid KYC credential
+    /// This account must be owned by the configured Hub Credential program and contain valid KYC credential
     pub investor_credential: UncheckedAccount<'info>,
 
+    /// Investor's recorded jurisdiction, checked when the property restricts countries
+    /// CHECK: Address and data checked during credential verification
+    pub investor_profile: UncheckedAccount<'info>,
+
+    /// Program config (Hub Credential program ID, protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Trusted issuer registry
+    #[account(
+        seeds = [ISSUER_REGISTRY_SEED],
+        bump = issuer_registry.bump,
+    )]
+    pub issuer_registry: Box<Account<'info, IssuerRegistry>>,
+
+    /// Investor's revenue accounting, created on their first mint
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + HolderRewards::INIT_SPACE,
+        seeds = [HOLDER_REWARDS_SEED, mint.key().as_ref(), investor.key().as_ref()],
+        bump,
+    )]
+    pub investor_rewards: Box<Account<'info, HolderRewards>>,
+
+    /// Investor's lock-up, required when minting with a lock-up schedule and
+    /// created by the first one. Without a schedule it may only be passed
+    /// once it exists, and is left untouched.
+    /// CHECK: Created or deserialized in the handler
+    #[account(
+        mut,
+        seeds = [HOLDER_LOCKUP_SEED, mint.key().as_ref(), investor.key().as_ref()],
+        bump,
+    )]
+    pub investor_lockup: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 /// Handler for mint_property_tokens instruction
-pub fn handler(ctx: Context<MintPropertyTokens>, amount: u64) -> Result<()> {
+///
+/// With a lock-up schedule the minted tokens are added to the investor's
+/// HolderLockup, which the transfer hook enforces.
+pub fn handler(
+    ctx: Context<MintPropertyTokens>,
+    amount: u64,
+    lockup: Option<LockupSchedule>,
+) -> Result<()> {
     let property_state = &mut ctx.accounts.property_state;
 
-    // 1. Verify property is active
+    // 1. Verify property is active and neither paused nor frozen
     require!(property_state.is_active, RwaError::PropertyNotActive);
+    ctx.accounts.program_config.require_not_paused()?;
+    property_state.require_not_frozen()?;
 
     // 2. Verify minting won't exceed total supply
     require!(
@@ -66,11 +114,19 @@ pub fn handler(ctx: Context<MintPropertyTokens>, amount: u64) -> Result<()> {
     );
 
     // 3. Verify Hub Credential for KYC compliance
-    verify_hub_credential(
+    // New mints are blocked while the credential is in its grace period
+    let standing = verify_hub_credential(
         &ctx.accounts.investor_credential.to_account_info(),
+        &ctx.accounts.investor_profile.to_account_info(),
         &ctx.accounts.investor.key(),
-        &ctx.accounts.mint.key(),
+        &ctx.accounts.program_config,
+        property_state,
+        &ctx.accounts.issuer_registry,
     )?;
+    require!(
+        standing == CredentialStanding::Valid,
+        RwaError::CredentialExpired
+    );
 
     msg!(
         "Hub Credential verification passed for investor: {}",
@@ -98,11 +154,76 @@ pub fn handler(ctx: Context<MintPropertyTokens>, amount: u64) -> Result<()> {
 
     token_2022::mint_to(cpi_context, amount)?;
 
-    // 5. Update circulating supply
+    // 5. Settle the investor's accrued revenue at the old balance and
+    //    enforce the holding limits
+    //    (a new account picks up tokens transferred in before it existed)
+    let investor_rewards = &mut ctx.accounts.investor_rewards;
+    if investor_rewards.owner == Pubkey::default() {
+        property_state.start_tracking_holder(
+            investor_rewards,
+            ctx.accounts.investor.key(),
+            ctx.accounts.investor_token_account.amount,
+            ctx.bumps.investor_rewards,
+        )?;
+    }
+    property_state.apply_holder_balance_change(investor_rewards, true, amount)?;
+
+    // 6. Lock up the minted tokens
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(schedule) = lockup {
+        schedule.validate(now)?;
+        let lockup_info = ctx
+            .accounts
+            .investor_lockup
+            .as_ref()
+            .ok_or(RwaError::MissingLockupAccount)?
+            .to_account_info();
+        let investor_key = ctx.accounts.investor.key();
+        let bump = ctx.bumps.investor_lockup.unwrap_or_default();
+        let mut investor_lockup = if lockup_info.owner == &crate::ID {
+            HolderLockup::try_deserialize(&mut &lockup_info.try_borrow_data()?[..])?
+        } else {
+            create_program_account(
+                &ctx.accounts.authority.to_account_info(),
+                &lockup_info,
+                &ctx.accounts.system_program.to_account_info(),
+                &[HOLDER_LOCKUP_SEED, mint_key.as_ref(), investor_key.as_ref(), &[bump]],
+                8 + HolderLockup::INIT_SPACE,
+            )?;
+            HolderLockup {
+                mint: mint_key,
+                owner: investor_key,
+                amount: 0,
+                lockup_until: 0,
+                vesting_end: 0,
+                bump,
+            }
+        };
+        investor_lockup.add(amount, schedule, now)?;
+        investor_lockup.try_serialize(&mut &mut lockup_info.try_borrow_mut_data()?[..])?;
+
+        emit!(TokensLockedUp {
+            mint: mint_key,
+            holder: investor_lockup.owner,
+            amount,
+            locked_amount: investor_lockup.amount,
+            lockup_until: investor_lockup.lockup_until,
+            vesting_end: investor_lockup.vesting_end,
+            timestamp: now,
+        });
+    } else if let Some(investor_lockup) = &ctx.accounts.investor_lockup {
+        require_keys_eq!(
+            *investor_lockup.owner,
+            crate::ID,
+            RwaError::InvalidHolderLockup
+        );
+    }
+
+    // 7. Update circulating supply
     property_state.circulating_supply += amount;
-    property_state.updated_at = Clock::get()?.unix_timestamp;
+    property_state.updated_at = now;
 
-    // 6. Emit tokens minted event
+    // 8. Emit tokens minted event
     emit!(TokensMinted {
         mint: ctx.accounts.mint.key(),
         investor: ctx.accounts.investor.key(),
@@ -6,13 +6,47 @@ pub mod mint_property_tokens;
 pub mod burn_property_tokens;
 pub mod update_property_details;
 pub mod toggle_property_status;
+pub mod migrate_property_state;
+pub mod set_sender_verification;
+pub mod set_allowed_credential_types;
+pub mod set_accepted_issuers;
+pub mod set_jurisdictions;
+pub mod set_investor_jurisdiction;
+pub mod set_kyc_grace_period;
+pub mod set_holding_limits;
+pub mod manage_lockup;
+pub mod set_transfer_freeze;
+pub mod holder_freeze;
+pub mod force_transfer;
+pub mod manage_issuer_registry;
+pub mod manage_program_config;
 pub mod transfer_hook;
 pub mod revenue_vault;
+pub mod continuous_revenue;
+pub mod redemption;
+pub mod primary_offering;
 
 pub use create_property_mint::*;
 pub use mint_property_tokens::*;
 pub use burn_property_tokens::*;
 pub use update_property_details::*;
 pub use toggle_property_status::*;
+pub use migrate_property_state::*;
+pub use set_sender_verification::*;
+pub use set_allowed_credential_types::*;
+pub use set_accepted_issuers::*;
+pub use set_jurisdictions::*;
+pub use set_investor_jurisdiction::*;
+pub use set_kyc_grace_period::*;
+pub use set_holding_limits::*;
+pub use manage_lockup::*;
+pub use set_transfer_freeze::*;
+pub use holder_freeze::*;
+pub use force_transfer::*;
+pub use manage_issuer_registry::*;
+pub use manage_program_config::*;
 pub use transfer_hook::*;
 pub use revenue_vault::*;
+pub use continuous_revenue::*;
+pub use redemption::*;
+pub use primary_offering::*;
@@ -0,0 +1,883 @@
+/// Primary Offering - sell newly minted property tokens for a stablecoin
+///
+/// The authority opens an offering with a price, soft and hard caps, ticket
+/// limits and a subscription window. Investors subscribe by paying into an
+/// escrow token account owned by the Offering PDA, so no tokens or funds
+/// change hands until the offering is settled.
+///
+/// Once the window ends (or the hard cap is reached) the offering settles:
+/// - soft cap reached: subscribers are minted their tokens (KYC re-checked)
+///   and the authority receives the escrowed payments
+/// - soft cap missed: every subscriber can take their payment back
+///
+/// Settlement mints to subscribers passed in `remaining_accounts`, so large
+/// offerings are settled over several transactions. Subscriptions left
+/// unsettled past the settlement deadline can be refunded.
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_2022::{
+        self,
+        spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccountState},
+        MintTo, Token2022,
+    },
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{constants::*, error::RwaError, events::*, state::*, utils::*};
+
+// ============================================================================
+// CREATE OFFERING
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateOffering<'info> {
+    /// Property authority running the offering
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Offering PDA
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Offering::INIT_SPACE,
+        seeds = [OFFERING_SEED, property_state.key().as_ref()],
+        bump,
+    )]
+    pub offering: Box<Account<'info, Offering>>,
+
+    /// Stablecoin subscriptions are paid in
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Escrow token account PDA holding subscription payments, owned by the offering
+    #[account(
+        init,
+        payer = authority,
+        seeds = [OFFERING_ESCROW_SEED, offering.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = offering,
+        token::token_program = quote_token_program,
+    )]
+    pub offering_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Open a primary offering for a property
+pub fn handler_create_offering(ctx: Context<CreateOffering>, params: OfferingParams) -> Result<()> {
+    let OfferingParams {
+        price_per_token,
+        min_raise,
+        max_raise,
+        min_ticket,
+        max_ticket,
+        start_ts,
+        end_ts,
+    } = params;
+
+    let property_state = &ctx.accounts.property_state;
+    require!(property_state.is_active, RwaError::PropertyNotActive);
+    require!(
+        price_per_token > 0
+            && end_ts > start_ts
+            && min_raise <= max_raise
+            && min_ticket > 0
+            && min_ticket <= max_ticket
+            && min_ticket <= max_raise,
+        RwaError::InvalidOfferingParameters
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let offering = &mut ctx.accounts.offering;
+    offering.property_state = property_state.key();
+    offering.authority = ctx.accounts.authority.key();
+    offering.quote_mint = ctx.accounts.quote_mint.key();
+    offering.price_per_token = price_per_token;
+    offering.min_raise = min_raise;
+    offering.max_raise = max_raise;
+    offering.min_ticket = min_ticket;
+    offering.max_ticket = max_ticket;
+    offering.start_ts = start_ts;
+    offering.end_ts = end_ts;
+    offering.total_raised = 0;
+    offering.total_settled = 0;
+    offering.total_refunded = 0;
+    offering.tokens_issued = 0;
+    offering.subscriber_count = 0;
+    offering.status = OfferingStatus::Open;
+    offering.created_at = now;
+    offering.bump = ctx.bumps.offering;
+
+    // A fully subscribed offering must fit in the remaining supply
+    let max_tokens = offering.tokens_for(max_raise, ctx.accounts.mint.decimals)?;
+    require!(max_tokens > 0, RwaError::InvalidOfferingParameters);
+    require!(
+        property_state
+            .circulating_supply
+            .checked_add(max_tokens)
+            .ok_or(RwaError::MathOverflow)?
+            <= property_state.total_supply,
+        RwaError::ExceedsMaxSupply
+    );
+
+    emit!(OfferingCreated {
+        mint: ctx.accounts.mint.key(),
+        offering: offering.key(),
+        quote_mint: offering.quote_mint,
+        price_per_token,
+        min_raise,
+        max_raise,
+        min_ticket,
+        max_ticket,
+        start_ts,
+        end_ts,
+        timestamp: now,
+    });
+
+    msg!(
+        "Offering created: {} per token, raising {} to {}",
+        price_per_token,
+        min_raise,
+        max_raise
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// SUBSCRIBE
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    /// Investor subscribing (must have valid Hub Credential)
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    /// PropertyState PDA (tracked supply of a new HolderRewards)
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Offering PDA
+    #[account(
+        mut,
+        seeds = [OFFERING_SEED, property_state.key().as_ref()],
+        bump = offering.bump,
+    )]
+    pub offering: Box<Account<'info, Offering>>,
+
+    /// Investor's subscription, created on their first subscription
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [SUBSCRIPTION_SEED, offering.key().as_ref(), investor.key().as_ref()],
+        bump,
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+
+    /// Stablecoin of the offering
+    #[account(
+        address = offering.quote_mint @ RwaError::InvalidPaymentAccounts,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Offering escrow token account
+    #[account(
+        mut,
+        seeds = [OFFERING_ESCROW_SEED, offering.key().as_ref()],
+        bump,
+    )]
+    pub offering_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Investor's stablecoin account paying the subscription
+    #[account(
+        mut,
+        constraint = investor_quote_account.owner == investor.key() @ RwaError::InvalidPaymentAccounts,
+        constraint = investor_quote_account.mint == quote_mint.key() @ RwaError::InvalidPaymentAccounts,
+    )]
+    pub investor_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Hub Credential account for investor KYC
+    /// CHECK: Verified using Hub Credential program
+    pub investor_credential: UncheckedAccount<'info>,
+
+    /// Investor's recorded jurisdiction, checked when the property restricts countries
+    /// CHECK: Address and data checked during credential verification
+    pub investor_profile: UncheckedAccount<'info>,
+
+    /// Program config (Hub Credential program ID, protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Trusted issuer registry
+    #[account(
+        seeds = [ISSUER_REGISTRY_SEED],
+        bump = issuer_registry.bump,
+    )]
+    pub issuer_registry: Box<Account<'info, IssuerRegistry>>,
+
+    /// Investor's revenue accounting, settled when their tokens are minted
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + HolderRewards::INIT_SPACE,
+        seeds = [HOLDER_REWARDS_SEED, mint.key().as_ref(), investor.key().as_ref()],
+        bump,
+    )]
+    pub investor_rewards: Box<Account<'info, HolderRewards>>,
+
+    /// Investor's property token ATA, which may not exist yet
+    /// CHECK: Address checked here, only read when creating investor_rewards
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &investor.key(),
+            &mint.key(),
+            &token_2022::ID,
+        ) @ RwaError::InvalidHolderRewards,
+    )]
+    pub investor_token_account: UncheckedAccount<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Escrow a subscription payment
+///
+/// An investor may subscribe several times; their running total must stay
+/// within the ticket limits.
+pub fn handler_subscribe(ctx: Context<Subscribe>, amount: u64) -> Result<()> {
+    require!(amount > 0, RwaError::InvalidAmount);
+    ctx.accounts.program_config.require_not_paused()?;
+    let property_state = &ctx.accounts.property_state;
+    require!(property_state.is_active, RwaError::PropertyNotActive);
+    property_state.require_not_frozen()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.offering.is_open(now),
+        RwaError::OfferingNotOpen
+    );
+
+    // Only investors who could be minted to may subscribe
+    let standing = verify_hub_credential(
+        &ctx.accounts.investor_credential.to_account_info(),
+        &ctx.accounts.investor_profile.to_account_info(),
+        &ctx.accounts.investor.key(),
+        &ctx.accounts.program_config,
+        property_state,
+        &ctx.accounts.issuer_registry,
+    )?;
+    require!(
+        standing == CredentialStanding::Valid,
+        RwaError::CredentialExpired
+    );
+
+    // Escrow the payment; a transfer fee is withheld from what the escrow receives
+    let escrow_before = ctx.accounts.offering_escrow.amount;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.quote_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.investor_quote_account.to_account_info(),
+                mint: ctx.accounts.quote_mint.to_account_info(),
+                to: ctx.accounts.offering_escrow.to_account_info(),
+                authority: ctx.accounts.investor.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+    ctx.accounts.offering_escrow.reload()?;
+    let credited = ctx
+        .accounts
+        .offering_escrow
+        .amount
+        .checked_sub(escrow_before)
+        .ok_or(RwaError::MathOverflow)?;
+
+    let offering_key = ctx.accounts.offering.key();
+    let investor_key = ctx.accounts.investor.key();
+    let offering = &mut ctx.accounts.offering;
+    let subscription = &mut ctx.accounts.subscription;
+    if subscription.investor == Pubkey::default() {
+        subscription.offering = offering_key;
+        subscription.investor = investor_key;
+        subscription.created_at = now;
+        subscription.bump = ctx.bumps.subscription;
+        offering.subscriber_count = offering
+            .subscriber_count
+            .checked_add(1)
+            .ok_or(RwaError::MathOverflow)?;
+    }
+
+    subscription.amount = subscription
+        .amount
+        .checked_add(credited)
+        .ok_or(RwaError::MathOverflow)?;
+    require!(
+        subscription.amount >= offering.min_ticket && subscription.amount <= offering.max_ticket,
+        RwaError::InvalidTicketSize
+    );
+
+    offering.total_raised = offering
+        .total_raised
+        .checked_add(credited)
+        .ok_or(RwaError::MathOverflow)?;
+    require!(
+        offering.total_raised <= offering.max_raise,
+        RwaError::OfferingCapExceeded
+    );
+
+    // A new account picks up tokens transferred in before it existed
+    let investor_rewards = &mut ctx.accounts.investor_rewards;
+    if investor_rewards.owner == Pubkey::default() {
+        ctx.accounts.property_state.start_tracking_holder(
+            investor_rewards,
+            investor_key,
+            token_account_balance(&ctx.accounts.investor_token_account)?,
+            ctx.bumps.investor_rewards,
+        )?;
+    }
+
+    emit!(OfferingSubscribed {
+        mint: ctx.accounts.mint.key(),
+        offering: offering_key,
+        investor: investor_key,
+        amount: credited,
+        subscription_total: subscription.amount,
+        total_raised: offering.total_raised,
+        timestamp: now,
+    });
+
+    msg!(
+        "Subscribed {} by {} (raised: {}/{})",
+        credited,
+        investor_key,
+        offering.total_raised,
+        offering.max_raise
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// SETTLE OFFERING
+// ============================================================================
+
+/// Settles a closed offering
+///
+/// When the offering succeeded, each subscription to issue is passed in
+/// `remaining_accounts` as a group of five:
+/// (subscription, investor's property token ATA, investor's Hub Credential,
+/// investor's InvestorProfile, investor's HolderRewards), all writable
+/// except the credential and profile.
+#[derive(Accounts)]
+pub struct SettleOffering<'info> {
+    /// Authority who created the offering, receives the proceeds
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Offering PDA
+    #[account(
+        mut,
+        seeds = [OFFERING_SEED, property_state.key().as_ref()],
+        bump = offering.bump,
+        has_one = authority @ RwaError::Unauthorized,
+    )]
+    pub offering: Box<Account<'info, Offering>>,
+
+    /// Stablecoin of the offering
+    #[account(
+        address = offering.quote_mint @ RwaError::InvalidPaymentAccounts,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Offering escrow token account
+    #[account(
+        mut,
+        seeds = [OFFERING_ESCROW_SEED, offering.key().as_ref()],
+        bump,
+    )]
+    pub offering_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Authority's stablecoin account receiving the proceeds
+    #[account(
+        mut,
+        constraint = authority_quote_account.owner == authority.key() @ RwaError::InvalidPaymentAccounts,
+        constraint = authority_quote_account.mint == quote_mint.key() @ RwaError::InvalidPaymentAccounts,
+    )]
+    pub authority_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Program config (Hub Credential program ID, protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Trusted issuer registry
+    #[account(
+        seeds = [ISSUER_REGISTRY_SEED],
+        bump = issuer_registry.bump,
+    )]
+    pub issuer_registry: Box<Account<'info, IssuerRegistry>>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Decide the offering's outcome and, if it succeeded, issue the passed subscriptions
+///
+/// Can be called again to issue further subscriptions until the settlement
+/// deadline. A subscription that can no longer be issued (see
+/// `check_issuable`) is not minted to and can be refunded instead, while a
+/// credential or profile account that does not belong to the subscriber
+/// fails the settlement.
+pub fn handler_settle_offering<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleOffering<'info>>,
+) -> Result<()> {
+    ctx.accounts.program_config.require_not_paused()?;
+    require!(
+        ctx.accounts.property_state.is_active,
+        RwaError::PropertyNotActive
+    );
+    ctx.accounts.property_state.require_not_frozen()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let offering = &mut ctx.accounts.offering;
+    require!(
+        !offering.settlement_expired(now),
+        RwaError::SettlementDeadlinePassed
+    );
+    if offering.status == OfferingStatus::Open {
+        require!(offering.is_closed(now), RwaError::OfferingStillOpen);
+        offering.status = if offering.total_raised >= offering.min_raise {
+            OfferingStatus::Succeeded
+        } else {
+            OfferingStatus::Failed
+        };
+    }
+    let status = offering.status;
+
+    require!(
+        ctx.remaining_accounts.len() % 5 == 0
+            && (status == OfferingStatus::Succeeded || ctx.remaining_accounts.is_empty()),
+        RwaError::InvalidSettlementAccounts
+    );
+
+    let offering_key = ctx.accounts.offering.key();
+    let mint_key = ctx.accounts.mint.key();
+    let decimals = ctx.accounts.mint.decimals;
+    let property_seeds = &[
+        PROPERTY_STATE_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.property_state.bump],
+    ];
+
+    let mut issued: u32 = 0;
+    let mut rejected: u32 = 0;
+    let mut tokens_issued: u64 = 0;
+    let mut proceeds: u64 = 0;
+
+    for group in ctx.remaining_accounts.chunks(5) {
+        let (subscription_info, token_account_info, credential_info, profile_info, rewards_info) =
+            (&group[0], &group[1], &group[2], &group[3], &group[4]);
+
+        let mut subscription = Account::<Subscription>::try_from(subscription_info)?;
+        require_keys_eq!(
+            subscription.offering,
+            offering_key,
+            RwaError::InvalidSettlementAccounts
+        );
+        require!(!subscription.settled, RwaError::InvalidSettlementAccounts);
+        let investor = subscription.investor;
+
+        require_keys_eq!(
+            token_account_info.key(),
+            get_associated_token_address_with_program_id(&investor, &mint_key, &token_2022::ID),
+            RwaError::InvalidSettlementAccounts
+        );
+        let mut investor_rewards = Account::<HolderRewards>::try_from(rewards_info)?;
+        require!(
+            investor_rewards.mint == mint_key && investor_rewards.owner == investor,
+            RwaError::InvalidHolderRewards
+        );
+
+        require_subscriber_accounts(
+            &ctx.accounts.program_config,
+            &investor,
+            credential_info,
+            profile_info,
+        )?;
+
+        subscription.settled = true;
+        let tokens = ctx
+            .accounts
+            .offering
+            .tokens_for(subscription.amount, decimals)?;
+        let issuable = check_issuable(
+            ctx.accounts,
+            credential_info,
+            profile_info,
+            token_account_info,
+            &investor_rewards,
+            tokens,
+        );
+        if let Err(error) = issuable {
+            msg!("Subscription rejected for {}: {}", investor, error);
+            subscription.rejected = true;
+            rejected += 1;
+        } else {
+            let property_state = &mut ctx.accounts.property_state;
+            let circulating_supply = property_state
+                .circulating_supply
+                .checked_add(tokens)
+                .ok_or(RwaError::MathOverflow)?;
+            require!(
+                circulating_supply <= property_state.total_supply,
+                RwaError::ExceedsMaxSupply
+            );
+
+            token_2022::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: token_account_info.clone(),
+                        authority: property_state.to_account_info(),
+                    },
+                    &[&property_seeds[..]],
+                ),
+                tokens,
+            )?;
+
+            // Settle the investor's accrued revenue at the old balance and
+            // enforce the holding limits
+            property_state.apply_holder_balance_change(&mut investor_rewards, true, tokens)?;
+            property_state.circulating_supply = circulating_supply;
+
+            subscription.tokens_issued = tokens;
+            issued += 1;
+            tokens_issued = tokens_issued
+                .checked_add(tokens)
+                .ok_or(RwaError::MathOverflow)?;
+            proceeds = proceeds
+                .checked_add(subscription.amount)
+                .ok_or(RwaError::MathOverflow)?;
+
+            emit!(TokensMinted {
+                mint: mint_key,
+                investor,
+                amount: tokens,
+                circulating_supply,
+                timestamp: now,
+            });
+        }
+
+        subscription.exit(ctx.program_id)?;
+        investor_rewards.exit(ctx.program_id)?;
+    }
+
+    // Release the issued subscriptions' payments to the authority
+    if proceeds > 0 {
+        let property_state_key = ctx.accounts.property_state.key();
+        let offering_seeds = &[
+            OFFERING_SEED,
+            property_state_key.as_ref(),
+            &[ctx.accounts.offering.bump],
+        ];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.quote_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.offering_escrow.to_account_info(),
+                    mint: ctx.accounts.quote_mint.to_account_info(),
+                    to: ctx.accounts.authority_quote_account.to_account_info(),
+                    authority: ctx.accounts.offering.to_account_info(),
+                },
+                &[&offering_seeds[..]],
+            ),
+            proceeds,
+            ctx.accounts.quote_mint.decimals,
+        )?;
+    }
+
+    let offering = &mut ctx.accounts.offering;
+    offering.total_settled = offering
+        .total_settled
+        .checked_add(proceeds)
+        .ok_or(RwaError::MathOverflow)?;
+    offering.tokens_issued = offering
+        .tokens_issued
+        .checked_add(tokens_issued)
+        .ok_or(RwaError::MathOverflow)?;
+    ctx.accounts.property_state.updated_at = now;
+
+    emit!(OfferingSettled {
+        mint: mint_key,
+        offering: offering_key,
+        status,
+        subscriptions_issued: issued,
+        subscriptions_rejected: rejected,
+        tokens_issued,
+        proceeds,
+        timestamp: now,
+    });
+
+    msg!(
+        "Offering settled ({:?}): {} issued, {} rejected, {} tokens for {}",
+        status,
+        issued,
+        rejected,
+        tokens_issued,
+        proceeds
+    );
+
+    Ok(())
+}
+
+/// Fails the settlement when a subscriber's credential or profile is not the
+/// account derived for them, or its data cannot be read
+///
+/// These are mistakes in the passed accounts, not reasons to reject the
+/// subscriber. A credential account that does not exist is left to
+/// check_issuable.
+fn require_subscriber_accounts(
+    program_config: &ProgramConfig,
+    investor: &Pubkey,
+    credential_info: &AccountInfo,
+    profile_info: &AccountInfo,
+) -> Result<()> {
+    let derived = [
+        program_config.credential_program_id,
+        program_config.previous_credential_program_id,
+    ]
+    .iter()
+    .any(|program_id| derive_hub_credential_pda(investor, program_id).0 == credential_info.key());
+    require!(derived, RwaError::InvalidSettlementAccounts);
+    if program_config.accepts_credential_program(credential_info.owner) {
+        HubCredential::parse(&credential_info.try_borrow_data()?)
+            .map_err(|_| RwaError::InvalidSettlementAccounts)?;
+    }
+
+    // Checks the profile address and reads it if it exists
+    investor_country(profile_info, investor)?;
+    Ok(())
+}
+
+/// Checks that a subscription can still be issued `tokens`
+///
+/// A subscriber failing any check is rejected rather than failing the whole
+/// settlement: their credential must be valid, their token account must exist
+/// and not be frozen, and the tokens must fit the holding limits.
+fn check_issuable(
+    accounts: &SettleOffering,
+    credential_info: &AccountInfo,
+    profile_info: &AccountInfo,
+    token_account_info: &AccountInfo,
+    investor_rewards: &HolderRewards,
+    tokens: u64,
+) -> Result<()> {
+    let standing = verify_hub_credential(
+        credential_info,
+        profile_info,
+        &investor_rewards.owner,
+        &accounts.program_config,
+        &accounts.property_state,
+        &accounts.issuer_registry,
+    )?;
+    require!(
+        standing == CredentialStanding::Valid,
+        RwaError::CredentialExpired
+    );
+
+    require_keys_eq!(
+        *token_account_info.owner,
+        token_2022::ID,
+        RwaError::SubscriberAccountUnavailable
+    );
+    let data = token_account_info.try_borrow_data()?;
+    let token_account = StateWithExtensions::<TokenAccountState>::unpack(&data)
+        .map_err(|_| RwaError::SubscriberAccountUnavailable)?;
+    require!(
+        !token_account.base.is_frozen(),
+        RwaError::SubscriberAccountUnavailable
+    );
+
+    let new_balance = investor_rewards
+        .balance
+        .checked_add(tokens)
+        .ok_or(RwaError::MathOverflow)?;
+    accounts
+        .property_state
+        .require_within_holding_limits(investor_rewards.balance, new_balance)
+}
+
+// ============================================================================
+// REFUND SUBSCRIPTION
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct RefundSubscription<'info> {
+    /// Investor taking their payment back
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account()]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// Offering PDA
+    #[account(
+        mut,
+        seeds = [OFFERING_SEED, property_state.key().as_ref()],
+        bump = offering.bump,
+    )]
+    pub offering: Box<Account<'info, Offering>>,
+
+    /// Investor's subscription, closed by the refund
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, offering.key().as_ref(), investor.key().as_ref()],
+        bump = subscription.bump,
+        close = investor,
+    )]
+    pub subscription: Box<Account<'info, Subscription>>,
+
+    /// Stablecoin of the offering
+    #[account(
+        address = offering.quote_mint @ RwaError::InvalidPaymentAccounts,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Offering escrow token account
+    #[account(
+        mut,
+        seeds = [OFFERING_ESCROW_SEED, offering.key().as_ref()],
+        bump,
+    )]
+    pub offering_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Investor's stablecoin account receiving the refund
+    #[account(
+        mut,
+        constraint = investor_quote_account.owner == investor.key() @ RwaError::InvalidPaymentAccounts,
+        constraint = investor_quote_account.mint == quote_mint.key() @ RwaError::InvalidPaymentAccounts,
+    )]
+    pub investor_quote_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Program config (protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Return an escrowed payment when the offering failed, the subscription was
+/// rejected, or it was left unsettled past the settlement deadline
+///
+/// Refunds of an offering that closed below its soft cap do not wait for
+/// settle_offering; the first refund marks it as failed.
+pub fn handler_refund_subscription(ctx: Context<RefundSubscription>) -> Result<()> {
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let offering = &mut ctx.accounts.offering;
+    let subscription = &ctx.accounts.subscription;
+    require!(
+        offering.has_failed(now)
+            || subscription.rejected
+            || (!subscription.settled && offering.settlement_expired(now)),
+        RwaError::SubscriptionNotRefundable
+    );
+    if offering.status == OfferingStatus::Open {
+        offering.status = OfferingStatus::Failed;
+    }
+
+    let amount = subscription.amount;
+    let property_state_key = ctx.accounts.property_state.key();
+    let seeds = &[
+        OFFERING_SEED,
+        property_state_key.as_ref(),
+        &[offering.bump],
+    ];
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.quote_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.offering_escrow.to_account_info(),
+                mint: ctx.accounts.quote_mint.to_account_info(),
+                to: ctx.accounts.investor_quote_account.to_account_info(),
+                authority: offering.to_account_info(),
+            },
+            &[&seeds[..]],
+        ),
+        amount,
+        ctx.accounts.quote_mint.decimals,
+    )?;
+
+    offering.total_refunded = offering
+        .total_refunded
+        .checked_add(amount)
+        .ok_or(RwaError::MathOverflow)?;
+
+    emit!(SubscriptionRefunded {
+        offering: offering.key(),
+        investor: ctx.accounts.investor.key(),
+        amount,
+        timestamp: now,
+    });
+
+    msg!(
+        "Subscription refunded: {} to {}",
+        amount,
+        ctx.accounts.investor.key()
+    );
+
+    Ok(())
+}
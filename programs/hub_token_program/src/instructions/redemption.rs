@@ -0,0 +1,549 @@
+/// Redemption Pool - burn property tokens in exchange for SOL or stablecoin
+///
+/// For liquidation events and building sales the authority funds a pool with
+/// a price per token, a window and a token cap. Holders redeem by burning
+/// tokens and are paid from the pool in the same instruction.
+///
+/// SOL pools keep the funds as lamports on the RedemptionPool account itself.
+/// Stablecoin pools keep them in a token account owned by the pool PDA; the
+/// optional payment accounts must then be passed.
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::{
+    token_2022::{self, Burn, Token2022},
+    token_interface::{
+        self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+/// Validated stablecoin accounts for a pool payment
+struct TokenPayment<'a, 'info> {
+    mint: &'a InterfaceAccount<'info, Mint>,
+    pool_account: &'a InterfaceAccount<'info, TokenAccount>,
+    counterparty_account: &'a InterfaceAccount<'info, TokenAccount>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Checks the optional payment accounts against the pool's payout currency
+///
+/// Returns None for SOL pools, which must not receive payment accounts.
+fn token_payment<'a, 'info>(
+    payment_mint: Option<Pubkey>,
+    pool: &Pubkey,
+    counterparty: &Pubkey,
+    mint: &'a Option<Box<InterfaceAccount<'info, Mint>>>,
+    pool_account: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    counterparty_account: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    token_program: &'a Option<Interface<'info, TokenInterface>>,
+) -> Result<Option<TokenPayment<'a, 'info>>> {
+    let Some(payment_mint) = payment_mint else {
+        require!(mint.is_none(), RwaError::InvalidPaymentAccounts);
+        return Ok(None);
+    };
+    let (Some(mint), Some(pool_account), Some(counterparty_account), Some(token_program)) =
+        (mint, pool_account, counterparty_account, token_program)
+    else {
+        return err!(RwaError::InvalidPaymentAccounts);
+    };
+
+    require_keys_eq!(mint.key(), payment_mint, RwaError::InvalidPaymentAccounts);
+    require_keys_eq!(*mint.to_account_info().owner, token_program.key(), RwaError::InvalidPaymentAccounts);
+    require_keys_eq!(pool_account.mint, payment_mint, RwaError::InvalidPaymentAccounts);
+    require_keys_eq!(pool_account.owner, *pool, RwaError::InvalidPaymentAccounts);
+    require_keys_eq!(counterparty_account.mint, payment_mint, RwaError::InvalidPaymentAccounts);
+    require_keys_eq!(counterparty_account.owner, *counterparty, RwaError::InvalidPaymentAccounts);
+
+    Ok(Some(TokenPayment {
+        mint,
+        pool_account,
+        counterparty_account,
+        token_program,
+    }))
+}
+
+// ============================================================================
+// CREATE REDEMPTION POOL
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CreateRedemptionPool<'info> {
+    /// Property authority funding the pool
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// RedemptionPool PDA (also holds the SOL for SOL pools)
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RedemptionPool::INIT_SPACE,
+        seeds = [REDEMPTION_POOL_SEED, property_state.key().as_ref()],
+        bump,
+    )]
+    pub redemption_pool: Box<Account<'info, RedemptionPool>>,
+
+    /// Stablecoin mint (omit for a SOL pool)
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Pool's stablecoin account, owned by the RedemptionPool PDA
+    #[account(mut)]
+    pub pool_payment_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Authority's stablecoin account funding the pool
+    #[account(mut)]
+    pub authority_payment_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create and fully fund the redemption pool for a property
+pub fn handler_create_redemption_pool(
+    ctx: Context<CreateRedemptionPool>,
+    price_per_token: u64,
+    start_ts: i64,
+    end_ts: i64,
+    max_tokens: u64,
+) -> Result<()> {
+    require!(end_ts > start_ts, RwaError::InvalidRedemptionWindow);
+    require!(
+        price_per_token > 0 && max_tokens > 0,
+        RwaError::InvalidAmount
+    );
+
+    let pool_key = ctx.accounts.redemption_pool.key();
+    let payment_mint = ctx.accounts.payment_mint.as_ref().map(|mint| mint.key());
+    let decimals = ctx.accounts.mint.decimals;
+
+    let pool = &mut ctx.accounts.redemption_pool;
+    pool.property_state = ctx.accounts.property_state.key();
+    pool.authority = ctx.accounts.authority.key();
+    pool.payment_mint = payment_mint;
+    pool.price_per_token = price_per_token;
+    pool.start_ts = start_ts;
+    pool.end_ts = end_ts;
+    pool.tokens_redeemed = 0;
+    pool.total_paid = 0;
+    pool.created_at = Clock::get()?.unix_timestamp;
+    pool.bump = ctx.bumps.redemption_pool;
+
+    // Fund the full payout for max_tokens up front
+    let funding = pool.payout_for(max_tokens, decimals)?;
+    require!(funding > 0, RwaError::InvalidAmount);
+
+    let pool_before = ctx
+        .accounts
+        .pool_payment_account
+        .as_ref()
+        .map(|account| account.amount);
+    match token_payment(
+        payment_mint,
+        &pool_key,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.pool_payment_account,
+        &ctx.accounts.authority_payment_account,
+        &ctx.accounts.payment_token_program,
+    )? {
+        Some(payment) => token_interface::transfer_checked(
+            CpiContext::new(
+                payment.token_program.to_account_info(),
+                TransferChecked {
+                    from: payment.counterparty_account.to_account_info(),
+                    mint: payment.mint.to_account_info(),
+                    to: payment.pool_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            funding,
+            payment.mint.decimals,
+        )?,
+        None => system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.redemption_pool.to_account_info(),
+                },
+            ),
+            funding,
+        )?,
+    }
+
+    // Credit what arrived: a transfer fee on the stablecoin lowers the
+    // number of tokens the pool can pay for
+    let total_funded = match (pool_before, ctx.accounts.pool_payment_account.as_mut()) {
+        (Some(pool_before), Some(pool_account)) if payment_mint.is_some() => {
+            pool_account.reload()?;
+            pool_account
+                .amount
+                .checked_sub(pool_before)
+                .ok_or(RwaError::MathOverflow)?
+        }
+        _ => funding,
+    };
+    let pool = &mut ctx.accounts.redemption_pool;
+    pool.total_funded = total_funded;
+    pool.max_tokens = max_tokens.min(pool.tokens_for(total_funded, decimals)?);
+    require!(pool.max_tokens > 0, RwaError::InvalidAmount);
+    let max_tokens = pool.max_tokens;
+
+    emit!(RedemptionPoolCreated {
+        mint: ctx.accounts.mint.key(),
+        redemption_pool: pool_key,
+        payment_mint,
+        price_per_token,
+        start_ts,
+        end_ts,
+        max_tokens,
+        total_funded,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Redemption pool created: {} per token, up to {} tokens, funded {}",
+        price_per_token,
+        max_tokens,
+        total_funded
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// REDEEM TOKENS
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct RedeemTokens<'info> {
+    /// Holder redeeming their tokens
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Holder's property token account
+    #[account(
+        mut,
+        constraint = holder_token_account.owner == holder.key() @ RwaError::Unauthorized,
+        constraint = holder_token_account.mint == mint.key() @ RwaError::InvalidMint,
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// RedemptionPool PDA
+    #[account(
+        mut,
+        seeds = [REDEMPTION_POOL_SEED, property_state.key().as_ref()],
+        bump = redemption_pool.bump,
+    )]
+    pub redemption_pool: Box<Account<'info, RedemptionPool>>,
+
+    /// Program config (protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Holder's revenue accounting. Until it is created the holder may only
+    /// redeem from their associated token account.
+    /// CHECK: Address checked in handler against the holder
+    #[account(mut)]
+    pub holder_rewards: UncheckedAccount<'info>,
+
+    /// Holder's lock-up, only initialized for locked-up holders
+    /// CHECK: Address checked in handler against the holder
+    pub holder_lockup: UncheckedAccount<'info>,
+
+    /// Stablecoin mint (stablecoin pools only)
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Pool's stablecoin account
+    #[account(mut)]
+    pub pool_payment_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Holder's stablecoin account receiving the payout
+    #[account(mut)]
+    pub holder_payment_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Burn the holder's tokens and pay them out of the redemption pool
+pub fn handler_redeem_tokens(ctx: Context<RedeemTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, RwaError::InvalidAmount);
+    ctx.accounts.program_config.require_not_paused()?;
+    ctx.accounts.property_state.require_not_frozen()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &ctx.accounts.redemption_pool;
+    require!(pool.is_open(now), RwaError::RedemptionWindowClosed);
+
+    let tokens_redeemed = pool
+        .tokens_redeemed
+        .checked_add(amount)
+        .ok_or(RwaError::MathOverflow)?;
+    require!(
+        tokens_redeemed <= pool.max_tokens,
+        RwaError::RedemptionCapExceeded
+    );
+
+    let payout = pool.payout_for(amount, ctx.accounts.mint.decimals)?;
+    require!(payout > 0, RwaError::ClaimTooSmall);
+
+    ctx.accounts
+        .property_state
+        .reconcile_circulating_supply(ctx.accounts.mint.supply)?;
+
+    // Burn the redeemed tokens
+    token_2022::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Settle accrued revenue at the old balance
+    let balance = ctx.accounts.property_state.apply_balance_change_to_holder(
+        &ctx.accounts.holder_rewards,
+        &ctx.accounts.holder.key(),
+        &ctx.accounts.holder_token_account.key(),
+        ctx.accounts.holder_token_account.amount,
+        false,
+        amount,
+    )?;
+
+    // Locked tokens cannot be redeemed
+    let locked = holder_locked_amount(
+        &ctx.accounts.holder_lockup,
+        &ctx.accounts.mint.key(),
+        &ctx.accounts.holder.key(),
+    )?;
+    require!(balance >= locked, RwaError::TokensLocked);
+
+    // Pay the holder
+    let pool_key = ctx.accounts.redemption_pool.key();
+    let property_state_key = ctx.accounts.property_state.key();
+    let seeds = &[
+        REDEMPTION_POOL_SEED,
+        property_state_key.as_ref(),
+        &[ctx.accounts.redemption_pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    match token_payment(
+        ctx.accounts.redemption_pool.payment_mint,
+        &pool_key,
+        &ctx.accounts.holder.key(),
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.pool_payment_account,
+        &ctx.accounts.holder_payment_account,
+        &ctx.accounts.payment_token_program,
+    )? {
+        Some(payment) => token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                payment.token_program.to_account_info(),
+                TransferChecked {
+                    from: payment.pool_account.to_account_info(),
+                    mint: payment.mint.to_account_info(),
+                    to: payment.counterparty_account.to_account_info(),
+                    authority: ctx.accounts.redemption_pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            payout,
+            payment.mint.decimals,
+        )?,
+        None => {
+            // The pool account is owned by this program, so lamports move directly
+            ctx.accounts.redemption_pool.sub_lamports(payout)?;
+            ctx.accounts.holder.add_lamports(payout)?;
+        }
+    }
+
+    let pool = &mut ctx.accounts.redemption_pool;
+    pool.tokens_redeemed = tokens_redeemed;
+    pool.total_paid = pool
+        .total_paid
+        .checked_add(payout)
+        .ok_or(RwaError::MathOverflow)?;
+
+    let property_state = &mut ctx.accounts.property_state;
+    property_state.circulating_supply = property_state
+        .circulating_supply
+        .checked_sub(amount)
+        .ok_or(RwaError::MathOverflow)?;
+    property_state.updated_at = now;
+
+    emit!(TokensRedeemed {
+        mint: ctx.accounts.mint.key(),
+        redemption_pool: pool_key,
+        holder: ctx.accounts.holder.key(),
+        amount,
+        payout,
+        circulating_supply: property_state.circulating_supply,
+        timestamp: now,
+    });
+
+    msg!(
+        "Redeemed {} tokens from {} for {}",
+        amount,
+        ctx.accounts.holder.key(),
+        payout
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// CLOSE REDEMPTION POOL
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct CloseRedemptionPool<'info> {
+    /// Authority who funded the pool
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// RedemptionPool PDA (remaining SOL returns to the authority)
+    #[account(
+        mut,
+        seeds = [REDEMPTION_POOL_SEED, property_state.key().as_ref()],
+        bump = redemption_pool.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        close = authority,
+    )]
+    pub redemption_pool: Box<Account<'info, RedemptionPool>>,
+
+    /// Stablecoin mint (stablecoin pools only)
+    pub payment_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Pool's stablecoin account, closed after the refund
+    #[account(mut)]
+    pub pool_payment_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Authority's stablecoin account receiving the refund
+    #[account(mut)]
+    pub authority_payment_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub payment_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Close the pool after its window ends (or its capacity is used) and refund the rest
+pub fn handler_close_redemption_pool(ctx: Context<CloseRedemptionPool>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &ctx.accounts.redemption_pool;
+    require!(
+        now >= pool.end_ts || pool.tokens_redeemed == pool.max_tokens,
+        RwaError::RedemptionPoolStillOpen
+    );
+
+    let refunded = pool
+        .total_funded
+        .checked_sub(pool.total_paid)
+        .ok_or(RwaError::MathOverflow)?;
+
+    let pool_key = pool.key();
+    let property_state_key = ctx.accounts.property_state.key();
+    let seeds = &[
+        REDEMPTION_POOL_SEED,
+        property_state_key.as_ref(),
+        &[pool.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if let Some(payment) = token_payment(
+        pool.payment_mint,
+        &pool_key,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.pool_payment_account,
+        &ctx.accounts.authority_payment_account,
+        &ctx.accounts.payment_token_program,
+    )? {
+        // Refund the whole balance, including anything sent to the pool directly
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                payment.token_program.to_account_info(),
+                TransferChecked {
+                    from: payment.pool_account.to_account_info(),
+                    mint: payment.mint.to_account_info(),
+                    to: payment.counterparty_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            payment.pool_account.amount,
+            payment.mint.decimals,
+        )?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            payment.token_program.to_account_info(),
+            CloseAccount {
+                account: payment.pool_account.to_account_info(),
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    emit!(RedemptionPoolClosed {
+        mint: ctx.accounts.mint.key(),
+        redemption_pool: pool_key,
+        tokens_redeemed: pool.tokens_redeemed,
+        total_paid: pool.total_paid,
+        refunded,
+        timestamp: now,
+    });
+
+    msg!(
+        "Redemption pool closed: {} tokens redeemed, {} refunded",
+        pool.tokens_redeemed,
+        refunded
+    );
+
+    Ok(())
+}
@@ -2,11 +2,40 @@
 ///
 /// Allows property owners to deposit rental revenue and token holders
 /// to claim their proportional share of dividends.
+///
+/// Shares are based on a balance snapshot committed as a merkle root at
+/// deposit time (see utils::merkle), not on balances at claim time.
+///
+/// Each epoch pays out in SOL or in a single SPL/Token-2022 revenue mint
+/// (e.g. USDC, BRZ). SOL revenue is held by a system-owned vault PDA that
+/// signs its payouts and keeps a rent-exempt reserve. Token revenue is held in
+/// a token account PDA owned by the RevenueEpoch, and the optional revenue
+/// accounts must then be passed.
+///
+/// Claims close at the epoch's claim deadline. Whatever is left can then be
+/// swept into a later epoch or back to the authority, closing the epoch.
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use anchor_spl::token_interface::{Mint, TokenAccount};
+use anchor_spl::{
+    token_2022::{
+        spl_token_2022::{
+            extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+            state::Account as TokenAccountState,
+        },
+        ID as TOKEN_2022_PROGRAM_ID,
+    },
+    token_2022_extensions::{harvest_withheld_tokens_to_mint, HarvestWithheldTokensToMint},
+    token_interface::{
+        self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+    },
+};
 
-use crate::{error::RwaError, state::*};
+use crate::{
+    constants::{MAX_BATCH_CLAIM_EPOCHS, MAX_MERKLE_PROOF_LEN, PROGRAM_CONFIG_SEED},
+    error::RwaError,
+    state::*,
+    utils::{create_program_account, entitlement_leaf, verify_merkle_proof},
+};
 
 /// Revenue distribution epoch - tracks a single distribution period
 #[account]
@@ -16,20 +45,48 @@ pub struct RevenueEpoch {
     pub property_state: Pubkey,
     /// Epoch number (incrementing)
     pub epoch_number: u64,
-    /// Total revenue deposited for this epoch (in lamports)
+    /// Mint revenue is paid in (None = SOL)
+    pub revenue_mint: Option<Pubkey>,
+    /// Total revenue deposited for this epoch (in lamports or revenue mint
+    /// base units, as credited to the vault after any transfer fee)
     pub total_revenue: u64,
+    /// Total revenue paid out to claimants so far
+    pub total_claimed: u64,
     /// Total tokens eligible at time of deposit (snapshot)
     pub eligible_supply: u64,
+    /// Merkle root of (investor, balance) leaves snapshotted at deposit;
+    /// the balances must sum to eligible_supply
+    pub entitlement_root: [u8; 32],
     /// Timestamp when revenue was deposited
     pub deposited_at: i64,
     /// Authority who deposited
     pub deposited_by: Pubkey,
-    /// Whether this epoch is finalized (no more deposits)
+    /// Whether this epoch is finalized (no more top-ups, open for claims)
     pub is_finalized: bool,
+    /// Last timestamp at which claims are accepted; afterwards the
+    /// remainder can be swept
+    pub claim_deadline: i64,
     /// Bump seed
     pub bump: u8,
 }
 
+impl RevenueEpoch {
+    /// Adds a claim to the epoch's running total, which can never exceed the
+    /// revenue deposited even if the entitlement snapshot over-allocates
+    pub fn record_claim(&mut self, claim_amount: u64) -> Result<()> {
+        let total_claimed = self
+            .total_claimed
+            .checked_add(claim_amount)
+            .ok_or(RwaError::MathOverflow)?;
+        require!(
+            total_claimed <= self.total_revenue,
+            RwaError::ClaimExceedsEpochRevenue
+        );
+        self.total_claimed = total_claimed;
+        Ok(())
+    }
+}
+
 /// Tracks individual investor claims for an epoch
 #[account]
 #[derive(InitSpace)]
@@ -38,7 +95,9 @@ pub struct ClaimRecord {
     pub epoch: Pubkey,
     /// The investor who claimed
     pub investor: Pubkey,
-    /// Amount claimed (in lamports)
+    /// Snapshot balance proven against the epoch's entitlement root
+    pub entitled_balance: u64,
+    /// Amount claimed (in lamports or revenue mint base units)
     pub amount_claimed: u64,
     /// Timestamp of claim
     pub claimed_at: i64,
@@ -55,19 +114,66 @@ pub const CLAIM_RECORD_SEED: &[u8] = b"claim_record";
 /// Seed for Revenue Vault PDA (holds SOL)
 pub const REVENUE_VAULT_SEED: &[u8] = b"revenue_vault";
 
+/// Seed for the revenue token account PDA of a token epoch
+pub const REVENUE_TOKEN_VAULT_SEED: &[u8] = b"revenue_token_vault";
+
+/// Validated revenue token accounts for a token epoch
+struct RevenueTokenAccounts<'a, 'info> {
+    mint: &'a InterfaceAccount<'info, Mint>,
+    vault: &'a InterfaceAccount<'info, TokenAccount>,
+    counterparty_account: &'a InterfaceAccount<'info, TokenAccount>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Checks the optional revenue accounts against the epoch's revenue mint
+///
+/// Returns None for SOL epochs, which must not receive revenue accounts.
+/// The vault's address and owner are checked by its account constraints.
+fn revenue_token_accounts<'a, 'info>(
+    revenue_mint: Option<Pubkey>,
+    counterparty: &Pubkey,
+    mint: &'a Option<Box<InterfaceAccount<'info, Mint>>>,
+    vault: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    counterparty_account: &'a Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    token_program: &'a Option<Interface<'info, TokenInterface>>,
+) -> Result<Option<RevenueTokenAccounts<'a, 'info>>> {
+    let Some(revenue_mint) = revenue_mint else {
+        require!(mint.is_none(), RwaError::InvalidPaymentAccounts);
+        return Ok(None);
+    };
+    let (Some(mint), Some(vault), Some(counterparty_account), Some(token_program)) =
+        (mint, vault, counterparty_account, token_program)
+    else {
+        return err!(RwaError::InvalidPaymentAccounts);
+    };
+
+    require_keys_eq!(mint.key(), revenue_mint, RwaError::InvalidPaymentAccounts);
+    require_keys_eq!(*mint.to_account_info().owner, token_program.key(), RwaError::InvalidPaymentAccounts);
+    require_keys_eq!(vault.mint, revenue_mint, RwaError::InvalidPaymentAccounts);
+    require_keys_eq!(counterparty_account.mint, revenue_mint, RwaError::InvalidPaymentAccounts);
+    require_keys_eq!(counterparty_account.owner, *counterparty, RwaError::InvalidPaymentAccounts);
+
+    Ok(Some(RevenueTokenAccounts {
+        mint,
+        vault,
+        counterparty_account,
+        token_program,
+    }))
+}
+
 // ============================================================================
 // DEPOSIT REVENUE
 // ============================================================================
 
 #[derive(Accounts)]
-#[instruction(epoch_number: u64)]
 pub struct DepositRevenue<'info> {
     /// Property authority depositing revenue
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// PropertyState - must be authority
+    /// PropertyState - must be authority, assigns the epoch number
     #[account(
+        mut,
         constraint = property_state.authority == authority.key() @ RwaError::Unauthorized,
         constraint = property_state.is_active @ RwaError::PropertyNotActive,
     )]
@@ -84,7 +190,11 @@ pub struct DepositRevenue<'info> {
         init,
         payer = authority,
         space = 8 + RevenueEpoch::INIT_SPACE,
-        seeds = [REVENUE_EPOCH_SEED, property_state.key().as_ref(), &epoch_number.to_le_bytes()],
+        seeds = [
+            REVENUE_EPOCH_SEED,
+            property_state.key().as_ref(),
+            &property_state.next_revenue_epoch.to_le_bytes(),
+        ],
         bump,
     )]
     pub revenue_epoch: Box<Account<'info, RevenueEpoch>>,
@@ -98,47 +208,93 @@ pub struct DepositRevenue<'info> {
     )]
     pub revenue_vault: SystemAccount<'info>,
 
+    /// Revenue mint of a token epoch (omit for SOL revenue)
+    pub revenue_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Token account PDA holding the epoch's token revenue, owned by the epoch
+    #[account(
+        init,
+        payer = authority,
+        seeds = [REVENUE_TOKEN_VAULT_SEED, revenue_epoch.key().as_ref()],
+        bump,
+        token::mint = revenue_mint,
+        token::authority = revenue_epoch,
+        token::token_program = revenue_token_program,
+    )]
+    pub revenue_token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Authority's token account funding the deposit
+    #[account(mut)]
+    pub authority_revenue_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub revenue_token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
 /// Deposit rental revenue for distribution to token holders
 pub fn handler_deposit_revenue(
     ctx: Context<DepositRevenue>,
-    epoch_number: u64,
     amount: u64,
+    entitlement_root: [u8; 32],
+    finalize: bool,
+    claim_deadline: i64,
 ) -> Result<()> {
     require!(amount > 0, RwaError::InvalidAmount);
-
-    let property_state = &ctx.accounts.property_state;
+    require!(
+        claim_deadline > Clock::get()?.unix_timestamp,
+        RwaError::InvalidClaimDeadline
+    );
 
     // Snapshot the current circulating supply for fair distribution
-    let eligible_supply = property_state.circulating_supply;
+    let eligible_supply = ctx.accounts.property_state.circulating_supply;
     require!(eligible_supply > 0, RwaError::NoTokenHolders);
 
+    let revenue_mint = ctx.accounts.revenue_mint.as_ref().map(|mint| mint.key());
+    let token_accounts = revenue_token_accounts(
+        revenue_mint,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.revenue_mint,
+        &ctx.accounts.revenue_token_vault,
+        &ctx.accounts.authority_revenue_account,
+        &ctx.accounts.revenue_token_program,
+    )?;
+    let vault_before = token_accounts.as_ref().map(|accounts| accounts.vault.amount);
+    fund_revenue_vault(
+        &ctx.accounts.authority,
+        &ctx.accounts.revenue_vault,
+        &ctx.accounts.system_program,
+        token_accounts,
+        amount,
+    )?;
+    let total_revenue = credited_amount(&mut ctx.accounts.revenue_token_vault, vault_before, amount)?;
+    require!(total_revenue > 0, RwaError::InvalidAmount);
+
+    // Take the next epoch number of this property
+    let property_state = &mut ctx.accounts.property_state;
+    let epoch_number = property_state.next_revenue_epoch;
+    property_state.next_revenue_epoch = epoch_number
+        .checked_add(1)
+        .ok_or(RwaError::MathOverflow)?;
+
     // Initialize the revenue epoch
     let revenue_epoch = &mut ctx.accounts.revenue_epoch;
     revenue_epoch.property_state = property_state.key();
     revenue_epoch.epoch_number = epoch_number;
-    revenue_epoch.total_revenue = amount;
+    revenue_epoch.revenue_mint = revenue_mint;
+    revenue_epoch.total_revenue = total_revenue;
+    revenue_epoch.total_claimed = 0;
     revenue_epoch.eligible_supply = eligible_supply;
+    revenue_epoch.entitlement_root = entitlement_root;
     revenue_epoch.deposited_at = Clock::get()?.unix_timestamp;
     revenue_epoch.deposited_by = ctx.accounts.authority.key();
-    revenue_epoch.is_finalized = true; // Single deposit per epoch
+    revenue_epoch.is_finalized = finalize;
+    revenue_epoch.claim_deadline = claim_deadline;
     revenue_epoch.bump = ctx.bumps.revenue_epoch;
 
-    // Transfer SOL from authority to revenue vault
-    let cpi_context = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-            from: ctx.accounts.authority.to_account_info(),
-            to: ctx.accounts.revenue_vault.to_account_info(),
-        },
-    );
-    system_program::transfer(cpi_context, amount)?;
-
     msg!(
-        "Revenue deposited: {} lamports for epoch {} (eligible supply: {})",
-        amount,
+        "Revenue deposited: {} for epoch {} (eligible supply: {})",
+        total_revenue,
         epoch_number,
         eligible_supply
     );
@@ -148,12 +304,236 @@ pub fn handler_deposit_revenue(
         property: property_state.key(),
         epoch: revenue_epoch.key(),
         epoch_number,
-        amount,
+        revenue_mint,
+        amount: total_revenue,
         eligible_supply,
+        entitlement_root,
         deposited_by: ctx.accounts.authority.key(),
         timestamp: Clock::get()?.unix_timestamp,
     });
 
+    if finalize {
+        emit!(RevenueEpochFinalized {
+            property: property_state.key(),
+            epoch: revenue_epoch.key(),
+            epoch_number,
+            total_revenue,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+/// Moves `amount` from the authority into the epoch's SOL or token vault
+fn fund_revenue_vault<'info>(
+    authority: &Signer<'info>,
+    revenue_vault: &SystemAccount<'info>,
+    system_program: &Program<'info, System>,
+    token_accounts: Option<RevenueTokenAccounts<'_, 'info>>,
+    amount: u64,
+) -> Result<()> {
+    match token_accounts {
+        Some(accounts) => token_interface::transfer_checked(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.counterparty_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.vault.to_account_info(),
+                    authority: authority.to_account_info(),
+                },
+            ),
+            amount,
+            accounts.mint.decimals,
+        ),
+        None => {
+            // The first deposit also funds the vault's rent-exempt reserve,
+            // which stays behind after the last claim
+            let rent_reserve = Rent::get()?
+                .minimum_balance(0)
+                .saturating_sub(revenue_vault.lamports());
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: authority.to_account_info(),
+                        to: revenue_vault.to_account_info(),
+                    },
+                ),
+                amount
+                    .checked_add(rent_reserve)
+                    .ok_or(RwaError::MathOverflow)?,
+            )
+        }
+    }
+}
+
+/// Amount that actually arrived in the vault: Token-2022 transfer fees are
+/// withheld in the vault account, so token deposits are measured
+fn credited_amount(
+    revenue_token_vault: &mut Option<Box<InterfaceAccount<TokenAccount>>>,
+    vault_before: Option<u64>,
+    amount: u64,
+) -> Result<u64> {
+    let Some(vault_before) = vault_before else {
+        return Ok(amount);
+    };
+    let vault = revenue_token_vault
+        .as_mut()
+        .ok_or(RwaError::InvalidPaymentAccounts)?;
+    vault.reload()?;
+    Ok(vault
+        .amount
+        .checked_sub(vault_before)
+        .ok_or(RwaError::MathOverflow)?)
+}
+
+// ============================================================================
+// TOP UP REVENUE
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct TopUpRevenue<'info> {
+    /// Property authority adding revenue
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState - must be authority
+    #[account(
+        constraint = property_state.authority == authority.key() @ RwaError::Unauthorized,
+        constraint = property_state.is_active @ RwaError::PropertyNotActive,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// Revenue Epoch being topped up
+    #[account(
+        mut,
+        constraint = revenue_epoch.property_state == property_state.key() @ RwaError::InvalidEpoch,
+        constraint = !revenue_epoch.is_finalized @ RwaError::EpochAlreadyFinalized,
+    )]
+    pub revenue_epoch: Box<Account<'info, RevenueEpoch>>,
+
+    /// Revenue Vault PDA - holds the SOL for this epoch
+    /// CHECK: PDA that holds SOL, validated by seeds
+    #[account(
+        mut,
+        seeds = [REVENUE_VAULT_SEED, revenue_epoch.key().as_ref()],
+        bump,
+    )]
+    pub revenue_vault: SystemAccount<'info>,
+
+    /// Revenue mint of a token epoch (omit for SOL revenue)
+    pub revenue_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Token account PDA holding the epoch's token revenue
+    #[account(
+        mut,
+        seeds = [REVENUE_TOKEN_VAULT_SEED, revenue_epoch.key().as_ref()],
+        bump,
+    )]
+    pub revenue_token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Authority's token account funding the top-up
+    #[account(mut)]
+    pub authority_revenue_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub revenue_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Add revenue to an epoch that has not been finalized yet
+pub fn handler_top_up_revenue(ctx: Context<TopUpRevenue>, amount: u64) -> Result<()> {
+    require!(amount > 0, RwaError::InvalidAmount);
+
+    let token_accounts = revenue_token_accounts(
+        ctx.accounts.revenue_epoch.revenue_mint,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.revenue_mint,
+        &ctx.accounts.revenue_token_vault,
+        &ctx.accounts.authority_revenue_account,
+        &ctx.accounts.revenue_token_program,
+    )?;
+    let vault_before = token_accounts.as_ref().map(|accounts| accounts.vault.amount);
+    fund_revenue_vault(
+        &ctx.accounts.authority,
+        &ctx.accounts.revenue_vault,
+        &ctx.accounts.system_program,
+        token_accounts,
+        amount,
+    )?;
+    let credited = credited_amount(&mut ctx.accounts.revenue_token_vault, vault_before, amount)?;
+    require!(credited > 0, RwaError::InvalidAmount);
+
+    let revenue_epoch = &mut ctx.accounts.revenue_epoch;
+    revenue_epoch.total_revenue = revenue_epoch
+        .total_revenue
+        .checked_add(credited)
+        .ok_or(RwaError::MathOverflow)?;
+
+    msg!(
+        "Revenue topped up: {} for epoch {} (total: {})",
+        credited,
+        revenue_epoch.epoch_number,
+        revenue_epoch.total_revenue
+    );
+
+    emit!(RevenueToppedUp {
+        property: ctx.accounts.property_state.key(),
+        epoch: revenue_epoch.key(),
+        epoch_number: revenue_epoch.epoch_number,
+        amount: credited,
+        total_revenue: revenue_epoch.total_revenue,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// FINALIZE REVENUE EPOCH
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct FinalizeRevenueEpoch<'info> {
+    /// Property authority
+    pub authority: Signer<'info>,
+
+    /// PropertyState - must be authority
+    #[account(
+        constraint = property_state.authority == authority.key() @ RwaError::Unauthorized,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// Revenue Epoch to open for claims
+    #[account(
+        mut,
+        constraint = revenue_epoch.property_state == property_state.key() @ RwaError::InvalidEpoch,
+        constraint = !revenue_epoch.is_finalized @ RwaError::EpochAlreadyFinalized,
+    )]
+    pub revenue_epoch: Box<Account<'info, RevenueEpoch>>,
+}
+
+/// Close an epoch to further top-ups and open it for claims
+pub fn handler_finalize_revenue_epoch(ctx: Context<FinalizeRevenueEpoch>) -> Result<()> {
+    let revenue_epoch = &mut ctx.accounts.revenue_epoch;
+    revenue_epoch.is_finalized = true;
+
+    msg!(
+        "Revenue epoch {} finalized with {}",
+        revenue_epoch.epoch_number,
+        revenue_epoch.total_revenue
+    );
+
+    emit!(RevenueEpochFinalized {
+        property: ctx.accounts.property_state.key(),
+        epoch: revenue_epoch.key(),
+        epoch_number: revenue_epoch.epoch_number,
+        total_revenue: revenue_epoch.total_revenue,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
@@ -177,15 +557,9 @@ pub struct ClaimRevenue<'info> {
     )]
     pub mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// Investor's token account - to verify balance
-    #[account(
-        constraint = investor_token_account.owner == investor.key() @ RwaError::Unauthorized,
-        constraint = investor_token_account.mint == mint.key() @ RwaError::InvalidMint,
-    )]
-    pub investor_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
-
     /// Revenue Epoch being claimed from
     #[account(
+        mut,
         constraint = revenue_epoch.property_state == property_state.key() @ RwaError::InvalidEpoch,
         constraint = revenue_epoch.is_finalized @ RwaError::EpochNotFinalized,
     )]
@@ -201,7 +575,7 @@ pub struct ClaimRevenue<'info> {
     )]
     pub claim_record: Box<Account<'info, ClaimRecord>>,
 
-    /// Revenue Vault PDA - holds the SOL
+    /// Revenue Vault PDA - holds the SOL, pays out by signing with its seeds
     /// CHECK: PDA that holds SOL, validated by seeds
     #[account(
         mut,
@@ -210,49 +584,90 @@ pub struct ClaimRevenue<'info> {
     )]
     pub revenue_vault: SystemAccount<'info>,
 
+    /// Program config (protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Revenue mint of a token epoch (omit for SOL revenue)
+    pub revenue_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Token account PDA holding the epoch's token revenue
+    #[account(
+        mut,
+        seeds = [REVENUE_TOKEN_VAULT_SEED, revenue_epoch.key().as_ref()],
+        bump,
+    )]
+    pub revenue_token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Investor's associated token account for the revenue mint
+    #[account(
+        mut,
+        associated_token::mint = revenue_mint,
+        associated_token::authority = investor,
+        associated_token::token_program = revenue_token_program,
+    )]
+    pub investor_revenue_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub revenue_token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
 /// Claim proportional share of revenue for an epoch
-pub fn handler_claim_revenue(ctx: Context<ClaimRevenue>) -> Result<()> {
-    let revenue_epoch = &ctx.accounts.revenue_epoch;
-    let investor_balance = ctx.accounts.investor_token_account.amount;
-
-    // Must have tokens to claim
-    require!(investor_balance > 0, RwaError::InsufficientBalance);
-
-    // Calculate proportional share
-    // claim_amount = (investor_balance / eligible_supply) * total_revenue
-    // Using u128 to prevent overflow
-    let claim_amount = (investor_balance as u128)
-        .checked_mul(revenue_epoch.total_revenue as u128)
-        .ok_or(RwaError::MathOverflow)?
-        .checked_div(revenue_epoch.eligible_supply as u128)
-        .ok_or(RwaError::MathOverflow)? as u64;
+///
+/// `snapshot_balance` and `proof` prove the investor's balance at deposit time
+/// against the epoch's entitlement root.
+pub fn handler_claim_revenue(
+    ctx: Context<ClaimRevenue>,
+    snapshot_balance: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let investor_balance = snapshot_balance;
 
-    require!(claim_amount > 0, RwaError::ClaimTooSmall);
+    ctx.accounts.program_config.require_not_paused()?;
+    ctx.accounts.property_state.require_not_frozen()?;
 
-    // Verify vault has enough balance
-    let vault_balance = ctx.accounts.revenue_vault.lamports();
-    require!(vault_balance >= claim_amount, RwaError::InsufficientVaultBalance);
+    let claim_amount = entitled_claim_amount(
+        &ctx.accounts.revenue_epoch,
+        &ctx.accounts.investor.key(),
+        investor_balance,
+        &proof,
+    )?;
+    ctx.accounts.revenue_epoch.record_claim(claim_amount)?;
+    let revenue_epoch = &ctx.accounts.revenue_epoch;
 
     // Record the claim
     let claim_record = &mut ctx.accounts.claim_record;
     claim_record.epoch = revenue_epoch.key();
     claim_record.investor = ctx.accounts.investor.key();
+    claim_record.entitled_balance = investor_balance;
     claim_record.amount_claimed = claim_amount;
     claim_record.claimed_at = Clock::get()?.unix_timestamp;
     claim_record.bump = ctx.bumps.claim_record;
 
-    // Transfer SOL from vault to investor
-    // Direct lamport manipulation (vault is a PDA we control)
-
-    // Transfer lamports from vault PDA to investor
-    **ctx.accounts.revenue_vault.to_account_info().try_borrow_mut_lamports()? -= claim_amount;
-    **ctx.accounts.investor.to_account_info().try_borrow_mut_lamports()? += claim_amount;
+    let token_accounts = revenue_token_accounts(
+        revenue_epoch.revenue_mint,
+        &ctx.accounts.investor.key(),
+        &ctx.accounts.revenue_mint,
+        &ctx.accounts.revenue_token_vault,
+        &ctx.accounts.investor_revenue_account,
+        &ctx.accounts.revenue_token_program,
+    )?;
+    pay_revenue_claim(
+        revenue_epoch,
+        &ctx.accounts.revenue_vault,
+        ctx.bumps.revenue_vault,
+        token_accounts,
+        &ctx.accounts.investor,
+        &ctx.accounts.system_program,
+        claim_amount,
+    )?;
 
     msg!(
-        "Revenue claimed: {} lamports by {} (balance: {}, supply: {})",
+        "Revenue claimed: {} by {} (balance: {}, supply: {})",
         claim_amount,
         ctx.accounts.investor.key(),
         investor_balance,
@@ -264,6 +679,7 @@ pub fn handler_claim_revenue(ctx: Context<ClaimRevenue>) -> Result<()> {
         property: ctx.accounts.property_state.key(),
         epoch: revenue_epoch.key(),
         epoch_number: revenue_epoch.epoch_number,
+        revenue_mint: revenue_epoch.revenue_mint,
         investor: ctx.accounts.investor.key(),
         amount: claim_amount,
         investor_balance,
@@ -273,6 +689,680 @@ pub fn handler_claim_revenue(ctx: Context<ClaimRevenue>) -> Result<()> {
     Ok(())
 }
 
+/// Checks that an epoch is open for claims and that `investor` held
+/// `snapshot_balance` in its entitlement snapshot, returning their share
+fn entitled_claim_amount(
+    revenue_epoch: &RevenueEpoch,
+    investor: &Pubkey,
+    snapshot_balance: u64,
+    proof: &[[u8; 32]],
+) -> Result<u64> {
+    require!(
+        Clock::get()?.unix_timestamp <= revenue_epoch.claim_deadline,
+        RwaError::ClaimDeadlinePassed
+    );
+
+    // Must have held tokens at the snapshot
+    require!(snapshot_balance > 0, RwaError::InsufficientBalance);
+    require!(
+        proof.len() <= MAX_MERKLE_PROOF_LEN,
+        RwaError::InvalidEntitlementProof
+    );
+    require!(
+        verify_merkle_proof(
+            proof,
+            &revenue_epoch.entitlement_root,
+            entitlement_leaf(investor, snapshot_balance),
+        ),
+        RwaError::InvalidEntitlementProof
+    );
+
+    // Calculate proportional share
+    // claim_amount = (investor_balance / eligible_supply) * total_revenue
+    // Using u128 to prevent overflow
+    let claim_amount = (snapshot_balance as u128)
+        .checked_mul(revenue_epoch.total_revenue as u128)
+        .ok_or(RwaError::MathOverflow)?
+        .checked_div(revenue_epoch.eligible_supply as u128)
+        .ok_or(RwaError::MathOverflow)? as u64;
+
+    require!(claim_amount > 0, RwaError::ClaimTooSmall);
+    Ok(claim_amount)
+}
+
+/// Pays `claim_amount` of an epoch's revenue to the investor, from the
+/// token vault when `token_accounts` is set and from the SOL vault otherwise
+fn pay_revenue_claim<'info>(
+    revenue_epoch: &Account<'info, RevenueEpoch>,
+    revenue_vault: &AccountInfo<'info>,
+    revenue_vault_bump: u8,
+    token_accounts: Option<RevenueTokenAccounts<'_, 'info>>,
+    investor: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    claim_amount: u64,
+) -> Result<()> {
+    match token_accounts {
+        Some(accounts) => {
+            // Verify vault has enough balance
+            require!(
+                accounts.vault.amount >= claim_amount,
+                RwaError::InsufficientVaultBalance
+            );
+
+            // Pay the investor's ATA, signed by the epoch that owns the vault;
+            // any transfer fee is withheld from the amount received
+            let epoch_number_bytes = revenue_epoch.epoch_number.to_le_bytes();
+            let seeds = &[
+                REVENUE_EPOCH_SEED,
+                revenue_epoch.property_state.as_ref(),
+                &epoch_number_bytes,
+                &[revenue_epoch.bump],
+            ];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.vault.to_account_info(),
+                        mint: accounts.mint.to_account_info(),
+                        to: accounts.counterparty_account.to_account_info(),
+                        authority: revenue_epoch.to_account_info(),
+                    },
+                    &[&seeds[..]],
+                ),
+                claim_amount,
+                accounts.mint.decimals,
+            )?;
+        }
+        None => {
+            // Verify vault has enough balance above its rent-exempt reserve
+            let vault_balance = revenue_vault.lamports();
+            let rent_reserve = Rent::get()?.minimum_balance(0);
+            require!(
+                vault_balance.saturating_sub(rent_reserve) >= claim_amount,
+                RwaError::InsufficientVaultBalance
+            );
+
+            // The vault is system-owned, so the System Program moves the
+            // lamports with the vault PDA signing
+            let revenue_epoch_key = revenue_epoch.key();
+            let seeds = &[
+                REVENUE_VAULT_SEED,
+                revenue_epoch_key.as_ref(),
+                &[revenue_vault_bump],
+            ];
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: revenue_vault.clone(),
+                        to: investor.clone(),
+                    },
+                    &[&seeds[..]],
+                ),
+                claim_amount,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// CLAIM REVENUE BATCH
+// ============================================================================
+
+/// Entitlement proven for one epoch of a batch claim
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct EpochEntitlement {
+    /// Investor balance in the epoch's entitlement snapshot
+    pub snapshot_balance: u64,
+    /// Merkle proof of that balance against the entitlement root
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Claims several epochs of one property in a single transaction
+///
+/// Each epoch is passed in `remaining_accounts` as a
+/// (revenue_epoch, revenue vault, claim_record) triple, in the same order as
+/// the entitlements: the epoch is writable so its claimed total can be
+/// updated, the vault is the epoch's SOL vault or, for token epochs,
+/// its revenue token vault (writable), and the claim record is the
+/// uninitialized ClaimRecord PDA (writable). All epochs must pay in the
+/// revenue mint passed here (none for SOL).
+#[derive(Accounts)]
+pub struct ClaimRevenueBatch<'info> {
+    /// Investor claiming their share
+    #[account(mut)]
+    pub investor: Signer<'info>,
+
+    /// PropertyState
+    #[account()]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property mint
+    #[account(
+        constraint = property_state.mint == mint.key() @ RwaError::InvalidMint,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Program config (protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Revenue mint of the epochs (omit for SOL revenue)
+    pub revenue_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Investor's associated token account for the revenue mint
+    #[account(
+        mut,
+        associated_token::mint = revenue_mint,
+        associated_token::authority = investor,
+        associated_token::token_program = revenue_token_program,
+    )]
+    pub investor_revenue_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub revenue_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Vault of the first epoch in a batch claim, which collects the shares of
+/// the other epochs and pays the investor once
+struct BatchPayoutVault<'info> {
+    revenue_epoch: Account<'info, RevenueEpoch>,
+    vault: &'info AccountInfo<'info>,
+    vault_bump: u8,
+    token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Vault balance before the batch less the first epoch's share
+    reserved: u64,
+}
+
+/// Claim proportional shares of several revenue epochs at once
+///
+/// Every epoch is validated and recorded exactly as in claim_revenue. The
+/// shares of later epochs are moved into the first epoch's vault, which pays
+/// the summed amount to the investor in a single transfer. For token epochs
+/// the investor receives what reaches that vault, so a mint with a transfer
+/// fee withholds it on the way in as well. One event reports the combined
+/// claim.
+pub fn handler_claim_revenue_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimRevenueBatch<'info>>,
+    entitlements: Vec<EpochEntitlement>,
+) -> Result<()> {
+    ctx.accounts.program_config.require_not_paused()?;
+    ctx.accounts.property_state.require_not_frozen()?;
+
+    require!(
+        !entitlements.is_empty() && entitlements.len() <= MAX_BATCH_CLAIM_EPOCHS,
+        RwaError::InvalidBatchClaim
+    );
+    require!(
+        ctx.remaining_accounts.len() == entitlements.len() * 3,
+        RwaError::InvalidBatchClaim
+    );
+
+    let property_state_key = ctx.accounts.property_state.key();
+    let investor_key = ctx.accounts.investor.key();
+    let claimed_at = Clock::get()?.unix_timestamp;
+    let mut epoch_numbers = Vec::with_capacity(entitlements.len());
+    let mut amounts = Vec::with_capacity(entitlements.len());
+    let mut total_amount: u64 = 0;
+    let mut payout: Option<BatchPayoutVault<'info>> = None;
+
+    for (triple, entitlement) in ctx.remaining_accounts.chunks(3).zip(entitlements.iter()) {
+        let (epoch_info, vault_info, claim_record_info) = (&triple[0], &triple[1], &triple[2]);
+
+        let mut revenue_epoch = Account::<RevenueEpoch>::try_from(epoch_info)?;
+        require_keys_eq!(
+            revenue_epoch.property_state,
+            property_state_key,
+            RwaError::InvalidEpoch
+        );
+        require!(revenue_epoch.is_finalized, RwaError::EpochNotFinalized);
+
+        let claim_amount = entitled_claim_amount(
+            &revenue_epoch,
+            &investor_key,
+            entitlement.snapshot_balance,
+            &entitlement.proof,
+        )?;
+
+        let vault_seed = match revenue_epoch.revenue_mint {
+            Some(_) => REVENUE_TOKEN_VAULT_SEED,
+            None => REVENUE_VAULT_SEED,
+        };
+        let (vault_address, vault_bump) =
+            Pubkey::find_program_address(&[vault_seed, epoch_info.key.as_ref()], ctx.program_id);
+        require_keys_eq!(vault_info.key(), vault_address, RwaError::InvalidBatchClaim);
+        require!(vault_info.is_writable, RwaError::InvalidBatchClaim);
+
+        // Creating the claim record fails for an epoch claimed before,
+        // including one repeated within this batch
+        let (claim_record_address, claim_record_bump) = Pubkey::find_program_address(
+            &[CLAIM_RECORD_SEED, epoch_info.key.as_ref(), investor_key.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            claim_record_info.key(),
+            claim_record_address,
+            RwaError::InvalidBatchClaim
+        );
+        require!(
+            claim_record_info.owner != ctx.program_id,
+            RwaError::EpochAlreadyClaimed
+        );
+        create_claim_record(
+            &ctx.accounts.investor,
+            claim_record_info,
+            &ctx.accounts.system_program,
+            &[
+                CLAIM_RECORD_SEED,
+                epoch_info.key.as_ref(),
+                investor_key.as_ref(),
+                &[claim_record_bump],
+            ],
+            &ClaimRecord {
+                epoch: epoch_info.key(),
+                investor: investor_key,
+                entitled_balance: entitlement.snapshot_balance,
+                amount_claimed: claim_amount,
+                claimed_at,
+                bump: claim_record_bump,
+            },
+        )?;
+        require!(epoch_info.is_writable, RwaError::InvalidBatchClaim);
+        revenue_epoch.record_claim(claim_amount)?;
+        revenue_epoch.exit(ctx.program_id)?;
+
+        let token_vault = match revenue_epoch.revenue_mint {
+            Some(_) => Some(Box::new(InterfaceAccount::<TokenAccount>::try_from(vault_info)?)),
+            None => None,
+        };
+        let token_accounts = revenue_token_accounts(
+            revenue_epoch.revenue_mint,
+            &investor_key,
+            &ctx.accounts.revenue_mint,
+            &token_vault,
+            &ctx.accounts.investor_revenue_account,
+            &ctx.accounts.revenue_token_program,
+        )?;
+
+        epoch_numbers.push(revenue_epoch.epoch_number);
+        amounts.push(claim_amount);
+        match &payout {
+            None => {
+                let reserved = match &token_vault {
+                    Some(token_vault) => token_vault
+                        .amount
+                        .checked_sub(claim_amount)
+                        .ok_or(RwaError::InsufficientVaultBalance)?,
+                    None => 0,
+                };
+                payout = Some(BatchPayoutVault {
+                    revenue_epoch,
+                    vault: vault_info,
+                    vault_bump,
+                    token_vault,
+                    reserved,
+                });
+            }
+            Some(payout) => {
+                // Move this epoch's share into the paying vault
+                let token_accounts = match (token_accounts, payout.token_vault.as_deref()) {
+                    (Some(accounts), Some(payout_vault)) => Some(RevenueTokenAccounts {
+                        counterparty_account: payout_vault,
+                        ..accounts
+                    }),
+                    (None, None) => None,
+                    _ => return err!(RwaError::InvalidBatchClaim),
+                };
+                pay_revenue_claim(
+                    &revenue_epoch,
+                    vault_info,
+                    vault_bump,
+                    token_accounts,
+                    payout.vault,
+                    &ctx.accounts.system_program,
+                    claim_amount,
+                )?;
+            }
+        }
+
+        total_amount = total_amount
+            .checked_add(claim_amount)
+            .ok_or(RwaError::MathOverflow)?;
+    }
+
+    // Pay the investor once out of the first epoch's vault
+    let mut payout = payout.ok_or(RwaError::InvalidBatchClaim)?;
+    let payout_amount = match payout.token_vault.as_mut() {
+        Some(token_vault) => {
+            token_vault.reload()?;
+            token_vault
+                .amount
+                .checked_sub(payout.reserved)
+                .ok_or(RwaError::MathOverflow)?
+        }
+        None => total_amount,
+    };
+    let token_accounts = revenue_token_accounts(
+        payout.revenue_epoch.revenue_mint,
+        &investor_key,
+        &ctx.accounts.revenue_mint,
+        &payout.token_vault,
+        &ctx.accounts.investor_revenue_account,
+        &ctx.accounts.revenue_token_program,
+    )?;
+    pay_revenue_claim(
+        &payout.revenue_epoch,
+        payout.vault,
+        payout.vault_bump,
+        token_accounts,
+        &ctx.accounts.investor,
+        &ctx.accounts.system_program,
+        payout_amount,
+    )?;
+
+    msg!(
+        "Revenue claimed: {} across {} epochs by {}",
+        total_amount,
+        epoch_numbers.len(),
+        investor_key
+    );
+
+    emit!(RevenueBatchClaimed {
+        property: property_state_key,
+        revenue_mint: ctx.accounts.revenue_mint.as_ref().map(|mint| mint.key()),
+        investor: investor_key,
+        epoch_numbers,
+        amounts,
+        total_amount,
+        timestamp: claimed_at,
+    });
+
+    Ok(())
+}
+
+/// Creates and writes a ClaimRecord PDA passed outside the accounts struct,
+/// funding it the way Anchor's `init` does even if it already holds lamports
+fn create_claim_record<'info>(
+    payer: &AccountInfo<'info>,
+    claim_record: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    record: &ClaimRecord,
+) -> Result<()> {
+    create_program_account(
+        payer,
+        claim_record,
+        system_program,
+        seeds,
+        8 + ClaimRecord::INIT_SPACE,
+    )?;
+
+    let mut data = claim_record.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+// ============================================================================
+// SWEEP UNCLAIMED REVENUE
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct SweepUnclaimedRevenue<'info> {
+    /// Property authority, receives the swept revenue when there is no
+    /// target epoch and the reclaimed rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState - must be authority
+    #[account(
+        constraint = property_state.authority == authority.key() @ RwaError::Unauthorized,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// Revenue Epoch past its claim deadline, closed by the sweep
+    #[account(
+        mut,
+        close = authority,
+        constraint = revenue_epoch.property_state == property_state.key() @ RwaError::InvalidEpoch,
+    )]
+    pub revenue_epoch: Box<Account<'info, RevenueEpoch>>,
+
+    /// Revenue Vault PDA - emptied and left to be garbage collected
+    /// CHECK: PDA that holds SOL, validated by seeds
+    #[account(
+        mut,
+        seeds = [REVENUE_VAULT_SEED, revenue_epoch.key().as_ref()],
+        bump,
+    )]
+    pub revenue_vault: SystemAccount<'info>,
+
+    /// Later, not yet finalized epoch receiving the remainder (omit to sweep
+    /// back to the authority)
+    #[account(
+        mut,
+        constraint = target_epoch.property_state == property_state.key() @ RwaError::InvalidEpoch,
+        constraint = target_epoch.epoch_number > revenue_epoch.epoch_number @ RwaError::InvalidEpoch,
+        constraint = target_epoch.revenue_mint == revenue_epoch.revenue_mint @ RwaError::InvalidEpoch,
+        constraint = !target_epoch.is_finalized @ RwaError::EpochAlreadyFinalized,
+    )]
+    pub target_epoch: Option<Box<Account<'info, RevenueEpoch>>>,
+
+    /// SOL or token vault of the target epoch
+    /// CHECK: Address checked in handler against the target epoch
+    #[account(mut)]
+    pub target_vault: Option<UncheckedAccount<'info>>,
+
+    /// Revenue mint of a token epoch (written when withheld transfer fees
+    /// are harvested before closing the vault)
+    #[account(mut)]
+    pub revenue_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Token account PDA holding the epoch's token revenue, closed by the sweep
+    #[account(
+        mut,
+        seeds = [REVENUE_TOKEN_VAULT_SEED, revenue_epoch.key().as_ref()],
+        bump,
+    )]
+    pub revenue_token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Authority's token account receiving a token remainder
+    #[account(mut)]
+    pub authority_revenue_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub revenue_token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Move what is left in an expired epoch into a later epoch or back to the
+/// authority, then close the epoch and its vault
+pub fn handler_sweep_unclaimed_revenue(ctx: Context<SweepUnclaimedRevenue>) -> Result<()> {
+    let revenue_epoch = &ctx.accounts.revenue_epoch;
+    require!(
+        Clock::get()?.unix_timestamp > revenue_epoch.claim_deadline,
+        RwaError::ClaimDeadlineNotReached
+    );
+
+    // The remainder goes to the target epoch's vault, or back to the authority
+    let target_vault = match (&ctx.accounts.target_epoch, &ctx.accounts.target_vault) {
+        (Some(target_epoch), Some(target_vault)) => {
+            let vault_seed = match revenue_epoch.revenue_mint {
+                Some(_) => REVENUE_TOKEN_VAULT_SEED,
+                None => REVENUE_VAULT_SEED,
+            };
+            let (expected, _) = Pubkey::find_program_address(
+                &[vault_seed, target_epoch.key().as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(target_vault.key(), expected, RwaError::InvalidEpoch);
+            Some(target_vault.to_account_info())
+        }
+        (None, None) => None,
+        _ => return err!(RwaError::InvalidEpoch),
+    };
+
+    let property_state_key = ctx.accounts.property_state.key();
+    let revenue_epoch_key = revenue_epoch.key();
+
+    let swept = match revenue_token_accounts(
+        revenue_epoch.revenue_mint,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.revenue_mint,
+        &ctx.accounts.revenue_token_vault,
+        &ctx.accounts.authority_revenue_account,
+        &ctx.accounts.revenue_token_program,
+    )? {
+        Some(accounts) => {
+            let epoch_number_bytes = revenue_epoch.epoch_number.to_le_bytes();
+            let seeds = &[
+                REVENUE_EPOCH_SEED,
+                property_state_key.as_ref(),
+                &epoch_number_bytes,
+                &[revenue_epoch.bump],
+            ];
+            let signer_seeds = &[&seeds[..]];
+            let token_program = accounts.token_program.to_account_info();
+            let mint = accounts.mint.to_account_info();
+            let vault = accounts.vault.to_account_info();
+            let destination = target_vault
+                .unwrap_or_else(|| accounts.counterparty_account.to_account_info());
+
+            let remaining = accounts.vault.amount;
+            let destination_before = token_account_amount(&destination)?;
+            if remaining > 0 {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.clone(),
+                        TransferChecked {
+                            from: vault.clone(),
+                            mint: mint.clone(),
+                            to: destination.clone(),
+                            authority: revenue_epoch.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    remaining,
+                    accounts.mint.decimals,
+                )?;
+            }
+
+            // Transfer fees withheld in the vault block closing it
+            if token_program.key() == TOKEN_2022_PROGRAM_ID && has_withheld_fees(&vault)? {
+                harvest_withheld_tokens_to_mint(
+                    CpiContext::new(
+                        token_program.clone(),
+                        HarvestWithheldTokensToMint {
+                            token_program_id: token_program.clone(),
+                            mint,
+                        },
+                    ),
+                    vec![vault.clone()],
+                )?;
+            }
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program,
+                CloseAccount {
+                    account: vault,
+                    destination: ctx.accounts.authority.to_account_info(),
+                    authority: revenue_epoch.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+
+            // Credit the target with what arrived after any transfer fee
+            token_account_amount(&destination)?
+                .checked_sub(destination_before)
+                .ok_or(RwaError::MathOverflow)?
+        }
+        None => {
+            let vault = ctx.accounts.revenue_vault.to_account_info();
+            let rent_reserve = Rent::get()?.minimum_balance(0);
+            let remaining = vault.lamports().saturating_sub(rent_reserve);
+            let seeds = &[
+                REVENUE_VAULT_SEED,
+                revenue_epoch_key.as_ref(),
+                &[ctx.bumps.revenue_vault],
+            ];
+            let signer_seeds = &[&seeds[..]];
+
+            // The remainder to its destination, the rent reserve to the authority
+            let authority = ctx.accounts.authority.to_account_info();
+            let payouts = [
+                (target_vault.unwrap_or(authority.clone()), remaining),
+                (authority, vault.lamports() - remaining),
+            ];
+            for (to, lamports) in payouts {
+                if lamports > 0 {
+                    system_program::transfer(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.system_program.to_account_info(),
+                            system_program::Transfer {
+                                from: vault.clone(),
+                                to,
+                            },
+                            signer_seeds,
+                        ),
+                        lamports,
+                    )?;
+                }
+            }
+            remaining
+        }
+    };
+
+    let target_epoch_key = match ctx.accounts.target_epoch.as_mut() {
+        Some(target_epoch) => {
+            target_epoch.total_revenue = target_epoch
+                .total_revenue
+                .checked_add(swept)
+                .ok_or(RwaError::MathOverflow)?;
+            Some(target_epoch.key())
+        }
+        None => None,
+    };
+
+    msg!(
+        "Swept {} unclaimed from revenue epoch {}",
+        swept,
+        ctx.accounts.revenue_epoch.epoch_number
+    );
+
+    emit!(RevenueSwept {
+        property: property_state_key,
+        epoch: revenue_epoch_key,
+        epoch_number: ctx.accounts.revenue_epoch.epoch_number,
+        revenue_mint: ctx.accounts.revenue_epoch.revenue_mint,
+        amount: swept,
+        target_epoch: target_epoch_key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Token balance of an SPL Token or Token-2022 account
+fn token_account_amount(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    Ok(StateWithExtensions::<TokenAccountState>::unpack(&data)?
+        .base
+        .amount)
+}
+
+/// Whether a Token-2022 account holds withheld transfer fees
+fn has_withheld_fees(token_account: &AccountInfo) -> Result<bool> {
+    let data = token_account.try_borrow_data()?;
+    let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    Ok(account
+        .get_extension::<TransferFeeAmount>()
+        .map(|fees| u64::from(fees.withheld_amount) > 0)
+        .unwrap_or(false))
+}
+
 // ============================================================================
 // EVENTS
 // ============================================================================
@@ -282,19 +1372,65 @@ pub struct RevenueDeposited {
     pub property: Pubkey,
     pub epoch: Pubkey,
     pub epoch_number: u64,
+    pub revenue_mint: Option<Pubkey>,
     pub amount: u64,
     pub eligible_supply: u64,
+    pub entitlement_root: [u8; 32],
     pub deposited_by: Pubkey,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct RevenueToppedUp {
+    pub property: Pubkey,
+    pub epoch: Pubkey,
+    pub epoch_number: u64,
+    pub amount: u64,
+    pub total_revenue: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevenueEpochFinalized {
+    pub property: Pubkey,
+    pub epoch: Pubkey,
+    pub epoch_number: u64,
+    pub total_revenue: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevenueSwept {
+    pub property: Pubkey,
+    pub epoch: Pubkey,
+    pub epoch_number: u64,
+    pub revenue_mint: Option<Pubkey>,
+    pub amount: u64,
+    /// Epoch the remainder was added to (None = returned to the authority)
+    pub target_epoch: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct RevenueClaimed {
     pub property: Pubkey,
     pub epoch: Pubkey,
     pub epoch_number: u64,
+    pub revenue_mint: Option<Pubkey>,
     pub investor: Pubkey,
     pub amount: u64,
     pub investor_balance: u64,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct RevenueBatchClaimed {
+    pub property: Pubkey,
+    pub revenue_mint: Option<Pubkey>,
+    pub investor: Pubkey,
+    /// Epochs claimed, with the amount paid for each in `amounts`
+    pub epoch_numbers: Vec<u64>,
+    pub amounts: Vec<u64>,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
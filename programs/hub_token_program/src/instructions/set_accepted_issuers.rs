@@ -0,0 +1,55 @@
+/// Restrict which trusted issuers are accepted for a property
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+#[derive(Accounts)]
+pub struct SetAcceptedIssuers<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Handler for set_accepted_issuers instruction
+pub fn handler(ctx: Context<SetAcceptedIssuers>, accepted_issuers: Vec<Pubkey>) -> Result<()> {
+    require!(
+        accepted_issuers.len() <= MAX_PROPERTY_ACCEPTED_ISSUERS,
+        RwaError::TooManyAcceptedIssuers
+    );
+
+    let property_state = &mut ctx.accounts.property_state;
+
+    property_state.accepted_issuers = accepted_issuers.clone();
+    property_state.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Property {} accepted issuers: {}",
+        property_state.property_name,
+        if accepted_issuers.is_empty() {
+            "ALL TRUSTED".to_string()
+        } else {
+            accepted_issuers.len().to_string()
+        }
+    );
+
+    emit!(AcceptedIssuersUpdated {
+        mint: ctx.accounts.mint.key(),
+        accepted_issuers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
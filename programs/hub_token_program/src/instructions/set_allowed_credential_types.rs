@@ -0,0 +1,52 @@
+/// Update the set of Hub Credential types accepted for a property
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::*, error::RwaError, events::*, state::*, utils::HubCredentialType};
+
+#[derive(Accounts)]
+pub struct SetAllowedCredentialTypes<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Handler for set_allowed_credential_types instruction
+pub fn handler(ctx: Context<SetAllowedCredentialTypes>, allowed_credential_types: u8) -> Result<()> {
+    require!(
+        allowed_credential_types != 0
+            && allowed_credential_types & !HubCredentialType::ALL_MASK == 0,
+        RwaError::InvalidCredentialTypeMask
+    );
+
+    let property_state = &mut ctx.accounts.property_state;
+
+    property_state.allowed_credential_types = allowed_credential_types;
+    property_state.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(AllowedCredentialTypesUpdated {
+        mint: ctx.accounts.mint.key(),
+        allowed_credential_types,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Property {} allowed credential types: {:#08b}",
+        property_state.property_name,
+        allowed_credential_types
+    );
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+/// Configure per-holder and holder count limits for a property
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+#[derive(Accounts)]
+pub struct SetHoldingLimits<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Handler for set_holding_limits instruction
+///
+/// Lowering a limit below current holdings does not affect existing
+/// balances; it only blocks further increases and new holders.
+pub fn handler(
+    ctx: Context<SetHoldingLimits>,
+    max_tokens_per_holder: u64,
+    max_holders: u32,
+) -> Result<()> {
+    let property_state = &mut ctx.accounts.property_state;
+
+    property_state.max_tokens_per_holder = max_tokens_per_holder;
+    property_state.max_holders = max_holders;
+    property_state.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(HoldingLimitsUpdated {
+        mint: ctx.accounts.mint.key(),
+        max_tokens_per_holder,
+        max_holders,
+        holder_count: property_state.holder_count,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Property {} holding limits: {} tokens per holder, {} holders (current: {})",
+        property_state.property_name,
+        max_tokens_per_holder,
+        max_holders,
+        property_state.holder_count
+    );
+
+    Ok(())
+}
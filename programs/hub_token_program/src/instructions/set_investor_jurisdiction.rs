@@ -0,0 +1,80 @@
+/// Record the jurisdiction an investor was verified in
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+#[derive(Accounts)]
+pub struct SetInvestorJurisdiction<'info> {
+    /// Trusted credential issuer or registry admin (pays for the profile)
+    #[account(mut)]
+    pub issuer: Signer<'info>,
+
+    /// Investor wallet
+    /// CHECK: Only used as a PDA seed
+    pub investor: UncheckedAccount<'info>,
+
+    /// Investor's profile, created on first use
+    #[account(
+        init_if_needed,
+        payer = issuer,
+        space = 8 + InvestorProfile::INIT_SPACE,
+        seeds = [INVESTOR_PROFILE_SEED, investor.key().as_ref()],
+        bump,
+    )]
+    pub investor_profile: Box<Account<'info, InvestorProfile>>,
+
+    /// Trusted issuer registry
+    #[account(
+        seeds = [ISSUER_REGISTRY_SEED],
+        bump = issuer_registry.bump,
+    )]
+    pub issuer_registry: Box<Account<'info, IssuerRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Handler for set_investor_jurisdiction instruction
+///
+/// A recorded country can only be changed by the issuer that recorded it or
+/// by the registry admin.
+pub fn handler(ctx: Context<SetInvestorJurisdiction>, country: [u8; 2]) -> Result<()> {
+    let issuer = ctx.accounts.issuer.key();
+    let issuer_registry = &ctx.accounts.issuer_registry;
+    if issuer != issuer_registry.admin {
+        issuer_registry
+            .require_trusted(&issuer)
+            .map_err(|_| RwaError::Unauthorized)?;
+    }
+    validate_country_code(&country)?;
+
+    let investor_profile = &mut ctx.accounts.investor_profile;
+    if investor_profile.updated_by != Pubkey::default() && issuer != issuer_registry.admin {
+        require_keys_eq!(
+            investor_profile.updated_by,
+            issuer,
+            RwaError::JurisdictionSetByOtherIssuer
+        );
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    investor_profile.wallet = ctx.accounts.investor.key();
+    investor_profile.country = country;
+    investor_profile.updated_by = issuer;
+    investor_profile.updated_at = now;
+    investor_profile.bump = ctx.bumps.investor_profile;
+
+    emit!(InvestorJurisdictionSet {
+        investor: investor_profile.wallet,
+        country,
+        updated_by: issuer,
+        timestamp: now,
+    });
+
+    msg!(
+        "Investor {} jurisdiction: {}",
+        investor_profile.wallet,
+        country_code_str(&country)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+/// Restrict which investor jurisdictions may hold a property's tokens
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+#[derive(Accounts)]
+pub struct SetJurisdictions<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Handler for set_jurisdictions instruction
+///
+/// Existing holders outside the new lists keep their tokens but can no
+/// longer receive more.
+pub fn handler(
+    ctx: Context<SetJurisdictions>,
+    allowed_jurisdictions: Vec<[u8; 2]>,
+    denied_jurisdictions: Vec<[u8; 2]>,
+) -> Result<()> {
+    require!(
+        allowed_jurisdictions.len() <= MAX_PROPERTY_JURISDICTIONS
+            && denied_jurisdictions.len() <= MAX_PROPERTY_JURISDICTIONS,
+        RwaError::TooManyJurisdictions
+    );
+    for country in allowed_jurisdictions.iter().chain(&denied_jurisdictions) {
+        validate_country_code(country)?;
+    }
+
+    let property_state = &mut ctx.accounts.property_state;
+
+    property_state.allowed_jurisdictions = allowed_jurisdictions.clone();
+    property_state.denied_jurisdictions = denied_jurisdictions.clone();
+    property_state.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Property {} jurisdictions: {} allowed, {} denied",
+        property_state.property_name,
+        if allowed_jurisdictions.is_empty() {
+            "ALL".to_string()
+        } else {
+            allowed_jurisdictions.len().to_string()
+        },
+        denied_jurisdictions.len()
+    );
+
+    emit!(JurisdictionsUpdated {
+        mint: ctx.accounts.mint.key(),
+        allowed_jurisdictions,
+        denied_jurisdictions,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+/// Configure the KYC grace period for expiring credentials
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+#[derive(Accounts)]
+pub struct SetKycGracePeriod<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Handler for set_kyc_grace_period instruction
+pub fn handler(ctx: Context<SetKycGracePeriod>, kyc_grace_period_seconds: i64) -> Result<()> {
+    require!(
+        (0..=MAX_KYC_GRACE_PERIOD_SECONDS).contains(&kyc_grace_period_seconds),
+        RwaError::InvalidGracePeriod
+    );
+
+    let property_state = &mut ctx.accounts.property_state;
+
+    property_state.kyc_grace_period_seconds = kyc_grace_period_seconds;
+    property_state.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(KycGracePeriodUpdated {
+        mint: ctx.accounts.mint.key(),
+        kyc_grace_period_seconds,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Property {} KYC grace period: {} seconds",
+        property_state.property_name,
+        kyc_grace_period_seconds
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+/// Enable or disable sender KYC verification on transfers
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+#[derive(Accounts)]
+pub struct SetSenderVerification<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Handler for set_sender_verification instruction
+pub fn handler(ctx: Context<SetSenderVerification>, verify_sender: bool) -> Result<()> {
+    let property_state = &mut ctx.accounts.property_state;
+
+    property_state.verify_sender = verify_sender;
+    property_state.updated_at = Clock::get()?.unix_timestamp;
+
+    emit!(SenderVerificationUpdated {
+        mint: ctx.accounts.mint.key(),
+        verify_sender,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Property {} sender verification: {}",
+        property_state.property_name,
+        if verify_sender { "ENFORCED" } else { "DISABLED" }
+    );
+
+    Ok(())
+}
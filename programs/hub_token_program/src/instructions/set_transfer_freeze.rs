@@ -0,0 +1,61 @@
+/// Freeze or unfreeze all movement of a property's tokens
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{constants::*, error::RwaError, events::*, state::*};
+
+#[derive(Accounts)]
+pub struct SetTransferFreeze<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// PropertyState PDA
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// The property token mint (Token-2022)
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+}
+
+/// Handler for set_transfer_freeze instruction
+pub fn handler(ctx: Context<SetTransferFreeze>, is_frozen: bool, reason: PauseReason) -> Result<()> {
+    let property_state = &mut ctx.accounts.property_state;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    property_state.transfers_frozen = is_frozen;
+    property_state.freeze_reason = reason;
+    property_state.updated_at = timestamp;
+
+    let mint = ctx.accounts.mint.key();
+    let authority = ctx.accounts.authority.key();
+    if is_frozen {
+        emit!(PropertyTransfersFrozen {
+            mint,
+            authority,
+            reason,
+            timestamp,
+        });
+    } else {
+        emit!(PropertyTransfersUnfrozen {
+            mint,
+            authority,
+            reason,
+            timestamp,
+        });
+    }
+
+    msg!(
+        "Property {} transfers {} (reason: {:?})",
+        property_state.property_name,
+        if is_frozen { "FROZEN" } else { "UNFROZEN" },
+        reason
+    );
+
+    Ok(())
+}
@@ -5,20 +5,187 @@
 ///
 /// Uses Hub Credential Protocol for KYC verification.
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::{
+    spl_token_2022::{
+        extension::{transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions},
+        state::Account as TokenAccountState,
+    },
+    ID as TOKEN_2022_PROGRAM_ID,
+};
 use anchor_spl::token_interface::Mint;
-
-use crate::{constants::HUB_CREDENTIAL_PROGRAM_ID, error::RwaError, events::*, utils::*};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+use crate::{
+    constants::{
+        HOLDER_LOCKUP_SEED, HOLDER_REWARDS_SEED, HUB_CREDENTIAL_SEED, INVESTOR_PROFILE_SEED,
+        ISSUER_REGISTRY_SEED, PROGRAM_CONFIG_SEED, PROPERTY_STATE_SEED,
+    },
+    error::RwaError,
+    events::*,
+    state::*,
+    utils::*,
+};
 
 /// Seeds for the ExtraAccountMeta PDA
 pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
 
-/// Size of ExtraAccountMeta structure (35 bytes each)
-/// discriminator (1) + address_config (32) + is_signer (1) + is_writable (1)
-pub const EXTRA_ACCOUNT_META_SIZE: usize = 35;
+/// Index of the source token account in the Execute instruction accounts
+const SOURCE_ACCOUNT_INDEX: u8 = 0;
+
+/// Index of the mint in the Execute instruction accounts
+const MINT_INDEX: u8 = 1;
+
+/// Index of the destination token account in the Execute instruction accounts
+const DESTINATION_ACCOUNT_INDEX: u8 = 2;
+
+/// Index of the Hub Credential program in the Execute instruction accounts
+const HUB_CREDENTIAL_PROGRAM_INDEX: u8 = 5;
+
+/// Offset of the owner field in a token account
+const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
+
+/// Extra accounts required by transfer_hook_execute
+///
+/// 5. Hub Credential program (from the ProgramConfig when the list is written)
+/// 6. Hub Credential PDA of the destination owner: `["credential", destination_owner]`
+///    under the Hub Credential program, with the owner read from the destination token account
+/// 7. Hub Credential PDA of the source owner, resolved the same way from the source token account
+/// 8. PropertyState PDA: `["property", mint]` (writable, tracks the holder count, so every
+///    transfer of the property write-locks it)
+/// 9. IssuerRegistry PDA: `["issuer_registry"]`
+/// 10. ProgramConfig PDA: `["config"]`
+/// 11. HolderRewards PDA of the source owner: `["holder_rewards", mint, source_owner]` (writable)
+/// 12. HolderRewards PDA of the destination owner: `["holder_rewards", mint, destination_owner]`
+///     (writable, may not exist)
+/// 13. HolderLockup PDA of the source owner: `["holder_lockup", mint, source_owner]`
+///     (may not exist)
+/// 14. InvestorProfile PDA of the destination owner: `["investor_profile", destination_owner]`
+///     (may not exist)
+/// 15. InvestorProfile PDA of the source owner: `["investor_profile", source_owner]`
+///     (may not exist)
+pub fn extra_account_metas(credential_program_id: &Pubkey) -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(credential_program_id, false, false)?,
+        token_owner_credential_meta(DESTINATION_ACCOUNT_INDEX)?,
+        token_owner_credential_meta(SOURCE_ACCOUNT_INDEX)?,
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: PROPERTY_STATE_SEED.to_vec(),
+                },
+                Seed::AccountKey {
+                    index: MINT_INDEX,
+                },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal {
+                bytes: ISSUER_REGISTRY_SEED.to_vec(),
+            }],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal {
+                bytes: PROGRAM_CONFIG_SEED.to_vec(),
+            }],
+            false,
+            false,
+        )?,
+        token_owner_holder_meta(HOLDER_REWARDS_SEED, SOURCE_ACCOUNT_INDEX, true)?,
+        token_owner_holder_meta(HOLDER_REWARDS_SEED, DESTINATION_ACCOUNT_INDEX, true)?,
+        token_owner_holder_meta(HOLDER_LOCKUP_SEED, SOURCE_ACCOUNT_INDEX, false)?,
+        token_owner_profile_meta(DESTINATION_ACCOUNT_INDEX)?,
+        token_owner_profile_meta(SOURCE_ACCOUNT_INDEX)?,
+    ])
+}
+
+/// Hub Credential PDA of the owner of the token account at `account_index`
+fn token_owner_credential_meta(account_index: u8) -> Result<ExtraAccountMeta> {
+    Ok(ExtraAccountMeta::new_external_pda_with_seeds(
+        HUB_CREDENTIAL_PROGRAM_INDEX,
+        &[
+            Seed::Literal {
+                bytes: HUB_CREDENTIAL_SEED.to_vec(),
+            },
+            Seed::AccountData {
+                account_index,
+                data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                length: 32,
+            },
+        ],
+        false,
+        false,
+    )?)
+}
+
+/// InvestorProfile PDA of the owner of the token account at `account_index`
+fn token_owner_profile_meta(account_index: u8) -> Result<ExtraAccountMeta> {
+    Ok(ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal {
+                bytes: INVESTOR_PROFILE_SEED.to_vec(),
+            },
+            Seed::AccountData {
+                account_index,
+                data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                length: 32,
+            },
+        ],
+        false,
+        false,
+    )?)
+}
+
+/// Per-holder PDA (`[seed, mint, owner]`) of the owner of the token account
+/// at `account_index`
+fn token_owner_holder_meta(
+    seed: &[u8],
+    account_index: u8,
+    is_writable: bool,
+) -> Result<ExtraAccountMeta> {
+    Ok(ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal {
+                bytes: seed.to_vec(),
+            },
+            Seed::AccountKey {
+                index: MINT_INDEX,
+            },
+            Seed::AccountData {
+                account_index,
+                data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                length: 32,
+            },
+        ],
+        false,
+        is_writable,
+    )?)
+}
+
+/// Space required by the ExtraAccountMetaList account
+pub fn extra_account_meta_list_size() -> Result<usize> {
+    Ok(ExtraAccountMetaList::size_of(
+        extra_account_metas(&Pubkey::default())?.len(),
+    )?)
+}
 
-/// Execute discriminator for Transfer Hook interface
-/// SHA256("spl-transfer-hook-interface:execute")[0..8]
-pub const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26];
+/// Writes the ExtraAccountMetaList for the Execute instruction into an allocated account
+pub fn write_extra_account_meta_list(
+    extra_account_meta_list: &AccountInfo,
+    credential_program_id: &Pubkey,
+) -> Result<()> {
+    let mut data = extra_account_meta_list.try_borrow_mut_data()?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut data,
+        &extra_account_metas(credential_program_id)?,
+    )?;
+    Ok(())
+}
 
 /// Transfer Hook Execute instruction
 /// Called by Token-2022 on every transfer
@@ -29,20 +196,31 @@ pub const EXECUTE_DISCRIMINATOR: [u8; 8] = [105, 37, 101, 197, 75, 251, 102, 26]
 /// 2. Destination token account
 /// 3. Owner/Authority
 /// 4. ExtraAccountMetaList PDA
-/// 5+ Additional accounts from ExtraAccountMetaList
+/// 5. Hub Credential program
+/// 6. Hub Credential PDA of the destination owner
+/// 7. Hub Credential PDA of the source owner
+/// 8. PropertyState PDA
+/// 9. IssuerRegistry PDA
+/// 10. ProgramConfig PDA
+/// 11. HolderRewards PDA of the source owner
+/// 12. HolderRewards PDA of the destination owner
+/// 13. HolderLockup PDA of the source owner
+/// 14. InvestorProfile PDA of the destination owner
+/// 15. InvestorProfile PDA of the source owner
 #[derive(Accounts)]
 pub struct TransferHook<'info> {
     /// Source token account (sender)
-    /// CHECK: Validated by Token-2022
-    #[account()]
+    /// CHECK: Owner checked here, transferring flag checked in handler
+    #[account(owner = TOKEN_2022_PROGRAM_ID @ RwaError::InvalidTokenProgram)]
     pub source_account: UncheckedAccount<'info>,
 
     /// The mint account
+    #[account(owner = TOKEN_2022_PROGRAM_ID @ RwaError::InvalidTokenProgram)]
     pub mint: InterfaceAccount<'info, Mint>,
 
     /// Destination token account (receiver)
-    /// CHECK: Validated by Token-2022
-    #[account()]
+    /// CHECK: Owner checked here, transferring flag checked in handler
+    #[account(owner = TOKEN_2022_PROGRAM_ID @ RwaError::InvalidTokenProgram)]
     pub destination_account: UncheckedAccount<'info>,
 
     /// Owner of the source account (authority)
@@ -57,10 +235,71 @@ pub struct TransferHook<'info> {
     )]
     pub extra_account_meta_list: UncheckedAccount<'info>,
 
+    /// Hub Credential program the mint's meta list resolves credentials under
+    /// CHECK: Must be the configured program, or the previous one until this
+    /// mint's list is rewritten after a rotation
+    #[account(
+        constraint = program_config.accepts_credential_program(&hub_credential_program.key())
+            @ RwaError::InvalidCredentialProgram,
+    )]
+    pub hub_credential_program: UncheckedAccount<'info>,
+
     /// Hub Credential for destination wallet (KYC verification)
     /// This is the Hub Credential that proves the destination wallet has completed KYC.
     /// CHECK: Will be verified using Hub Credential program
-    pub hub_credential: UncheckedAccount<'info>,
+    pub destination_credential: UncheckedAccount<'info>,
+
+    /// Hub Credential for source wallet, verified when the property enforces sender KYC
+    /// CHECK: Will be verified using Hub Credential program
+    pub source_credential: UncheckedAccount<'info>,
+
+    /// PropertyState PDA (holding limits and holder count)
+    /// Writable, so transfers of the same property cannot run in parallel
+    #[account(
+        mut,
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
+
+    /// Trusted issuer registry
+    #[account(
+        seeds = [ISSUER_REGISTRY_SEED],
+        bump = issuer_registry.bump,
+    )]
+    pub issuer_registry: Box<Account<'info, IssuerRegistry>>,
+
+    /// Program config (Hub Credential program ID, protocol pause)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    /// Revenue accounting of the source owner. Until it is created the owner
+    /// may only send from their associated token account.
+    /// CHECK: Address checked in handler against the source token account owner
+    #[account(mut)]
+    pub source_rewards: UncheckedAccount<'info>,
+
+    /// Revenue accounting of the destination owner. Until it is created the
+    /// owner may only receive into their associated token account, whose
+    /// tokens it picks up once created.
+    /// CHECK: Address checked in handler against the destination token account owner
+    #[account(mut)]
+    pub destination_rewards: UncheckedAccount<'info>,
+
+    /// Lock-up of the source owner, only initialized for locked-up holders
+    /// CHECK: Address checked in handler against the source token account owner
+    pub source_lockup: UncheckedAccount<'info>,
+
+    /// Recorded jurisdiction of the destination owner
+    /// CHECK: Address and data checked during credential verification
+    pub destination_profile: UncheckedAccount<'info>,
+
+    /// Recorded jurisdiction of the source owner
+    /// CHECK: Address and data checked during credential verification
+    pub source_profile: UncheckedAccount<'info>,
 }
 
 /// Handler for transfer hook execute instruction
@@ -68,33 +307,120 @@ pub struct TransferHook<'info> {
 pub fn handler(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
     msg!("Transfer Hook: Verifying Hub Credential for transfer of {} tokens", amount);
 
-    // Get destination wallet from the token account
-    // Token account structure: owner is at offset 32-64
-    let destination_data = ctx.accounts.destination_account.try_borrow_data()?;
-    if destination_data.len() < 64 {
-        return Err(RwaError::KycVerificationRequired.into());
-    }
-    let destination_owner = Pubkey::try_from(&destination_data[32..64])
-        .map_err(|_| RwaError::KycVerificationRequired)?;
+    // Reject standalone invocations: Token-2022 only sets the transferring
+    // flag on both token accounts for the duration of a real transfer
+    let (source_owner, source_amount) = assert_is_transferring(&ctx.accounts.source_account)?;
+    let (destination_owner, destination_amount) =
+        assert_is_transferring(&ctx.accounts.destination_account)?;
+
+    // Circuit breakers: protocol-wide pause and per-property freeze
+    ctx.accounts.program_config.require_not_paused()?;
+    ctx.accounts.property_state.require_not_frozen()?;
+
+    // Credentials in their grace period only allow moving tokens between
+    // the holder's own accounts: incoming transfers from others are blocked
+    let is_self_transfer = source_owner == destination_owner;
 
     // Verify Hub Credential for destination wallet
-    verify_hub_credential(
-        &ctx.accounts.hub_credential.to_account_info(),
+    let destination_standing = verify_hub_credential(
+        &ctx.accounts.destination_credential.to_account_info(),
+        &ctx.accounts.destination_profile.to_account_info(),
         &destination_owner,
-        &ctx.accounts.mint.key(),
-    )?;
+        &ctx.accounts.program_config,
+        &ctx.accounts.property_state,
+        &ctx.accounts.issuer_registry,
+    )
+    .inspect_err(|_| {
+        msg!(
+            "Transfer Hook: KYC verification failed for destination wallet: {}",
+            destination_owner
+        )
+    })?;
+    if destination_standing != CredentialStanding::Valid && !is_self_transfer {
+        msg!(
+            "Transfer Hook: destination wallet {} is in its grace period and cannot receive tokens",
+            destination_owner
+        );
+        return err!(RwaError::CredentialExpired);
+    }
 
     msg!(
         "Transfer Hook: Hub Credential verified for destination wallet: {}",
         destination_owner
     );
 
+    // Verify Hub Credential for source wallet
+    if ctx.accounts.property_state.verify_sender {
+        let source_standing = verify_hub_credential(
+            &ctx.accounts.source_credential.to_account_info(),
+            &ctx.accounts.source_profile.to_account_info(),
+            &source_owner,
+            &ctx.accounts.program_config,
+            &ctx.accounts.property_state,
+            &ctx.accounts.issuer_registry,
+        )
+        .inspect_err(|_| {
+            msg!(
+                "Transfer Hook: KYC verification failed for source wallet: {}",
+                source_owner
+            )
+        })?;
+        if source_standing != CredentialStanding::Valid && !is_self_transfer {
+            msg!(
+                "Transfer Hook: source wallet {} is in its grace period and can only move tokens between its own accounts",
+                source_owner
+            );
+            return err!(RwaError::CredentialExpired);
+        }
+
+        msg!(
+            "Transfer Hook: Hub Credential verified for source wallet: {}",
+            source_owner
+        );
+    }
+
+    // Settle accrued revenue at the old balances before they change, and
+    // enforce the holding limits on the destination. A wallet without a
+    // HolderRewards counts through its associated token account alone.
+    if !is_self_transfer {
+        let mint = ctx.accounts.mint.key();
+        let source_amount_before = source_amount
+            .checked_add(amount)
+            .ok_or(RwaError::MathOverflow)?;
+        let destination_amount_before = destination_amount
+            .checked_sub(amount)
+            .ok_or(RwaError::MathOverflow)?;
+        let property_state = &mut ctx.accounts.property_state;
+        let source_balance = property_state.apply_balance_change_to_holder(
+            &ctx.accounts.source_rewards,
+            &source_owner,
+            &ctx.accounts.source_account.key(),
+            source_amount_before,
+            false,
+            amount,
+        )?;
+        property_state.apply_balance_change_to_holder(
+            &ctx.accounts.destination_rewards,
+            &destination_owner,
+            &ctx.accounts.destination_account.key(),
+            destination_amount_before,
+            true,
+            amount,
+        )?;
+
+        // The source wallet may not drop below its still-locked tokens
+        let locked = holder_locked_amount(&ctx.accounts.source_lockup, &mint, &source_owner)?;
+        require!(source_balance >= locked, RwaError::TokensLocked);
+    }
+
     // Emit transfer verification event
     emit!(TransferKycVerified {
         mint: ctx.accounts.mint.key(),
         source: ctx.accounts.source_account.key(),
+        source_owner,
         destination: ctx.accounts.destination_account.key(),
         destination_owner,
+        sender_verified: ctx.accounts.property_state.verify_sender,
         amount,
         timestamp: Clock::get()?.unix_timestamp,
     });
@@ -102,6 +428,20 @@ pub fn handler(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
     Ok(())
 }
 
+/// Checks that a Token-2022 account is mid-transfer and returns its owner
+/// and (post-transfer) balance
+fn assert_is_transferring(token_account: &AccountInfo) -> Result<(Pubkey, u64)> {
+    let data = token_account.try_borrow_data()?;
+    let account = StateWithExtensions::<TokenAccountState>::unpack(&data)?;
+    let extension = account
+        .get_extension::<TransferHookAccount>()
+        .map_err(|_| RwaError::NotTransferring)?;
+
+    require!(bool::from(extension.transferring), RwaError::NotTransferring);
+
+    Ok((account.base.owner, account.base.amount))
+}
+
 /// Initialize the ExtraAccountMetaList for the transfer hook
 /// This defines which additional accounts are required during transfers
 #[derive(Accounts)]
@@ -114,8 +454,7 @@ pub struct InitializeExtraAccountMetaList<'info> {
     #[account(
         init,
         payer = payer,
-        // Space: 8 (anchor discriminator) + 4 (length) + 4 (count) + 35 (1 extra account meta)
-        space = 8 + 4 + 4 + EXTRA_ACCOUNT_META_SIZE,
+        space = extra_account_meta_list_size()?,
         seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
         bump,
     )]
@@ -124,6 +463,13 @@ pub struct InitializeExtraAccountMetaList<'info> {
     /// The mint this transfer hook is for
     pub mint: InterfaceAccount<'info, Mint>,
 
+    /// Program config (Hub Credential program ID)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -133,70 +479,205 @@ pub struct InitializeExtraAccountMetaList<'info> {
 /// For Hub Credential verification, we need the credential account to be passed.
 /// The credential PDA is derived from:
 /// - "credential" literal
-/// - Destination wallet pubkey
+/// - Destination wallet pubkey (owner field of the destination token account)
 /// - Hub Credential Program ID
 pub fn handler_initialize_extra_account_metas(
     ctx: Context<InitializeExtraAccountMetaList>,
 ) -> Result<()> {
-    let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+    let credential_program_id = ctx.accounts.program_config.credential_program_id;
+    write_extra_account_meta_list(&ctx.accounts.extra_account_meta_list, &credential_program_id)?;
 
-    // Write the ExtraAccountMetaList manually
-    // Format: ExecuteInstruction discriminator (8) + length (4) + count (4) + metas (35 * count)
+    msg!(
+        "Initialized ExtraAccountMetaList for mint: {}",
+        ctx.accounts.mint.key()
+    );
+    msg!(
+        "Hub Credential Program: {}",
+        credential_program_id
+    );
+
+    emit!(ExtraAccountMetasInitialized {
+        mint: ctx.accounts.mint.key(),
+        extra_account_meta_list: ctx.accounts.extra_account_meta_list.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
-    // Write Execute discriminator
-    data[0..8].copy_from_slice(&EXECUTE_DISCRIMINATOR);
+    Ok(())
+}
 
-    // Write length (total size of the account list data: 4 + 35*1 = 39)
-    let list_length: u32 = 4 + EXTRA_ACCOUNT_META_SIZE as u32;
-    data[8..12].copy_from_slice(&list_length.to_le_bytes());
+/// Rewrite the ExtraAccountMetaList after the Hub Credential program changes
+#[derive(Accounts)]
+pub struct UpdateExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
 
-    // Write count (1 extra account)
-    let count: u32 = 1;
-    data[12..16].copy_from_slice(&count.to_le_bytes());
+    /// PropertyState PDA
+    #[account(
+        seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
+        bump = property_state.bump,
+        has_one = authority @ RwaError::Unauthorized,
+        has_one = mint @ RwaError::InvalidMint,
+    )]
+    pub property_state: Box<Account<'info, PropertyState>>,
 
-    // Write ExtraAccountMeta for Hub Credential PDA
-    // We use discriminator = 2 (External PDA - PDA from another program)
-    data[16] = 2;
+    /// ExtraAccountMetaList PDA to be rewritten, resized when metas were added
+    /// CHECK: Validated by seeds
+    #[account(
+        mut,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
 
-    // address_config: seeds configuration for Hub Credential PDA
-    // Credential PDA seeds: ["credential", wallet]
-    // We need to get the destination wallet from the destination token account's owner field
-    let mut address_config = [0u8; 32];
+    /// The mint this transfer hook is for
+    pub mint: InterfaceAccount<'info, Mint>,
 
-    // Seed 1: Literal "credential" (10 bytes)
-    address_config[0] = 10; // length
-    address_config[1] = 0;  // seed type: Literal
-    // "credential" bytes would go here in a proper implementation
+    /// Program config (Hub Credential program ID)
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
 
-    // Seed 2: AccountData - get owner from destination token account (index 2)
-    // Token account owner is at offset 32-64 (32 bytes)
-    address_config[12] = 32; // length
-    address_config[13] = 2;  // seed type: AccountData
-    address_config[14] = 2;  // account index (destination token account)
-    address_config[15] = 32; // data offset (owner field starts at byte 32)
+    pub system_program: Program<'info, System>,
+}
 
-    // Store address config
-    data[17..49].copy_from_slice(&address_config);
+/// Handler to point an existing ExtraAccountMetaList at the configured Hub Credential program
+pub fn handler_update_extra_account_metas(ctx: Context<UpdateExtraAccountMetaList>) -> Result<()> {
+    let credential_program_id = ctx.accounts.program_config.credential_program_id;
+    let extra_account_meta_list = ctx.accounts.extra_account_meta_list.to_account_info();
+
+    // Lists written before metas were added must grow first
+    let size = extra_account_meta_list_size()?;
+    if extra_account_meta_list.data_len() < size {
+        let shortfall = Rent::get()?
+            .minimum_balance(size)
+            .saturating_sub(extra_account_meta_list.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: extra_account_meta_list.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        extra_account_meta_list.realloc(size, false)?;
+    }
 
-    // is_signer = false
-    data[49] = 0;
-    // is_writable = false
-    data[50] = 0;
+    let mut data = extra_account_meta_list.try_borrow_mut_data()?;
+    ExtraAccountMetaList::update::<ExecuteInstruction>(
+        &mut data,
+        &extra_account_metas(&credential_program_id)?,
+    )?;
 
     msg!(
-        "Initialized ExtraAccountMetaList for mint: {}",
+        "Updated ExtraAccountMetaList for mint: {}",
         ctx.accounts.mint.key()
     );
     msg!(
         "Hub Credential Program: {}",
-        HUB_CREDENTIAL_PROGRAM_ID
+        credential_program_id
     );
 
-    emit!(ExtraAccountMetasInitialized {
-        mint: ctx.accounts.mint.key(),
-        extra_account_meta_list: ctx.accounts.extra_account_meta_list.key(),
-        timestamp: Clock::get()?.unix_timestamp,
-    });
-
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extra_account_metas_resolve_credentials_and_property() {
+        let credential_program_id = Pubkey::new_unique();
+        let metas = extra_account_metas(&credential_program_id).unwrap();
+        let mut data = vec![0u8; extra_account_meta_list_size().unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas).unwrap();
+
+        let source_owner = Pubkey::new_unique();
+        let mut source_data = vec![0u8; 165];
+        source_data[32..64].copy_from_slice(source_owner.as_ref());
+
+        let destination_owner = Pubkey::new_unique();
+        let mut destination_data = vec![0u8; 165];
+        destination_data[32..64].copy_from_slice(destination_owner.as_ref());
+
+        let mint = Pubkey::new_unique();
+        let keys = [
+            Pubkey::new_unique(),
+            mint,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            credential_program_id,
+        ];
+        let resolve = |meta: &ExtraAccountMeta| {
+            meta.resolve(&[], &crate::ID, |index| {
+                keys.get(index).map(|key| {
+                    let data = match index as u8 {
+                        SOURCE_ACCOUNT_INDEX => Some(source_data.as_slice()),
+                        DESTINATION_ACCOUNT_INDEX => Some(destination_data.as_slice()),
+                        _ => None,
+                    };
+                    (key, data)
+                })
+            })
+            .unwrap()
+            .pubkey
+        };
+
+        assert_eq!(resolve(&metas[0]), credential_program_id);
+        assert_eq!(
+            resolve(&metas[1]),
+            derive_hub_credential_pda(&destination_owner, &credential_program_id).0
+        );
+        assert_eq!(
+            resolve(&metas[2]),
+            derive_hub_credential_pda(&source_owner, &credential_program_id).0
+        );
+        assert_eq!(
+            resolve(&metas[3]),
+            Pubkey::find_program_address(&[PROPERTY_STATE_SEED, mint.as_ref()], &crate::ID).0
+        );
+        assert_eq!(
+            resolve(&metas[4]),
+            Pubkey::find_program_address(&[ISSUER_REGISTRY_SEED], &crate::ID).0
+        );
+        assert_eq!(
+            resolve(&metas[5]),
+            Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], &crate::ID).0
+        );
+        assert_eq!(
+            resolve(&metas[6]),
+            Pubkey::find_program_address(
+                &[HOLDER_REWARDS_SEED, mint.as_ref(), source_owner.as_ref()],
+                &crate::ID
+            )
+            .0
+        );
+        assert_eq!(
+            resolve(&metas[7]),
+            Pubkey::find_program_address(
+                &[HOLDER_REWARDS_SEED, mint.as_ref(), destination_owner.as_ref()],
+                &crate::ID
+            )
+            .0
+        );
+        assert_eq!(
+            resolve(&metas[8]),
+            Pubkey::find_program_address(
+                &[HOLDER_LOCKUP_SEED, mint.as_ref(), source_owner.as_ref()],
+                &crate::ID
+            )
+            .0
+        );
+        assert_eq!(
+            resolve(&metas[9]),
+            derive_investor_profile_pda(&destination_owner).0
+        );
+        assert_eq!(resolve(&metas[10]), derive_investor_profile_pda(&source_owner).0);
+    }
+}
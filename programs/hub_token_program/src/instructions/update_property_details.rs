@@ -1,15 +1,21 @@
 /// Update property metadata and details
+///
+/// The mint's TokenMetadata (uri and the additional property fields) is kept
+/// in sync through Token-2022 update_field.
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::{token_metadata_update_field, TokenMetadataUpdateField};
 use anchor_spl::token_interface::Mint;
 
-use crate::{constants::*, error::RwaError, events::*, state::*};
+use crate::{constants::*, error::RwaError, events::*, state::*, utils::*};
 
 #[derive(Accounts)]
 pub struct UpdatePropertyDetails<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
-    /// PropertyState PDA
+    /// PropertyState PDA (TokenMetadata update authority)
     #[account(
         mut,
         seeds = [PROPERTY_STATE_SEED, mint.key().as_ref()],
@@ -19,8 +25,12 @@ pub struct UpdatePropertyDetails<'info> {
     )]
     pub property_state: Box<Account<'info, PropertyState>>,
 
-    /// The property token mint (Token-2022)
+    /// The property token mint (Token-2022), holding its TokenMetadata
+    #[account(mut)]
     pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
 }
 
 /// Handler for update_property_details instruction
@@ -31,6 +41,9 @@ pub fn handler(
     // Validate new property details
     new_details.validate()?;
 
+    // Sync TokenMetadata fields that changed
+    sync_token_metadata(&ctx, &new_details)?;
+
     let property_state = &mut ctx.accounts.property_state;
 
     // Update property details
@@ -62,3 +75,71 @@ pub fn handler(
 
     Ok(())
 }
+
+/// Writes changed fields to the mint's TokenMetadata, topping up rent first
+/// since Token-2022 reallocs the mint without funding it
+fn sync_token_metadata(ctx: &Context<UpdatePropertyDetails>, new_details: &PropertyDetails) -> Result<()> {
+    let mint = ctx.accounts.mint.to_account_info();
+    let mut metadata = read_token_metadata(&mint)?;
+    let old_metadata_len = token_metadata_len(&metadata)?;
+
+    let changed: Vec<_> = property_metadata_fields(new_details)
+        .into_iter()
+        .filter(|(field, value)| {
+            let mut updated = metadata.clone();
+            updated.update(field.clone(), value.clone());
+            updated != metadata
+        })
+        .collect();
+    if changed.is_empty() {
+        return Ok(());
+    }
+    for (field, value) in &changed {
+        metadata.update(field.clone(), value.clone());
+    }
+
+    let new_len = mint
+        .data_len()
+        .checked_sub(old_metadata_len)
+        .and_then(|len| len.checked_add(token_metadata_len(&metadata).ok()?))
+        .ok_or(RwaError::MathOverflow)?;
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    if required_lamports > mint.lamports() {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: mint.clone(),
+                },
+            ),
+            required_lamports - mint.lamports(),
+        )?;
+    }
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        PROPERTY_STATE_SEED,
+        mint_key.as_ref(),
+        &[ctx.accounts.property_state.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    for (field, value) in changed {
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TokenMetadataUpdateField {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    metadata: mint.clone(),
+                    update_authority: ctx.accounts.property_state.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            field,
+            value,
+        )?;
+    }
+
+    Ok(())
+}
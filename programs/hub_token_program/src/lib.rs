@@ -19,6 +19,7 @@
 //! ```
 
 use anchor_lang::prelude::*;
+use spl_transfer_hook_interface::instruction::TransferHookInstruction;
 
 // Module declarations
 pub mod constants;
@@ -82,17 +83,24 @@ pub mod hub_token_program {
     ///
     /// # Arguments
     /// * `amount` - Number of tokens to mint
+    /// * `lockup` - Optional lock-up for the minted tokens: fully locked until
+    ///   `lockup_until`, then vesting linearly until `vesting_end`. Requires the
+    ///   investor_lockup account and adds to any lock-up the investor already has.
     ///
     /// # Security
     /// - Verifies SAS attestation exists and is valid
     /// - Checks attestation has not expired
+    /// - Checks the credential type is allowed for the property
+    /// - Checks the credential issuer is trusted and accepted for the property
+    /// - Checks the investor's jurisdiction is allowed for the property
     /// - Ensures property is active
     /// - Validates total supply limit
     pub fn mint_property_tokens(
         ctx: Context<MintPropertyTokens>,
         amount: u64,
+        lockup: Option<LockupSchedule>,
     ) -> Result<()> {
-        instructions::mint_property_tokens::handler(ctx, amount)
+        instructions::mint_property_tokens::handler(ctx, amount, lockup)
     }
 
     /// Burn property tokens (redemption/exit)
@@ -131,6 +139,249 @@ pub mod hub_token_program {
         instructions::toggle_property_status::handler(ctx)
     }
 
+    /// Migrate a PropertyState created before its fields were extended
+    ///
+    /// Grows the account to the current layout, keeping the original fields
+    /// and giving the appended ones the defaults of a new property. Existing
+    /// holders start without HolderRewards and are picked up as they create
+    /// them. Run update_extra_account_metas afterwards so transfers resolve
+    /// the accounts the transfer hook now needs.
+    ///
+    /// # Access Control
+    /// - Only the property authority
+    pub fn migrate_property_state(ctx: Context<MigratePropertyState>) -> Result<()> {
+        instructions::migrate_property_state::handler(ctx)
+    }
+
+    /// Enable or disable sender KYC verification on transfers
+    ///
+    /// When enabled, the transfer hook verifies the source wallet's Hub Credential
+    /// in addition to the destination's. Only the property authority can change it.
+    ///
+    /// # Arguments
+    /// * `verify_sender` - Whether the sender's credential must be valid
+    pub fn set_sender_verification(
+        ctx: Context<SetSenderVerification>,
+        verify_sender: bool,
+    ) -> Result<()> {
+        instructions::set_sender_verification::handler(ctx, verify_sender)
+    }
+
+    /// Update the Hub Credential types accepted for this property
+    ///
+    /// Minting and transfers reject credentials whose type is not in the set.
+    /// Only the property authority can update it.
+    ///
+    /// # Arguments
+    /// * `allowed_credential_types` - Bitmask of HubCredentialType values (bit = 1 << type)
+    pub fn set_allowed_credential_types(
+        ctx: Context<SetAllowedCredentialTypes>,
+        allowed_credential_types: u8,
+    ) -> Result<()> {
+        instructions::set_allowed_credential_types::handler(ctx, allowed_credential_types)
+    }
+
+    /// Restrict which trusted issuers are accepted for this property
+    ///
+    /// Credentials must come from an issuer that is both in the IssuerRegistry
+    /// and in this list. An empty list accepts every trusted issuer.
+    ///
+    /// # Arguments
+    /// * `accepted_issuers` - Issuer keys (max 8)
+    pub fn set_accepted_issuers(
+        ctx: Context<SetAcceptedIssuers>,
+        accepted_issuers: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_accepted_issuers::handler(ctx, accepted_issuers)
+    }
+
+    /// Restrict which investor jurisdictions are accepted for this property
+    ///
+    /// Checked with the Hub Credential on mints, subscriptions, offering
+    /// settlement and transfers, against the country recorded in each
+    /// investor's InvestorProfile. Once either list is set, investors without
+    /// a recorded country are rejected. Existing mints must refresh their
+    /// transfer hook accounts with update_extra_account_metas first.
+    ///
+    /// # Arguments
+    /// * `allowed_jurisdictions` - ISO 3166-1 alpha-2 codes (max 16, empty = all)
+    /// * `denied_jurisdictions` - ISO 3166-1 alpha-2 codes (max 16)
+    pub fn set_jurisdictions(
+        ctx: Context<SetJurisdictions>,
+        allowed_jurisdictions: Vec<[u8; 2]>,
+        denied_jurisdictions: Vec<[u8; 2]>,
+    ) -> Result<()> {
+        instructions::set_jurisdictions::handler(ctx, allowed_jurisdictions, denied_jurisdictions)
+    }
+
+    /// Record the jurisdiction an investor was verified in
+    ///
+    /// Hub Credentials carry no country, so the issuer that verified the
+    /// investor records it in their InvestorProfile.
+    ///
+    /// # Arguments
+    /// * `country` - ISO 3166-1 alpha-2 code (e.g. "BR")
+    ///
+    /// # Access Control
+    /// - Trusted, non-suspended issuer or the issuer registry admin
+    /// - A recorded country can only be changed by the issuer that recorded
+    ///   it or the issuer registry admin
+    pub fn set_investor_jurisdiction(
+        ctx: Context<SetInvestorJurisdiction>,
+        country: [u8; 2],
+    ) -> Result<()> {
+        instructions::set_investor_jurisdiction::handler(ctx, country)
+    }
+
+    /// Configure the KYC grace period for expiring credentials
+    ///
+    /// Within the grace period after a credential expires, holders can still move
+    /// tokens between their own accounts, but new mints and incoming transfers are
+    /// blocked. Only the property authority can change it.
+    ///
+    /// # Arguments
+    /// * `kyc_grace_period_seconds` - Grace period length (0 to 30 days, 0 disables)
+    pub fn set_kyc_grace_period(
+        ctx: Context<SetKycGracePeriod>,
+        kyc_grace_period_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_kyc_grace_period::handler(ctx, kyc_grace_period_seconds)
+    }
+
+    /// Configure holding limits for a property
+    ///
+    /// Caps how many tokens one wallet may hold across its accounts and how
+    /// many wallets may hold the token, as securities and crowdfunding rules
+    /// require. Enforced on mints, transfers and offering settlement;
+    /// existing mints must refresh their transfer hook accounts with
+    /// update_extra_account_metas first. Only the property authority can
+    /// change them.
+    ///
+    /// # Arguments
+    /// * `max_tokens_per_holder` - Token base units per holder (0 = no limit)
+    /// * `max_holders` - Wallets with a non-zero balance (0 = no limit)
+    pub fn set_holding_limits(
+        ctx: Context<SetHoldingLimits>,
+        max_tokens_per_holder: u64,
+        max_holders: u32,
+    ) -> Result<()> {
+        instructions::set_holding_limits::handler(ctx, max_tokens_per_holder, max_holders)
+    }
+
+    /// Extend a holder's lock-up
+    ///
+    /// Tokens still locked are re-locked under the new schedule; tokens that
+    /// already vested stay transferable. Neither date may move earlier.
+    ///
+    /// # Arguments
+    /// * `lockup_until` - Timestamp until which the tokens are fully locked
+    /// * `vesting_end` - Timestamp at which the tokens are fully vested
+    ///
+    /// # Access Control
+    /// - Only the property authority
+    pub fn extend_lockup(
+        ctx: Context<UpdateHolderLockup>,
+        lockup_until: i64,
+        vesting_end: i64,
+    ) -> Result<()> {
+        instructions::manage_lockup::handler_extend_lockup(ctx, lockup_until, vesting_end)
+    }
+
+    /// Release a holder's lock-up, making all of their tokens transferable
+    ///
+    /// Closes the HolderLockup and returns its rent to the authority.
+    ///
+    /// # Access Control
+    /// - Only the property authority
+    pub fn release_lockup(ctx: Context<UpdateHolderLockup>) -> Result<()> {
+        instructions::manage_lockup::handler_release_lockup(ctx)
+    }
+
+    /// Freeze or unfreeze a property's tokens
+    ///
+    /// While frozen, transfers, mints, burns and revenue claims for this property
+    /// are rejected. Emits PropertyTransfersFrozen or PropertyTransfersUnfrozen.
+    ///
+    /// # Arguments
+    /// * `is_frozen` - Whether to freeze the property
+    /// * `reason` - Reason code recorded with the change
+    ///
+    /// # Access Control
+    /// - Only the property authority
+    pub fn set_transfer_freeze(
+        ctx: Context<SetTransferFreeze>,
+        is_frozen: bool,
+        reason: PauseReason,
+    ) -> Result<()> {
+        instructions::set_transfer_freeze::handler(ctx, is_frozen, reason)
+    }
+
+    /// Freeze a holder's token account
+    ///
+    /// CPIs into Token-2022 with the PropertyState PDA as freeze authority,
+    /// e.g. for a court order or sanctions match. Emits HolderFrozen.
+    ///
+    /// # Arguments
+    /// * `reason_hash` - Hash of the off-chain order or case file
+    ///
+    /// # Access Control
+    /// - Property authority or compliance authority
+    pub fn freeze_holder(
+        ctx: Context<UpdateHolderFreeze>,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::holder_freeze::handler_freeze_holder(ctx, reason_hash)
+    }
+
+    /// Thaw a frozen holder token account
+    ///
+    /// # Arguments
+    /// * `reason_hash` - Hash of the off-chain release decision
+    ///
+    /// # Access Control
+    /// - Property authority or compliance authority
+    pub fn thaw_holder(
+        ctx: Context<UpdateHolderFreeze>,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::holder_freeze::handler_thaw_holder(ctx, reason_hash)
+    }
+
+    /// Force a transfer between holders (clawback)
+    ///
+    /// Recovers tokens after a lost key, an estate settlement or a legal seizure
+    /// using the PropertyState PDA as permanent delegate. Emits ForcedTransfer.
+    ///
+    /// # Arguments
+    /// * `amount` - Number of tokens to move
+    /// * `skip_destination_kyc` - Skip the destination Hub Credential check
+    /// * `case_reference` - Court, estate or recovery case reference (max 64 chars)
+    ///
+    /// # Access Control
+    /// - Property authority or compliance authority
+    pub fn force_transfer(
+        ctx: Context<ForceTransfer>,
+        amount: u64,
+        skip_destination_kyc: bool,
+        case_reference: String,
+    ) -> Result<()> {
+        instructions::force_transfer::handler(ctx, amount, skip_destination_kyc, case_reference)
+    }
+
+    /// Set the compliance authority allowed to freeze and thaw holders
+    ///
+    /// # Arguments
+    /// * `compliance_authority` - Compliance officer wallet
+    ///
+    /// # Access Control
+    /// - Only the property authority
+    pub fn set_compliance_authority(
+        ctx: Context<SetComplianceAuthority>,
+        compliance_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::holder_freeze::handler_set_compliance_authority(ctx, compliance_authority)
+    }
+
     /// Initialize the ExtraAccountMetaList for Transfer Hook
     ///
     /// This must be called after creating a mint with the TransferHook extension.
@@ -142,12 +393,25 @@ pub mod hub_token_program {
         instructions::transfer_hook::handler_initialize_extra_account_metas(ctx)
     }
 
+    /// Rewrite the ExtraAccountMetaList for an existing mint
+    ///
+    /// Run after `update_config` changes the Hub Credential program so transfers
+    /// resolve credentials under the new program before the admin stops
+    /// accepting the previous one. Only the property authority can call it.
+    pub fn update_extra_account_metas(
+        ctx: Context<UpdateExtraAccountMetaList>,
+    ) -> Result<()> {
+        instructions::transfer_hook::handler_update_extra_account_metas(ctx)
+    }
+
     /// Transfer Hook Execute - KYC verification on transfers
     ///
     /// This instruction is automatically called by Token-2022 during every transfer.
-    /// It verifies that the destination wallet has valid KYC (SAS attestation).
+    /// It verifies that the destination wallet has valid KYC (SAS attestation),
+    /// and the source wallet too when the property enforces sender verification.
     ///
     /// # Security
+    /// - Only honoured during a Token-2022 transfer (transferring flag set)
     /// - Verifies SAS attestation exists for destination wallet
     /// - Checks attestation has not expired
     /// - Ensures destination is not a sanctioned wallet
@@ -159,18 +423,165 @@ pub mod hub_token_program {
         instructions::transfer_hook::handler(ctx, amount)
     }
 
+    // ========================================================================
+    // PROGRAM CONFIG - Protocol-wide settings
+    // ========================================================================
+
+    /// Initialize the program config singleton
+    ///
+    /// The signer becomes the protocol admin.
+    ///
+    /// # Arguments
+    /// * `credential_program_id` - Hub Credential program for this cluster
+    /// * `fee_recipient` - Recipient of protocol fees
+    ///
+    /// # Access Control
+    /// - Only the program upgrade authority
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        credential_program_id: Pubkey,
+        fee_recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::manage_program_config::handler_initialize_config(
+            ctx,
+            credential_program_id,
+            fee_recipient,
+        )
+    }
+
+    /// Update the program config
+    ///
+    /// Fields passed as `None` are left unchanged. A new admin does not take
+    /// over the issuer registry; hand it over with set_registry_admin.
+    ///
+    /// # Rotating the Hub Credential program
+    /// Every mint's ExtraAccountMetaList names the credential program, so the
+    /// replaced program stays accepted alongside the new one:
+    /// 1. `update_config` with the new `credential_program_id`
+    /// 2. Reissue credentials under the new program
+    /// 3. Each property authority runs `update_extra_account_metas`
+    /// 4. `update_config` with the same `credential_program_id` again stops
+    ///    accepting the previous program
+    ///
+    /// # Access Control
+    /// - Only the protocol admin
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        new_admin: Option<Pubkey>,
+        credential_program_id: Option<Pubkey>,
+        fee_recipient: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::manage_program_config::handler_update_config(
+            ctx,
+            new_admin,
+            credential_program_id,
+            fee_recipient,
+        )
+    }
+
+    /// Pause or unpause the whole protocol
+    ///
+    /// While paused, transfers, mints, burns and revenue claims are rejected for
+    /// every property. Emits ProtocolPaused or ProtocolUnpaused.
+    ///
+    /// # Arguments
+    /// * `is_paused` - Whether to pause the protocol
+    /// * `reason` - Reason code recorded with the change
+    ///
+    /// # Access Control
+    /// - Only the protocol admin
+    pub fn set_protocol_pause(
+        ctx: Context<UpdateConfig>,
+        is_paused: bool,
+        reason: PauseReason,
+    ) -> Result<()> {
+        instructions::manage_program_config::handler_set_protocol_pause(ctx, is_paused, reason)
+    }
+
+    // ========================================================================
+    // ISSUER REGISTRY - Trusted Hub Credential issuers
+    // ========================================================================
+
+    /// Initialize the program-wide trusted issuer registry
+    ///
+    /// The signer becomes the registry admin.
+    ///
+    /// # Access Control
+    /// - Only the protocol admin in the program config
+    pub fn initialize_issuer_registry(ctx: Context<InitializeIssuerRegistry>) -> Result<()> {
+        instructions::manage_issuer_registry::handler_initialize_issuer_registry(ctx)
+    }
+
+    /// Add a trusted Hub Credential issuer
+    ///
+    /// # Arguments
+    /// * `issuer` - Issuer key written into credentials
+    ///
+    /// # Access Control
+    /// - Only the registry admin
+    pub fn add_trusted_issuer(ctx: Context<UpdateIssuerRegistry>, issuer: Pubkey) -> Result<()> {
+        instructions::manage_issuer_registry::handler_add_trusted_issuer(ctx, issuer)
+    }
+
+    /// Remove a Hub Credential issuer from the registry
+    ///
+    /// # Access Control
+    /// - Only the registry admin
+    pub fn remove_trusted_issuer(ctx: Context<UpdateIssuerRegistry>, issuer: Pubkey) -> Result<()> {
+        instructions::manage_issuer_registry::handler_remove_trusted_issuer(ctx, issuer)
+    }
+
+    /// Suspend or reinstate a trusted issuer
+    ///
+    /// Credentials from a suspended issuer are rejected until it is reinstated.
+    ///
+    /// # Arguments
+    /// * `issuer` - Registered issuer key
+    /// * `is_suspended` - true to suspend, false to reinstate
+    ///
+    /// # Access Control
+    /// - Only the registry admin
+    pub fn suspend_trusted_issuer(
+        ctx: Context<UpdateIssuerRegistry>,
+        issuer: Pubkey,
+        is_suspended: bool,
+    ) -> Result<()> {
+        instructions::manage_issuer_registry::handler_suspend_trusted_issuer(ctx, issuer, is_suspended)
+    }
+
+    /// Hand the issuer registry over to a new admin
+    ///
+    /// Use together with update_config's `new_admin` when rotating the
+    /// protocol admin, since the registry keeps its own admin.
+    ///
+    /// # Arguments
+    /// * `new_admin` - Wallet that becomes the registry admin
+    ///
+    /// # Access Control
+    /// - Only the current registry admin
+    pub fn set_registry_admin(ctx: Context<UpdateIssuerRegistry>, new_admin: Pubkey) -> Result<()> {
+        instructions::manage_issuer_registry::handler_set_registry_admin(ctx, new_admin)
+    }
+
     // ========================================================================
     // REVENUE VAULT - Dividend Distribution
     // ========================================================================
 
     /// Deposit rental revenue for distribution to token holders
     ///
-    /// Creates a new revenue epoch and deposits SOL into the vault.
-    /// Token holders can then claim their proportional share.
+    /// Creates the property's next revenue epoch (numbered by the program)
+    /// and deposits SOL into the vault, or a stablecoin when the optional
+    /// revenue accounts are passed. Token holders can claim their proportional
+    /// share once the epoch is finalized.
     ///
     /// # Arguments
-    /// * `epoch_number` - Sequential epoch number for this distribution
-    /// * `amount` - Amount of SOL (in lamports) to deposit
+    /// * `amount` - Amount to deposit, in lamports or revenue mint base units;
+    ///   the epoch is credited with what arrives after any transfer fee
+    /// * `entitlement_root` - Merkle root of (investor, balance) leaves at this slot
+    /// * `finalize` - Open the epoch for claims now; otherwise it can be
+    ///   topped up until `finalize_revenue_epoch`
+    /// * `claim_deadline` - Last timestamp claims are accepted; the rest can
+    ///   then be swept with `sweep_unclaimed_revenue`
     ///
     /// # Access Control
     /// - Only property authority can deposit revenue
@@ -178,25 +589,290 @@ pub mod hub_token_program {
     /// - Must have token holders (circulating supply > 0)
     pub fn deposit_revenue(
         ctx: Context<DepositRevenue>,
-        epoch_number: u64,
         amount: u64,
+        entitlement_root: [u8; 32],
+        finalize: bool,
+        claim_deadline: i64,
     ) -> Result<()> {
-        instructions::revenue_vault::handler_deposit_revenue(ctx, epoch_number, amount)
+        instructions::revenue_vault::handler_deposit_revenue(
+            ctx,
+            amount,
+            entitlement_root,
+            finalize,
+            claim_deadline,
+        )
+    }
+
+    /// Add revenue to a revenue epoch that is not finalized yet
+    ///
+    /// # Arguments
+    /// * `amount` - Amount to add, in the epoch's currency; the epoch is
+    ///   credited with what arrives after any transfer fee
+    ///
+    /// # Access Control
+    /// - Only property authority can top up revenue
+    /// - Property must be active
+    /// - Epoch must not be finalized
+    pub fn top_up_revenue(ctx: Context<TopUpRevenue>, amount: u64) -> Result<()> {
+        instructions::revenue_vault::handler_top_up_revenue(ctx, amount)
+    }
+
+    /// Finalize a revenue epoch, ending top-ups and opening it for claims
+    ///
+    /// # Access Control
+    /// - Only property authority can finalize
+    /// - Epoch must not be finalized
+    pub fn finalize_revenue_epoch(ctx: Context<FinalizeRevenueEpoch>) -> Result<()> {
+        instructions::revenue_vault::handler_finalize_revenue_epoch(ctx)
     }
 
     /// Claim proportional share of revenue for an epoch
     ///
     /// Token holders call this to receive their dividend based on their
-    /// token balance at the time of deposit.
+    /// token balance at the time of deposit. Token epochs pay to the
+    /// investor's associated token account for the revenue mint.
     ///
     /// # Calculation
-    /// `claim_amount = (investor_balance / eligible_supply) * total_revenue`
+    /// `claim_amount = (snapshot_balance / eligible_supply) * total_revenue`
+    ///
+    /// # Arguments
+    /// * `snapshot_balance` - Investor balance in the epoch's entitlement snapshot
+    /// * `proof` - Merkle proof of that balance against the entitlement root
     ///
     /// # Access Control
-    /// - Must hold property tokens
+    /// - Must have held property tokens at the snapshot
     /// - Can only claim each epoch once
-    /// - Epoch must be finalized
-    pub fn claim_revenue(ctx: Context<ClaimRevenue>) -> Result<()> {
-        instructions::revenue_vault::handler_claim_revenue(ctx)
+    /// - Epoch must be finalized and its claim deadline not passed
+    pub fn claim_revenue(
+        ctx: Context<ClaimRevenue>,
+        snapshot_balance: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::revenue_vault::handler_claim_revenue(ctx, snapshot_balance, proof)
+    }
+
+    /// Claim proportional shares of several revenue epochs in one transaction
+    ///
+    /// Each epoch is passed in `remaining_accounts` as a
+    /// (revenue_epoch, revenue vault, claim_record) triple in the order of
+    /// `entitlements`. All epochs must pay in the same currency, and the
+    /// summed amount reaches the investor in a single transfer from the first
+    /// epoch's vault.
+    ///
+    /// # Arguments
+    /// * `entitlements` - Snapshot balance and merkle proof for each epoch
+    ///
+    /// # Access Control
+    /// - Same checks as claim_revenue for every epoch
+    /// - At most MAX_BATCH_CLAIM_EPOCHS epochs per batch
+    pub fn claim_revenue_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRevenueBatch<'info>>,
+        entitlements: Vec<EpochEntitlement>,
+    ) -> Result<()> {
+        instructions::revenue_vault::handler_claim_revenue_batch(ctx, entitlements)
+    }
+
+    /// Sweep what is left in a revenue epoch after its claim deadline
+    ///
+    /// The remainder is added to a later, not yet finalized epoch when the
+    /// optional target accounts are passed, and returned to the authority
+    /// otherwise. The epoch and its vault are closed and their rent returned
+    /// to the authority.
+    ///
+    /// # Access Control
+    /// - Only property authority can sweep
+    /// - Claim deadline must have passed
+    pub fn sweep_unclaimed_revenue(ctx: Context<SweepUnclaimedRevenue>) -> Result<()> {
+        instructions::revenue_vault::handler_sweep_unclaimed_revenue(ctx)
+    }
+
+    // ========================================================================
+    // CONTINUOUS REVENUE - Cumulative reward-per-token distribution
+    // ========================================================================
+
+    /// Create the HolderRewards account of a wallet for a property
+    ///
+    /// Transfers to a wallet without one land in its associated token
+    /// account and earn no revenue until it exists; this picks those tokens
+    /// up. Minting and forced transfers create it automatically.
+    ///
+    /// # Access Control
+    /// - Anyone can pay for any wallet's account
+    pub fn initialize_holder_rewards(ctx: Context<InitializeHolderRewards>) -> Result<()> {
+        instructions::continuous_revenue::handler_initialize_holder_rewards(ctx)
+    }
+
+    /// Distribute revenue to all current holders pro rata to their balances
+    ///
+    /// Raises `acc_revenue_per_token` by `amount / tracked_supply` and
+    /// moves the SOL into the property's rewards vault. Only tokens recorded
+    /// on HolderRewards accounts share in it.
+    ///
+    /// # Arguments
+    /// * `amount` - Amount of SOL (in lamports) to distribute
+    ///
+    /// # Access Control
+    /// - Only property authority can distribute revenue
+    /// - Property must be active
+    /// - Must have tracked token holders (tracked supply > 0)
+    pub fn distribute_revenue(ctx: Context<DistributeRevenue>, amount: u64) -> Result<()> {
+        instructions::continuous_revenue::handler_distribute_revenue(ctx, amount)
+    }
+
+    /// Claim all revenue accrued to the holder across every distribution
+    ///
+    /// # Calculation
+    /// `pending + balance * acc_revenue_per_token - reward_debt`
+    ///
+    /// # Access Control
+    /// - Only the holder can claim
+    /// - Protocol must not be paused and property transfers not frozen
+    pub fn claim_all_revenue(ctx: Context<ClaimAllRevenue>) -> Result<()> {
+        instructions::continuous_revenue::handler_claim_all_revenue(ctx)
+    }
+
+    /// Lower a holder's recorded balance after direct Token-2022 burns
+    ///
+    /// Every token account of the holder for the mint is passed in
+    /// `remaining_accounts`; their total becomes the recorded balance, which
+    /// stops the burned tokens earning a share of later distributions.
+    ///
+    /// # Access Control
+    /// - Property authority or compliance authority
+    pub fn reconcile_holder_rewards(ctx: Context<ReconcileHolderRewards>) -> Result<()> {
+        instructions::continuous_revenue::handler_reconcile_holder_rewards(ctx)
+    }
+
+    // ========================================================================
+    // REDEMPTION POOL - Token buyback for liquidations and sales
+    // ========================================================================
+
+    /// Create and fund the redemption pool for a property
+    ///
+    /// The pool pays `price_per_token` per whole token in SOL, or in the
+    /// stablecoin given by the optional payment accounts, and is funded for
+    /// `max_tokens` up front. The pool is credited with what arrives; if the
+    /// stablecoin charges a transfer fee, `max_tokens` is lowered to what
+    /// that covers.
+    ///
+    /// # Arguments
+    /// * `price_per_token` - Payout per whole property token, in payment base units
+    /// * `start_ts` / `end_ts` - Redemption window
+    /// * `max_tokens` - Maximum property token base units to redeem
+    ///
+    /// # Access Control
+    /// - Only the property authority
+    pub fn create_redemption_pool(
+        ctx: Context<CreateRedemptionPool>,
+        price_per_token: u64,
+        start_ts: i64,
+        end_ts: i64,
+        max_tokens: u64,
+    ) -> Result<()> {
+        instructions::redemption::handler_create_redemption_pool(
+            ctx,
+            price_per_token,
+            start_ts,
+            end_ts,
+            max_tokens,
+        )
+    }
+
+    /// Redeem property tokens: burn them and receive the pool payout
+    ///
+    /// # Arguments
+    /// * `amount` - Property token base units to redeem
+    pub fn redeem_tokens(ctx: Context<RedeemTokens>, amount: u64) -> Result<()> {
+        instructions::redemption::handler_redeem_tokens(ctx, amount)
+    }
+
+    /// Close a finished redemption pool and refund what was not paid out
+    ///
+    /// # Access Control
+    /// - Only the authority who created the pool
+    pub fn close_redemption_pool(ctx: Context<CloseRedemptionPool>) -> Result<()> {
+        instructions::redemption::handler_close_redemption_pool(ctx)
+    }
+
+    // ========================================================================
+    // PRIMARY OFFERING - Escrowed subscription sale of new tokens
+    // ========================================================================
+
+    /// Open a primary offering selling new property tokens for a stablecoin
+    ///
+    /// Subscriptions are escrowed in a token account owned by the offering
+    /// until it is settled.
+    ///
+    /// # Arguments
+    /// * `params` - Price, soft/hard cap, ticket limits and subscription window
+    ///
+    /// # Access Control
+    /// - Only the property authority
+    /// - A fully subscribed offering must fit in the remaining supply
+    pub fn create_offering(ctx: Context<CreateOffering>, params: OfferingParams) -> Result<()> {
+        instructions::primary_offering::handler_create_offering(ctx, params)
+    }
+
+    /// Subscribe to an open offering by escrowing a stablecoin payment
+    ///
+    /// # Arguments
+    /// * `amount` - Quote base units to pay in
+    ///
+    /// # Access Control
+    /// - Investor must have a valid Hub Credential
+    /// - Running total must stay within the ticket limits and the hard cap
+    pub fn subscribe(ctx: Context<Subscribe>, amount: u64) -> Result<()> {
+        instructions::primary_offering::handler_subscribe(ctx, amount)
+    }
+
+    /// Settle a closed offering
+    ///
+    /// If the soft cap was reached, mints tokens to the subscriptions passed
+    /// in `remaining_accounts` (KYC re-checked) and releases their payments
+    /// to the authority; otherwise opens refunds. Subscriptions that can no
+    /// longer be issued are rejected and become refundable; mismatched
+    /// credential or profile accounts fail the settlement.
+    ///
+    /// # Access Control
+    /// - Only the authority who created the offering
+    /// - Property active, window ended or hard cap reached, and the
+    ///   settlement deadline not passed
+    pub fn settle_offering<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleOffering<'info>>,
+    ) -> Result<()> {
+        instructions::primary_offering::handler_settle_offering(ctx)
+    }
+
+    /// Take back an escrowed subscription payment
+    ///
+    /// # Access Control
+    /// - Only the subscriber
+    /// - Offering failed to reach its soft cap, the subscription was
+    ///   rejected at settlement, or it is still unsettled after the
+    ///   settlement deadline
+    pub fn refund_subscription(ctx: Context<RefundSubscription>) -> Result<()> {
+        instructions::primary_offering::handler_refund_subscription(ctx)
+    }
+
+    /// Fallback for SPL Transfer Hook interface instructions
+    ///
+    /// Token-2022 invokes the hook with the interface `Execute` discriminator
+    /// rather than Anchor's sighash, so it is decoded here and forwarded to
+    /// transfer_hook_execute.
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        match TransferHookInstruction::unpack(data)? {
+            TransferHookInstruction::Execute { amount } => {
+                __private::__global::transfer_hook_execute(
+                    program_id,
+                    accounts,
+                    &amount.to_le_bytes(),
+                )
+            }
+            _ => Err(ProgramError::InvalidInstructionData.into()),
+        }
     }
 }
@@ -0,0 +1,103 @@
+/// Lock-up and linear vesting of a holder's minted property tokens
+use anchor_lang::prelude::*;
+
+use crate::{constants::HOLDER_LOCKUP_SEED, error::RwaError};
+
+/// Lock-up terms given when minting
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct LockupSchedule {
+    /// Until this timestamp all of the locked tokens stay locked
+    pub lockup_until: i64,
+    /// Locked tokens vest linearly from lockup_until to this timestamp
+    /// (equal to lockup_until for a plain lock-up)
+    pub vesting_end: i64,
+}
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct HolderLockup {
+    /// Property token mint
+    pub mint: Pubkey,
+
+    /// Wallet whose balance is locked
+    pub owner: Pubkey,
+
+    /// Tokens subject to the schedule
+    pub amount: u64,
+
+    /// Until this timestamp the whole amount is locked
+    pub lockup_until: i64,
+
+    /// The amount vests linearly from lockup_until until this timestamp
+    pub vesting_end: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl HolderLockup {
+    /// Tokens still locked at `now`
+    pub fn locked_amount(&self, now: i64) -> Result<u64> {
+        if now < self.lockup_until {
+            return Ok(self.amount);
+        }
+        if now >= self.vesting_end {
+            return Ok(0);
+        }
+        // Round up so a fraction of a token is never released early
+        let remaining = (self.vesting_end - now) as u128;
+        let duration = (self.vesting_end - self.lockup_until) as u128;
+        let locked = (self.amount as u128)
+            .checked_mul(remaining)
+            .ok_or(RwaError::MathOverflow)?
+            .div_ceil(duration);
+        u64::try_from(locked).map_err(|_| RwaError::MathOverflow.into())
+    }
+
+    /// Locks `amount` more tokens under `schedule`
+    ///
+    /// Tokens still locked stay locked alongside the new ones, all vesting
+    /// on the later of the two schedules from no earlier than `now`, so
+    /// nothing is released sooner than either schedule would allow.
+    pub fn add(&mut self, amount: u64, schedule: LockupSchedule, now: i64) -> Result<()> {
+        let still_locked = self.locked_amount(now)?;
+        self.amount = still_locked
+            .checked_add(amount)
+            .ok_or(RwaError::MathOverflow)?;
+        if still_locked == 0 {
+            self.lockup_until = schedule.lockup_until;
+            self.vesting_end = schedule.vesting_end;
+        } else {
+            self.lockup_until = self.lockup_until.max(schedule.lockup_until).max(now);
+            self.vesting_end = self.vesting_end.max(schedule.vesting_end);
+        }
+        Ok(())
+    }
+}
+
+impl LockupSchedule {
+    /// Checks the schedule still locks something after `now`
+    pub fn validate(&self, now: i64) -> Result<()> {
+        require!(
+            self.vesting_end >= self.lockup_until && self.vesting_end > now,
+            RwaError::InvalidLockupSchedule
+        );
+        Ok(())
+    }
+}
+
+/// Tokens of a holder still locked up, read from their HolderLockup PDA, or 0
+/// when they have no lock-up (the account does not exist)
+pub fn holder_locked_amount(lockup: &AccountInfo, mint: &Pubkey, owner: &Pubkey) -> Result<u64> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[HOLDER_LOCKUP_SEED, mint.as_ref(), owner.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(lockup.key(), expected, RwaError::InvalidHolderLockup);
+
+    if lockup.owner != &crate::ID {
+        return Ok(0);
+    }
+    let lockup = HolderLockup::try_deserialize(&mut &lockup.try_borrow_data()?[..])?;
+    lockup.locked_amount(Clock::get()?.unix_timestamp)
+}
@@ -0,0 +1,113 @@
+/// Per-holder revenue accounting against the property's acc_revenue_per_token
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{HOLDER_REWARDS_SEED, REVENUE_PER_TOKEN_PRECISION},
+    error::RwaError,
+};
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct HolderRewards {
+    /// Property token mint
+    pub mint: Pubkey,
+
+    /// Wallet whose token accounts this tracks
+    pub owner: Pubkey,
+
+    /// Tokens held across all of the owner's token accounts
+    pub balance: u64,
+
+    /// balance * acc_revenue_per_token at the last settlement (scaled)
+    pub reward_debt: u128,
+
+    /// Settled revenue not yet claimed (lamports)
+    pub pending: u64,
+
+    /// Revenue claimed so far (lamports)
+    pub total_claimed: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl HolderRewards {
+    /// Starts tracking a holder, picking up `balance` tokens received before
+    /// the account existed; revenue distributed before now is not owed on them
+    pub fn start_tracking(
+        &mut self,
+        mint: Pubkey,
+        owner: Pubkey,
+        balance: u64,
+        acc_revenue_per_token: u128,
+        bump: u8,
+    ) -> Result<()> {
+        self.mint = mint;
+        self.owner = owner;
+        self.balance = balance;
+        self.reward_debt = (balance as u128)
+            .checked_mul(acc_revenue_per_token)
+            .ok_or(RwaError::MathOverflow)?;
+        self.pending = 0;
+        self.total_claimed = 0;
+        self.bump = bump;
+        Ok(())
+    }
+
+    /// Moves revenue accrued since the last settlement into `pending`
+    pub fn settle(&mut self, acc_revenue_per_token: u128) -> Result<()> {
+        let accrued = (self.balance as u128)
+            .checked_mul(acc_revenue_per_token)
+            .ok_or(RwaError::MathOverflow)?
+            .checked_sub(self.reward_debt)
+            .ok_or(RwaError::MathOverflow)?
+            / REVENUE_PER_TOKEN_PRECISION;
+        self.pending = self
+            .pending
+            .checked_add(u64::try_from(accrued).map_err(|_| RwaError::MathOverflow)?)
+            .ok_or(RwaError::MathOverflow)?;
+        self.reward_debt = (self.balance as u128)
+            .checked_mul(acc_revenue_per_token)
+            .ok_or(RwaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Settles, then records a balance change of `delta` tokens
+    pub fn apply_balance_change(
+        &mut self,
+        acc_revenue_per_token: u128,
+        increase: bool,
+        delta: u64,
+    ) -> Result<()> {
+        self.settle(acc_revenue_per_token)?;
+        self.balance = if increase {
+            self.balance.checked_add(delta)
+        } else {
+            self.balance.checked_sub(delta)
+        }
+        .ok_or(RwaError::MathOverflow)?;
+        self.reward_debt = (self.balance as u128)
+            .checked_mul(acc_revenue_per_token)
+            .ok_or(RwaError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// Loads a holder's HolderRewards, or None while it has not been created
+pub fn load_holder_rewards(
+    rewards: &AccountInfo,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<Option<HolderRewards>> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[HOLDER_REWARDS_SEED, mint.as_ref(), owner.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(rewards.key(), expected, RwaError::InvalidHolderRewards);
+
+    if rewards.owner != &crate::ID {
+        return Ok(None);
+    }
+    let rewards = HolderRewards::try_deserialize(&mut &rewards.try_borrow_data()?[..])?;
+    Ok(Some(rewards))
+}
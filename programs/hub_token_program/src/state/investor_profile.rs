@@ -0,0 +1,41 @@
+/// Compliance data about an investor wallet that Hub Credentials do not carry
+use anchor_lang::prelude::*;
+
+use crate::error::RwaError;
+
+/// ISO 3166-1 alpha-2 country code in upper-case ASCII (e.g. `*b"BR"`)
+/// (account fields spell out `[u8; 2]` so InitSpace can size them)
+pub type CountryCode = [u8; 2];
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct InvestorProfile {
+    /// Investor wallet
+    pub wallet: Pubkey,
+
+    /// Jurisdiction the investor was verified in (ISO 3166-1 alpha-2)
+    pub country: [u8; 2],
+
+    /// Trusted issuer (or registry admin) that recorded the jurisdiction
+    pub updated_by: Pubkey,
+
+    /// Timestamp of last update
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Checks a country code is two upper-case ASCII letters
+pub fn validate_country_code(country: &CountryCode) -> Result<()> {
+    require!(
+        country.iter().all(u8::is_ascii_uppercase),
+        RwaError::InvalidCountryCode
+    );
+    Ok(())
+}
+
+/// Printable form of a country code for logs
+pub fn country_code_str(country: &CountryCode) -> &str {
+    std::str::from_utf8(country).unwrap_or("??")
+}
@@ -0,0 +1,46 @@
+/// Program-wide registry of trusted Hub Credential issuers
+use anchor_lang::prelude::*;
+
+use crate::error::RwaError;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct IssuerRegistry {
+    /// Admin who can add, remove and suspend issuers
+    pub admin: Pubkey,
+
+    /// Issuers whose credentials are accepted
+    #[max_len(32)]
+    pub issuers: Vec<TrustedIssuer>,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct TrustedIssuer {
+    /// Issuer key written into Hub Credentials
+    pub issuer: Pubkey,
+
+    /// Suspended issuers stay registered but their credentials are rejected
+    pub is_suspended: bool,
+
+    /// Timestamp when the issuer was added
+    pub added_at: i64,
+}
+
+impl IssuerRegistry {
+    /// Returns the registry entry for an issuer, if any
+    pub fn find(&self, issuer: &Pubkey) -> Option<&TrustedIssuer> {
+        self.issuers.iter().find(|entry| entry.issuer == *issuer)
+    }
+
+    /// Checks that an issuer is registered and not suspended
+    pub fn require_trusted(&self, issuer: &Pubkey) -> Result<()> {
+        match self.find(issuer) {
+            Some(entry) if entry.is_suspended => Err(RwaError::IssuerSuspended.into()),
+            Some(_) => Ok(()),
+            None => Err(RwaError::IssuerNotTrusted.into()),
+        }
+    }
+}
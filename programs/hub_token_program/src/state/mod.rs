@@ -1,5 +1,23 @@
+pub mod holder_lockup;
+pub mod holder_rewards;
+pub mod investor_profile;
+pub mod issuer_registry;
+pub mod offering;
+pub mod pause_reason;
+pub mod program_config;
 pub mod property_details;
 pub mod property_state;
+pub mod redemption_pool;
+pub mod rewards_vault;
 
+pub use holder_lockup::*;
+pub use holder_rewards::*;
+pub use investor_profile::*;
+pub use issuer_registry::*;
+pub use offering::*;
+pub use pause_reason::*;
+pub use program_config::*;
 pub use property_details::*;
 pub use property_state::*;
+pub use redemption_pool::*;
+pub use rewards_vault::*;
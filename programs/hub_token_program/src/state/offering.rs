@@ -0,0 +1,159 @@
+/// Primary offering selling newly minted property tokens for a stablecoin
+use anchor_lang::prelude::*;
+
+use crate::{constants::OFFERING_SETTLEMENT_PERIOD_SECONDS, error::RwaError};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum OfferingStatus {
+    /// Accepting subscriptions (or closed and waiting for settlement)
+    Open,
+    /// Soft cap reached: subscribers receive tokens, the authority the proceeds
+    Succeeded,
+    /// Soft cap missed: subscribers can take their payment back
+    Failed,
+}
+
+/// Terms of an offering, fixed at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct OfferingParams {
+    /// Price per whole property token, in quote base units
+    pub price_per_token: u64,
+    /// Soft and hard cap of the raise, in quote base units
+    pub min_raise: u64,
+    pub max_raise: u64,
+    /// Minimum and maximum total subscription per investor, in quote base units
+    pub min_ticket: u64,
+    pub max_ticket: u64,
+    /// Subscription window (inclusive start, exclusive end)
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Offering {
+    /// Property whose tokens are offered
+    pub property_state: Pubkey,
+
+    /// Authority who created the offering and receives the proceeds
+    pub authority: Pubkey,
+
+    /// Stablecoin subscriptions are paid in, escrowed in the offering's
+    /// escrow token account until settlement
+    pub quote_mint: Pubkey,
+
+    /// Price per whole property token, in quote base units
+    /// Example: 50_000_000 = 50 USDC per token
+    pub price_per_token: u64,
+
+    /// Soft cap: minimum raise (quote base units) for the offering to succeed
+    pub min_raise: u64,
+
+    /// Hard cap: maximum raise (quote base units)
+    pub max_raise: u64,
+
+    /// Minimum and maximum total subscription per investor (quote base units)
+    pub min_ticket: u64,
+    pub max_ticket: u64,
+
+    /// Subscription window (inclusive start, exclusive end)
+    pub start_ts: i64,
+    pub end_ts: i64,
+
+    /// Quote base units escrowed by subscribers
+    pub total_raised: u64,
+
+    /// Quote base units released to the authority for issued subscriptions
+    pub total_settled: u64,
+
+    /// Quote base units refunded to subscribers
+    pub total_refunded: u64,
+
+    /// Property token base units minted to subscribers
+    pub tokens_issued: u64,
+
+    /// Number of investors who subscribed
+    pub subscriber_count: u32,
+
+    /// Outcome of the offering
+    pub status: OfferingStatus,
+
+    /// Timestamp when the offering was created
+    pub created_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Offering {
+    /// Checks whether subscriptions are accepted at the given time
+    pub fn is_open(&self, now: i64) -> bool {
+        self.status == OfferingStatus::Open && now >= self.start_ts && now < self.end_ts
+    }
+
+    /// Whether the outcome can be decided: the window ended or the hard cap was hit
+    pub fn is_closed(&self, now: i64) -> bool {
+        now >= self.end_ts || self.total_raised == self.max_raise
+    }
+
+    /// Whether subscribers can be refunded: settled as failed, or closed
+    /// below the soft cap without being settled yet
+    pub fn has_failed(&self, now: i64) -> bool {
+        match self.status {
+            OfferingStatus::Failed => true,
+            OfferingStatus::Open => self.is_closed(now) && self.total_raised < self.min_raise,
+            OfferingStatus::Succeeded => false,
+        }
+    }
+
+    /// Last moment settle_offering accepts subscriptions
+    pub fn settlement_deadline(&self) -> i64 {
+        self.end_ts.saturating_add(OFFERING_SETTLEMENT_PERIOD_SECONDS)
+    }
+
+    /// Whether the settlement deadline has passed, freeing unsettled
+    /// subscriptions for refund
+    pub fn settlement_expired(&self, now: i64) -> bool {
+        now > self.settlement_deadline()
+    }
+
+    /// Property token base units bought for `payment` quote base units
+    pub fn tokens_for(&self, payment: u64, decimals: u8) -> Result<u64> {
+        let tokens = (payment as u128)
+            .checked_mul(10u128.pow(decimals as u32))
+            .ok_or(RwaError::MathOverflow)?
+            .checked_div(self.price_per_token as u128)
+            .ok_or(RwaError::MathOverflow)?;
+        u64::try_from(tokens).map_err(|_| RwaError::MathOverflow.into())
+    }
+}
+
+/// An investor's escrowed subscription to an offering
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Subscription {
+    /// Offering subscribed to
+    pub offering: Pubkey,
+
+    /// Subscribing investor
+    pub investor: Pubkey,
+
+    /// Quote base units escrowed for this investor
+    pub amount: u64,
+
+    /// Property token base units minted at settlement
+    pub tokens_issued: u64,
+
+    /// Whether settlement has processed this subscription
+    pub settled: bool,
+
+    /// Rejected at settlement (KYC no longer valid, token account missing or
+    /// frozen, or holding limits exceeded); the payment is refundable
+    pub rejected: bool,
+
+    /// Timestamp of the first subscription
+    pub created_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
@@ -0,0 +1,18 @@
+/// Reason codes recorded with protocol pauses and property transfer freezes
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum PauseReason {
+    /// No reason given (also used when lifting a pause or freeze)
+    Unspecified,
+    /// Suspected exploit or key compromise
+    SecurityIncident,
+    /// Court order or regulator request
+    RegulatoryOrder,
+    /// Property sale, restructuring or other corporate action
+    CorporateAction,
+    /// Planned upgrade or migration
+    Maintenance,
+    /// Anything else, documented off-chain
+    Other,
+}
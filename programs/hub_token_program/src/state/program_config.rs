@@ -0,0 +1,52 @@
+/// Program-wide configuration singleton
+use anchor_lang::prelude::*;
+
+use super::PauseReason;
+use crate::error::RwaError;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ProgramConfig {
+    /// Protocol admin who can update the config
+    pub admin: Pubkey,
+
+    /// Hub Credential program whose accounts are accepted as KYC credentials
+    /// Lets devnet, mainnet and local tests use different credential programs
+    pub credential_program_id: Pubkey,
+
+    /// Recipient of protocol fees
+    pub fee_recipient: Pubkey,
+
+    /// Protocol-wide pause flag: blocks transfers, mints, burns and claims
+    /// on every property
+    pub is_paused: bool,
+
+    /// Reason given for the current pause
+    pub pause_reason: PauseReason,
+
+    /// Timestamp of last update
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Hub Credential program replaced by the last rotation, still accepted
+    /// while properties rewrite their ExtraAccountMetaList (equal to
+    /// credential_program_id once the transition is closed)
+    pub previous_credential_program_id: Pubkey,
+}
+
+impl ProgramConfig {
+    /// Fails while the protocol-wide pause is active
+    pub fn require_not_paused(&self) -> Result<()> {
+        require!(!self.is_paused, RwaError::ProtocolPaused);
+        Ok(())
+    }
+
+    /// Whether credentials owned by `program_id` are accepted: the configured
+    /// program, or the previous one during a rotation
+    pub fn accepts_credential_program(&self, program_id: &Pubkey) -> bool {
+        *program_id == self.credential_program_id
+            || *program_id == self.previous_credential_program_id
+    }
+}
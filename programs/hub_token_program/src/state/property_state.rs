@@ -1,6 +1,11 @@
 /// Main state account for tokenized real estate properties
 use anchor_lang::prelude::*;
-use super::PropertyDetails;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_2022::ID as TOKEN_2022_PROGRAM_ID,
+};
+use super::{country_code_str, load_holder_rewards, CountryCode, HolderRewards, PauseReason, PropertyDetails};
+use crate::error::RwaError;
 
 #[account]
 #[derive(InitSpace, Debug)]
@@ -41,9 +46,292 @@ pub struct PropertyState {
 
     /// PDA bump seed
     pub bump: u8,
+
+    // Fields below were appended after the first release; accounts created
+    // before them are brought to this layout with migrate_property_state.
+
+    /// Whether transfers also require a valid Hub Credential for the sender
+    pub verify_sender: bool,
+
+    /// Bitmask of accepted HubCredentialType values (bit = 1 << type)
+    /// Example: 0b0000_0100 = AccreditedInvestor only
+    pub allowed_credential_types: u8,
+
+    /// Issuers accepted for this property (must also be trusted in the IssuerRegistry)
+    /// Empty = every trusted issuer is accepted
+    #[max_len(8)]
+    pub accepted_issuers: Vec<Pubkey>,
+
+    /// Seconds after credential expiry during which holders may still move
+    /// tokens between their own accounts (0 = no grace period)
+    pub kyc_grace_period_seconds: i64,
+
+    /// Whether transfers, mints, burns and claims are frozen for this property
+    pub transfers_frozen: bool,
+
+    /// Reason given for the current freeze
+    pub freeze_reason: PauseReason,
+
+    /// Compliance officer who can freeze and thaw holder accounts
+    /// alongside the authority (defaults to the authority)
+    pub compliance_authority: Pubkey,
+
+    /// Revenue (lamports) distributed per token since creation, scaled by
+    /// REVENUE_PER_TOKEN_PRECISION
+    pub acc_revenue_per_token: u128,
+
+    /// Total revenue (lamports) distributed through the rewards vault
+    pub total_revenue_distributed: u64,
+
+    /// Number assigned to the next revenue epoch (epochs are numbered from 0)
+    pub next_revenue_epoch: u64,
+
+    /// Maximum token base units a single holder may own across their
+    /// accounts (0 = no limit)
+    pub max_tokens_per_holder: u64,
+
+    /// Maximum number of wallets holding a non-zero balance (0 = no limit)
+    pub max_holders: u32,
+
+    /// Number of wallets currently holding a non-zero balance
+    pub holder_count: u32,
+
+    /// Investor countries accepted for this property (ISO 3166-1 alpha-2)
+    /// Empty = every country not in the deny list is accepted
+    #[max_len(16)]
+    pub allowed_jurisdictions: Vec<[u8; 2]>,
+
+    /// Investor countries rejected for this property
+    #[max_len(16)]
+    pub denied_jurisdictions: Vec<[u8; 2]>,
+
+    /// Tokens recorded on HolderRewards accounts, over which continuous
+    /// revenue is divided; tokens of untracked wallets earn nothing
+    pub tracked_supply: u64,
+}
+
+/// PropertyState as first released, before any appended fields
+///
+/// Only read by migrate_property_state.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct LegacyPropertyState {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    #[max_len(50)]
+    pub property_name: String,
+    #[max_len(10)]
+    pub property_symbol: String,
+    pub total_supply: u64,
+    pub circulating_supply: u64,
+    pub details: PropertyDetails,
+    pub is_active: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub bump: u8,
 }
 
 impl PropertyState {
+    /// Checks whether a signer may freeze or thaw holder accounts
+    pub fn can_enforce_compliance(&self, signer: &Pubkey) -> bool {
+        *signer == self.authority || *signer == self.compliance_authority
+    }
+
+    /// Fails while transfers of this property's tokens are frozen
+    pub fn require_not_frozen(&self) -> Result<()> {
+        require!(!self.transfers_frozen, RwaError::PropertyTransfersFrozen);
+        Ok(())
+    }
+
+    /// Checks whether credentials from an issuer are accepted for this property
+    pub fn accepts_issuer(&self, issuer: &Pubkey) -> bool {
+        self.accepted_issuers.is_empty() || self.accepted_issuers.contains(issuer)
+    }
+
+    /// Whether investors must have a recorded jurisdiction for this property
+    pub fn restricts_jurisdictions(&self) -> bool {
+        !self.allowed_jurisdictions.is_empty() || !self.denied_jurisdictions.is_empty()
+    }
+
+    /// Fails unless an investor from `country` may hold this property's tokens
+    ///
+    /// Investors without a recorded jurisdiction are rejected once any
+    /// country list is configured.
+    pub fn require_jurisdiction_allowed(&self, country: Option<CountryCode>) -> Result<()> {
+        if !self.restricts_jurisdictions() {
+            return Ok(());
+        }
+        let Some(country) = country else {
+            msg!("No jurisdiction recorded for property {}", self.mint);
+            return err!(RwaError::JurisdictionNotAllowed);
+        };
+        let allowed = !self.denied_jurisdictions.contains(&country)
+            && (self.allowed_jurisdictions.is_empty()
+                || self.allowed_jurisdictions.contains(&country));
+        if !allowed {
+            msg!(
+                "Jurisdiction {} not allowed for property {}",
+                country_code_str(&country),
+                self.mint
+            );
+            return err!(RwaError::JurisdictionNotAllowed);
+        }
+        Ok(())
+    }
+
+    /// Starts tracking a holder whose HolderRewards was just created, adding
+    /// the `balance` they already hold to tracked_supply
+    pub fn start_tracking_holder(
+        &mut self,
+        holder_rewards: &mut HolderRewards,
+        owner: Pubkey,
+        balance: u64,
+        bump: u8,
+    ) -> Result<()> {
+        holder_rewards.start_tracking(self.mint, owner, balance, self.acc_revenue_per_token, bump)?;
+        self.tracked_supply = self
+            .tracked_supply
+            .checked_add(balance)
+            .ok_or(RwaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Records a holder's balance change on their HolderRewards and keeps
+    /// holder_count and tracked_supply in step
+    ///
+    /// Increases must respect max_tokens_per_holder and, for a new holder,
+    /// max_holders.
+    pub fn apply_holder_balance_change(
+        &mut self,
+        holder_rewards: &mut HolderRewards,
+        increase: bool,
+        delta: u64,
+    ) -> Result<()> {
+        let old_balance = holder_rewards.balance;
+        holder_rewards.apply_balance_change(self.acc_revenue_per_token, increase, delta)?;
+        self.record_holder_balance(old_balance, holder_rewards.balance)?;
+        self.tracked_supply = if increase {
+            self.tracked_supply.checked_add(delta)
+        } else {
+            self.tracked_supply.checked_sub(delta)
+        }
+        .ok_or(RwaError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Records a balance change of a holder without a HolderRewards, whose
+    /// associated token account held `balance` tokens before it
+    ///
+    /// Nothing accrues to untracked holders, so only the holding limits and
+    /// holder_count apply. Returns the new balance.
+    pub fn apply_untracked_balance_change(
+        &mut self,
+        balance: u64,
+        increase: bool,
+        delta: u64,
+    ) -> Result<u64> {
+        let new_balance = if increase {
+            balance.checked_add(delta)
+        } else {
+            balance.checked_sub(delta)
+        }
+        .ok_or(RwaError::MathOverflow)?;
+        self.record_holder_balance(balance, new_balance)?;
+        Ok(new_balance)
+    }
+
+    /// Records a balance change of a holder whose HolderRewards may not have
+    /// been created, writing it back if it exists
+    ///
+    /// Without one, the holder's tokens must all be in `token_account`, their
+    /// associated token account, which held `amount_before` before the
+    /// change. Returns the holder's new balance.
+    pub fn apply_balance_change_to_holder(
+        &mut self,
+        holder_rewards: &AccountInfo,
+        owner: &Pubkey,
+        token_account: &Pubkey,
+        amount_before: u64,
+        increase: bool,
+        delta: u64,
+    ) -> Result<u64> {
+        match load_holder_rewards(holder_rewards, &self.mint, owner)? {
+            Some(mut rewards) => {
+                self.apply_holder_balance_change(&mut rewards, increase, delta)?;
+                rewards.try_serialize(&mut &mut holder_rewards.try_borrow_mut_data()?[..])?;
+                Ok(rewards.balance)
+            }
+            None => {
+                require_keys_eq!(
+                    *token_account,
+                    get_associated_token_address_with_program_id(
+                        owner,
+                        &self.mint,
+                        &TOKEN_2022_PROGRAM_ID,
+                    ),
+                    RwaError::InvalidHolderRewards
+                );
+                self.apply_untracked_balance_change(amount_before, increase, delta)
+            }
+        }
+    }
+
+    /// Enforces the holding limits on a growing holder and updates
+    /// holder_count as a balance crosses zero
+    fn record_holder_balance(&mut self, old_balance: u64, new_balance: u64) -> Result<()> {
+        if new_balance > old_balance {
+            self.require_within_holding_limits(old_balance, new_balance)?;
+        }
+        if old_balance == 0 && new_balance > 0 {
+            self.holder_count = self
+                .holder_count
+                .checked_add(1)
+                .ok_or(RwaError::MathOverflow)?;
+        } else if old_balance > 0 && new_balance == 0 {
+            self.holder_count = self
+                .holder_count
+                .checked_sub(1)
+                .ok_or(RwaError::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that a holder growing from `old_balance` to `new_balance` stays
+    /// within max_tokens_per_holder and, if new, max_holders
+    pub fn require_within_holding_limits(&self, old_balance: u64, new_balance: u64) -> Result<()> {
+        require!(
+            self.max_tokens_per_holder == 0 || new_balance <= self.max_tokens_per_holder,
+            RwaError::HoldingLimitExceeded
+        );
+        if old_balance == 0 && new_balance > 0 {
+            require!(
+                self.max_holders == 0 || self.holder_count < self.max_holders,
+                RwaError::MaxHoldersExceeded
+            );
+        }
+        Ok(())
+    }
+
+    /// Lowers circulating_supply to the mint supply before a burn
+    ///
+    /// Holders can burn directly through Token-2022, bypassing this program,
+    /// so the mint supply may fall below the recorded supply. Only the
+    /// PropertyState PDA can mint, so a mint supply above it is rejected.
+    pub fn reconcile_circulating_supply(&mut self, mint_supply: u64) -> Result<()> {
+        require!(
+            self.circulating_supply >= mint_supply,
+            RwaError::SupplyMismatch
+        );
+        if self.circulating_supply > mint_supply {
+            msg!(
+                "Circulating supply reconciled from {} to mint supply {}",
+                self.circulating_supply,
+                mint_supply
+            );
+            self.circulating_supply = mint_supply;
+        }
+        Ok(())
+    }
+
     /// Checks if more tokens can be minted
     pub fn can_mint(&self, amount: u64) -> bool {
         self.is_active && self.circulating_supply + amount <= self.total_supply
@@ -0,0 +1,71 @@
+/// Funded pool paying holders who redeem (burn) their property tokens
+use anchor_lang::prelude::*;
+
+use crate::error::RwaError;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct RedemptionPool {
+    /// Property this pool redeems tokens for
+    pub property_state: Pubkey,
+
+    /// Authority who funded the pool and receives leftovers on close
+    pub authority: Pubkey,
+
+    /// Payout currency: None = SOL held by this account, Some = SPL mint
+    /// held in the pool's associated token account
+    pub payment_mint: Option<Pubkey>,
+
+    /// Payout per whole property token, in payment base units
+    /// Example: 1_500_000 = 1.5 USDC per token
+    pub price_per_token: u64,
+
+    /// Redemption window (inclusive start, exclusive end)
+    pub start_ts: i64,
+    pub end_ts: i64,
+
+    /// Maximum number of property tokens (base units) the pool will redeem,
+    /// lowered at creation to what the funding covers after any transfer fee
+    pub max_tokens: u64,
+
+    /// Property tokens (base units) redeemed so far
+    pub tokens_redeemed: u64,
+
+    /// Payment base units the pool received from the authority
+    pub total_funded: u64,
+
+    /// Payment base units paid out to holders
+    pub total_paid: u64,
+
+    /// Timestamp when the pool was created
+    pub created_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RedemptionPool {
+    /// Checks whether redemptions are accepted at the given time
+    pub fn is_open(&self, now: i64) -> bool {
+        now >= self.start_ts && now < self.end_ts
+    }
+
+    /// Payout for `amount` property token base units with the given decimals
+    pub fn payout_for(&self, amount: u64, decimals: u8) -> Result<u64> {
+        let payout = (amount as u128)
+            .checked_mul(self.price_per_token as u128)
+            .ok_or(RwaError::MathOverflow)?
+            .checked_div(10u128.pow(decimals as u32))
+            .ok_or(RwaError::MathOverflow)?;
+        u64::try_from(payout).map_err(|_| RwaError::MathOverflow.into())
+    }
+    /// Property token base units `payment` pays for with the given decimals
+    pub fn tokens_for(&self, payment: u64, decimals: u8) -> Result<u64> {
+        let tokens = (payment as u128)
+            .checked_mul(10u128.pow(decimals as u32))
+            .ok_or(RwaError::MathOverflow)?
+            .checked_div(self.price_per_token as u128)
+            .ok_or(RwaError::MathOverflow)?;
+        u64::try_from(tokens).map_err(|_| RwaError::MathOverflow.into())
+    }
+}
@@ -0,0 +1,12 @@
+/// Program-owned account holding continuously distributed revenue (lamports)
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct RewardsVault {
+    /// Property whose revenue this vault holds
+    pub property_state: Pubkey,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
@@ -14,7 +14,12 @@
 /// - bump: u8 (1 byte)
 use anchor_lang::prelude::*;
 
-use crate::{constants::HUB_CREDENTIAL_PROGRAM_ID, error::RwaError, events::*};
+use crate::{
+    constants::{HUB_CREDENTIAL_SEED, INVESTOR_PROFILE_SEED},
+    error::RwaError,
+    events::*,
+    state::{CountryCode, InvestorProfile, IssuerRegistry, ProgramConfig, PropertyState},
+};
 
 /// Credential Type enum matching the Hub Credential Protocol
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -29,6 +34,19 @@ pub enum HubCredentialType {
 }
 
 impl HubCredentialType {
+    /// Bitmask with every credential type allowed
+    pub const ALL_MASK: u8 = 0b0011_1111;
+
+    /// Bit representing this credential type in an allowed-types mask
+    pub fn mask(self) -> u8 {
+        1 << self as u8
+    }
+
+    /// Checks whether this credential type is in an allowed-types mask
+    pub fn is_allowed(self, allowed_mask: u8) -> bool {
+        allowed_mask & self.mask() != 0
+    }
+
     fn from_u8(value: u8) -> Option<Self> {
         match value {
             0 => Some(HubCredentialType::KycBasic),
@@ -64,6 +82,15 @@ impl HubCredentialStatus {
     }
 }
 
+/// Outcome of a successful Hub Credential verification
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CredentialStanding {
+    /// Credential is active and not expired
+    Valid,
+    /// Credential expired but is within the property's KYC grace period
+    InGracePeriod,
+}
+
 /// Hub Credential data structure (partial, only what we need)
 pub struct HubCredential {
     pub user: Pubkey,
@@ -134,29 +161,42 @@ impl HubCredential {
 ///
 /// # Arguments
 /// * `credential_account` - The Hub Credential account
+/// * `investor_profile` - The wallet's InvestorProfile PDA (may not exist)
 /// * `wallet` - The wallet address to verify
-/// * `property_mint` - The property token mint (for event logging)
+/// * `program_config` - Program config holding the Hub Credential program ID
+/// * `property_state` - The property whose credential requirements apply
+/// * `issuer_registry` - The program-wide trusted issuer registry
 ///
 /// # Returns
-/// * `Result<()>` - Ok if wallet has valid credential, Error otherwise
+/// * `Result<CredentialStanding>` - Ok if wallet has a valid credential or one
+///   within the property's grace period, Error otherwise. Callers decide which
+///   operations a credential in its grace period may perform.
 ///
 /// # Verification Steps
-/// 1. Verify the account is owned by the Hub Credential program
+/// 1. Verify the account is owned by the configured Hub Credential program,
+///    or the previous one while a rotation is in progress
 /// 2. Parse and verify the credential belongs to the wallet
-/// 3. Verify the credential is not expired
+/// 3. Verify the credential is not expired beyond the grace period
 /// 4. Verify the credential status is Active
+/// 5. Verify the credential type is allowed by the property
+/// 6. Verify the issuer is trusted, not suspended and accepted by the property
+/// 7. Verify the wallet's jurisdiction is allowed by the property
 pub fn verify_hub_credential(
     credential_account: &AccountInfo,
+    investor_profile: &AccountInfo,
     wallet: &Pubkey,
-    _property_mint: &Pubkey,
-) -> Result<()> {
+    program_config: &ProgramConfig,
+    property_state: &PropertyState,
+    issuer_registry: &IssuerRegistry,
+) -> Result<CredentialStanding> {
     msg!("Verifying Hub Credential for wallet: {}", wallet);
 
-    // 1. Verify account owner is the Hub Credential program
-    if credential_account.owner != &HUB_CREDENTIAL_PROGRAM_ID {
+    // 1. Verify account owner is the Hub Credential program (or the previous
+    //    one while a rotation is in progress)
+    if !program_config.accepts_credential_program(credential_account.owner) {
         msg!(
             "Invalid credential account owner: expected {}, got {}",
-            HUB_CREDENTIAL_PROGRAM_ID,
+            program_config.credential_program_id,
             credential_account.owner
         );
         return Err(RwaError::KycVerificationRequired.into());
@@ -187,9 +227,12 @@ pub fn verify_hub_credential(
         });
     }
 
-    // 5. Check expiration
+    // 5. Check expiration, allowing the property's grace period
     let current_time = Clock::get()?.unix_timestamp;
-    if current_time >= credential.expires_at {
+    let grace_ends_at = credential
+        .expires_at
+        .saturating_add(property_state.kyc_grace_period_seconds);
+    if current_time >= grace_ends_at {
         msg!(
             "Credential expired at {}, current time: {}",
             credential.expires_at,
@@ -197,6 +240,66 @@ pub fn verify_hub_credential(
         );
         return Err(RwaError::CredentialExpired.into());
     }
+    let standing = if current_time >= credential.expires_at {
+        CredentialStanding::InGracePeriod
+    } else {
+        CredentialStanding::Valid
+    };
+
+    // 6. Check the credential type is accepted for this property
+    if !credential
+        .credential_type
+        .is_allowed(property_state.allowed_credential_types)
+    {
+        msg!(
+            "Credential type {:?} not allowed for property {} (allowed mask: {:#08b})",
+            credential.credential_type,
+            property_state.mint,
+            property_state.allowed_credential_types
+        );
+        return Err(RwaError::CredentialTypeNotAllowed.into());
+    }
+
+    // 7. Check the issuer is trusted and accepted for this property
+    if let Err(error) = issuer_registry.require_trusted(&credential.issuer) {
+        msg!("Credential issuer {} is not trusted or suspended", credential.issuer);
+        return Err(error);
+    }
+    if !property_state.accepts_issuer(&credential.issuer) {
+        msg!(
+            "Credential issuer {} not accepted for property {}",
+            credential.issuer,
+            property_state.mint
+        );
+        return Err(RwaError::IssuerNotAcceptedForProperty.into());
+    }
+
+    // 8. Check the investor's jurisdiction against the property's country lists
+    if property_state.restricts_jurisdictions() {
+        let country = investor_country(investor_profile, wallet)?;
+        property_state.require_jurisdiction_allowed(country)?;
+    }
+
+    if standing == CredentialStanding::InGracePeriod {
+        // Let the API push renewal reminders
+        emit!(CredentialInGracePeriod {
+            wallet: *wallet,
+            credential: credential_account.key(),
+            mint: property_state.mint,
+            expires_at: credential.expires_at,
+            grace_ends_at,
+            timestamp: current_time,
+        });
+
+        msg!(
+            "Hub Credential for wallet {} expired at {}, in grace period until {}",
+            wallet,
+            credential.expires_at,
+            grace_ends_at
+        );
+
+        return Ok(standing);
+    }
 
     // Emit success event for audit trail
     emit!(HubCredentialVerified {
@@ -212,7 +315,30 @@ pub fn verify_hub_credential(
         credential.credential_type
     );
 
-    Ok(())
+    Ok(standing)
+}
+
+/// Reads the jurisdiction recorded in a wallet's InvestorProfile, if any
+pub fn investor_country(investor_profile: &AccountInfo, wallet: &Pubkey) -> Result<Option<CountryCode>> {
+    let (expected, _) = derive_investor_profile_pda(wallet);
+    require_keys_eq!(
+        investor_profile.key(),
+        expected,
+        RwaError::InvalidInvestorProfile
+    );
+
+    if investor_profile.owner != &crate::ID {
+        return Ok(None);
+    }
+    let profile = InvestorProfile::try_deserialize(&mut &investor_profile.try_borrow_data()?[..])?;
+    Ok(Some(profile.country))
+}
+
+/// Derive the InvestorProfile PDA for a wallet
+///
+/// Seeds: ["investor_profile", wallet] under this program
+pub fn derive_investor_profile_pda(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INVESTOR_PROFILE_SEED, wallet.as_ref()], &crate::ID)
 }
 
 /// Derive the Hub Credential PDA for a wallet
@@ -221,18 +347,18 @@ pub fn verify_hub_credential(
 /// - "credential" literal
 /// - The wallet address
 ///
-/// Seeds: ["credential", wallet]
-pub fn derive_hub_credential_pda(wallet: &Pubkey) -> (Pubkey, u8) {
+/// Seeds: ["credential", wallet] under the configured Hub Credential program
+pub fn derive_hub_credential_pda(wallet: &Pubkey, credential_program_id: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
-        &[b"credential", wallet.as_ref()],
-        &HUB_CREDENTIAL_PROGRAM_ID,
+        &[HUB_CREDENTIAL_SEED, wallet.as_ref()],
+        credential_program_id,
     )
 }
 
 /// Check if a Hub Credential exists for a wallet (without full verification)
-pub fn hub_credential_exists(credential_account: &AccountInfo) -> bool {
+pub fn hub_credential_exists(credential_account: &AccountInfo, credential_program_id: &Pubkey) -> bool {
     credential_account.data_len() >= HubCredential::MIN_SIZE
-        && credential_account.owner == &HUB_CREDENTIAL_PROGRAM_ID
+        && credential_account.owner == credential_program_id
 }
 
 #[cfg(test)]
@@ -242,11 +368,16 @@ mod tests {
     #[test]
     fn test_derive_hub_credential_pda() {
         let wallet = Pubkey::new_unique();
-        let (pda, _bump) = derive_hub_credential_pda(&wallet);
+        let credential_program_id = Pubkey::new_unique();
+        let (pda, _bump) = derive_hub_credential_pda(&wallet, &credential_program_id);
 
         // Verify PDA is deterministic
-        let (pda2, _) = derive_hub_credential_pda(&wallet);
+        let (pda2, _) = derive_hub_credential_pda(&wallet, &credential_program_id);
         assert_eq!(pda, pda2);
+
+        // Verify PDA depends on the configured credential program
+        let (other, _) = derive_hub_credential_pda(&wallet, &Pubkey::new_unique());
+        assert_ne!(pda, other);
     }
 
     #[test]
@@ -278,4 +409,13 @@ mod tests {
         );
         assert_eq!(HubCredentialStatus::from_u8(10), None);
     }
+
+    #[test]
+    fn test_credential_type_allowed_mask() {
+        let accredited_only = HubCredentialType::AccreditedInvestor.mask();
+        assert_eq!(accredited_only, 0b0000_0100);
+        assert!(HubCredentialType::AccreditedInvestor.is_allowed(accredited_only));
+        assert!(!HubCredentialType::KycBasic.is_allowed(accredited_only));
+        assert!(HubCredentialType::BrazilianCnpj.is_allowed(HubCredentialType::ALL_MASK));
+    }
 }
@@ -0,0 +1,71 @@
+/// Merkle proofs for revenue entitlement snapshots
+///
+/// At deposit time the authority commits the root of a tree whose leaves are
+/// (investor, balance) pairs taken at the deposit slot. Claims prove their
+/// snapshot balance against that root, so moving tokens afterwards cannot
+/// earn a second share.
+///
+/// Leaves and nodes are keccak256 hashes with distinct prefixes (0x00 leaf,
+/// 0x01 node) and sibling pairs are hashed in sorted order.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf committing `investor` to a snapshot balance
+pub fn entitlement_leaf(investor: &Pubkey, balance: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, investor.as_ref(), &balance.to_le_bytes()]).to_bytes()
+}
+
+/// Parent of two sibling nodes
+pub fn merkle_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+/// Checks that `leaf` is part of the tree with the given root
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof
+        .iter()
+        .fold(leaf, |node, sibling| merkle_node(&node, sibling));
+    computed == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_merkle_proof() {
+        let investors: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = investors
+            .iter()
+            .zip([600u64, 300, 100])
+            .map(|(investor, balance)| entitlement_leaf(investor, balance))
+            .collect();
+
+        // Three leaves: the odd one is promoted to the next level
+        let left = merkle_node(&leaves[0], &leaves[1]);
+        let root = merkle_node(&left, &leaves[2]);
+
+        assert!(verify_merkle_proof(&[leaves[1], leaves[2]], &root, leaves[0]));
+        assert!(verify_merkle_proof(&[leaves[0], leaves[2]], &root, leaves[1]));
+        assert!(verify_merkle_proof(&[left], &root, leaves[2]));
+
+        // A different balance or wallet does not verify
+        assert!(!verify_merkle_proof(
+            &[leaves[1], leaves[2]],
+            &root,
+            entitlement_leaf(&investors[0], 601)
+        ));
+        assert!(!verify_merkle_proof(
+            &[leaves[1], leaves[2]],
+            &root,
+            entitlement_leaf(&Pubkey::new_unique(), 600)
+        ));
+
+        // A single-leaf tree has the leaf as root and an empty proof
+        assert!(verify_merkle_proof(&[], &leaves[0], leaves[0]));
+    }
+}
@@ -1,3 +1,11 @@
 pub mod hub_credential_verification;
+pub mod merkle;
+pub mod program_account;
+pub mod property_metadata;
+pub mod token_account;
 
 pub use hub_credential_verification::*;
+pub use merkle::*;
+pub use program_account::*;
+pub use property_metadata::*;
+pub use token_account::*;
@@ -0,0 +1,81 @@
+/// Token-2022 TokenMetadata helpers for property mints
+///
+/// The mint carries its own TokenMetadata (MetadataPointer points at the mint)
+/// so wallets and explorers can display property tokens. The PropertyState PDA
+/// is the metadata update authority.
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+use anchor_spl::token_2022_extensions::{
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+};
+
+use crate::{error::RwaError, state::PropertyDetails};
+
+/// Additional metadata keys mirrored from PropertyDetails
+pub const METADATA_KEY_PROPERTY_ADDRESS: &str = "property_address";
+pub const METADATA_KEY_PROPERTY_TYPE: &str = "property_type";
+pub const METADATA_KEY_TOTAL_VALUE_USD: &str = "total_value_usd";
+pub const METADATA_KEY_RENTAL_YIELD_BPS: &str = "rental_yield_bps";
+
+/// Fields kept in sync with PropertyDetails: the URI plus the additional keys
+pub fn property_metadata_fields(details: &PropertyDetails) -> Vec<(Field, String)> {
+    vec![
+        (Field::Uri, details.metadata_uri.clone()),
+        (
+            Field::Key(METADATA_KEY_PROPERTY_ADDRESS.to_string()),
+            details.property_address.clone(),
+        ),
+        (
+            Field::Key(METADATA_KEY_PROPERTY_TYPE.to_string()),
+            details.property_type.clone(),
+        ),
+        (
+            Field::Key(METADATA_KEY_TOTAL_VALUE_USD.to_string()),
+            details.total_value_usd.to_string(),
+        ),
+        (
+            Field::Key(METADATA_KEY_RENTAL_YIELD_BPS.to_string()),
+            details.rental_yield_bps.to_string(),
+        ),
+    ]
+}
+
+/// Builds the full TokenMetadata for a new property mint
+pub fn property_token_metadata(
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    name: String,
+    symbol: String,
+    details: &PropertyDetails,
+) -> TokenMetadata {
+    let mut metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey(*update_authority),
+        mint: *mint,
+        name,
+        symbol,
+        ..Default::default()
+    };
+    for (field, value) in property_metadata_fields(details) {
+        metadata.update(field, value);
+    }
+    metadata
+}
+
+/// Reads the TokenMetadata stored in a Token-2022 mint
+pub fn read_token_metadata(mint: &AccountInfo) -> Result<TokenMetadata> {
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    let metadata = mint_state
+        .get_variable_len_extension::<TokenMetadata>()
+        .map_err(|_| RwaError::InvalidMint)?;
+    Ok(metadata)
+}
+
+/// TLV size of the metadata extension, used to fund the mint before reallocs
+pub fn token_metadata_len(metadata: &TokenMetadata) -> Result<usize> {
+    Ok(metadata.tlv_size_of()?)
+}
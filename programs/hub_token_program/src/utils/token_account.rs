@@ -0,0 +1,15 @@
+/// Token-2022 account helpers
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::{
+    spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccountState},
+    ID as TOKEN_2022_PROGRAM_ID,
+};
+
+/// Balance of a Token-2022 account, or 0 if it has not been created
+pub fn token_account_balance(token_account: &AccountInfo) -> Result<u64> {
+    if token_account.owner != &TOKEN_2022_PROGRAM_ID {
+        return Ok(0);
+    }
+    let data = token_account.try_borrow_data()?;
+    Ok(StateWithExtensions::<TokenAccountState>::unpack(&data)?.base.amount)
+}
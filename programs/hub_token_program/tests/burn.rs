@@ -0,0 +1,127 @@
+//! Burning property tokens
+mod common;
+
+use anchor_lang::{prelude::*, AccountDeserialize};
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use hub_token_program::{PropertyState, RwaError};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+async fn property_state(context: &mut ProgramTestContext, mint: &Pubkey) -> PropertyState {
+    let account = context
+        .banks_client
+        .get_account(property_state_address(mint))
+        .await
+        .unwrap()
+        .unwrap();
+    PropertyState::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+#[tokio::test]
+async fn test_burn_updates_circulating_supply() {
+    let holder = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let token_account = mint_tokens(&mut context, &holder.pubkey(), &mint, 1_000).await;
+
+    let burn = burn_tokens_instruction(&holder.pubkey(), &mint, 300);
+    process(&mut context, &[burn], &[&holder]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, &token_account).await, 700);
+    assert_eq!(
+        property_state(&mut context, &mint).await.circulating_supply,
+        700
+    );
+}
+
+#[tokio::test]
+async fn test_burn_rejects_foreign_token_account() {
+    let holder = Keypair::new();
+    let attacker = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &holder.pubkey(), &mint, 1_000).await;
+
+    let mut burn = burn_tokens_instruction(&attacker.pubkey(), &mint, 100);
+    // Point investor_token_account and investor_rewards at the holder's accounts
+    burn.accounts[3].pubkey = token_account_address(&holder.pubkey(), &mint);
+    burn.accounts[5].pubkey = holder_rewards_address(&holder.pubkey(), &mint);
+    assert_rwa_error(
+        process(&mut context, &[burn], &[&attacker]).await,
+        RwaError::Unauthorized,
+    );
+}
+
+#[tokio::test]
+async fn test_burn_reconciles_direct_token_2022_burns() {
+    let holder = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let token_account = mint_tokens(&mut context, &holder.pubkey(), &mint, 1_000).await;
+
+    // Burning straight through Token-2022 bypasses the program
+    let direct_burn = spl_token_2022::instruction::burn_checked(
+        &spl_token_2022::ID,
+        &token_account,
+        &mint,
+        &holder.pubkey(),
+        &[],
+        1,
+        DECIMALS,
+    )
+    .unwrap();
+    process(&mut context, &[direct_burn], &[&holder])
+        .await
+        .unwrap();
+
+    let burn = burn_tokens_instruction(&holder.pubkey(), &mint, 300);
+    process(&mut context, &[burn], &[&holder]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, &token_account).await, 699);
+    assert_eq!(
+        property_state(&mut context, &mint).await.circulating_supply,
+        699
+    );
+}
+
+#[tokio::test]
+async fn test_burn_rejects_supply_drift() {
+    let holder = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &holder.pubkey(), &mint, 1_000).await;
+
+    // Simulate the recorded supply drifting from the mint
+    let mut state = property_state(&mut context, &mint).await;
+    state.circulating_supply = 500;
+    let address = property_state_address(&mint);
+    let mut account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    context.set_account(&address, &account.into());
+
+    let burn = burn_tokens_instruction(&holder.pubkey(), &mint, 100);
+    assert_rwa_error(
+        process(&mut context, &[burn], &[&holder]).await,
+        RwaError::SupplyMismatch,
+    );
+}
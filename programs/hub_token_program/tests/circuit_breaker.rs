@@ -0,0 +1,199 @@
+//! Protocol-wide pause and per-property transfer freeze
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use common::*;
+use hub_token_program::{PauseReason, RwaError};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn set_protocol_pause_instruction(admin: &Pubkey, is_paused: bool) -> Instruction {
+    update_config_instruction(
+        admin,
+        hub_token_program::instruction::SetProtocolPause {
+            is_paused,
+            reason: PauseReason::SecurityIncident,
+        }
+        .data(),
+    )
+}
+
+fn set_transfer_freeze_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    is_frozen: bool,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SetTransferFreeze {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            mint: *mint,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SetTransferFreeze {
+            is_frozen,
+            reason: PauseReason::RegulatoryOrder,
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn test_protocol_pause_blocks_token_movement() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &sender.pubkey());
+    add_credential(&mut program_test, &receiver.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let source = mint_tokens(&mut context, &sender.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &receiver.pubkey(), &mint).await;
+
+    let pause = set_protocol_pause_instruction(&payer, true);
+    process(&mut context, &[pause], &[]).await.unwrap();
+
+    let transfer = transfer_checked_instruction(
+        &context,
+        &source,
+        &mint,
+        &destination,
+        &sender.pubkey(),
+        100,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[transfer.clone()], &[&sender]).await,
+        RwaError::ProtocolPaused,
+    );
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[mint_tokens_instruction(
+                &payer,
+                &receiver.pubkey(),
+                &mint,
+                1,
+            )],
+            &[],
+        )
+        .await,
+        RwaError::ProtocolPaused,
+    );
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[burn_tokens_instruction(&sender.pubkey(), &mint, 1)],
+            &[&sender],
+        )
+        .await,
+        RwaError::ProtocolPaused,
+    );
+
+    let unpause = set_protocol_pause_instruction(&payer, false);
+    process(&mut context, &[unpause], &[]).await.unwrap();
+    process(&mut context, &[transfer], &[&sender])
+        .await
+        .unwrap();
+    assert_eq!(token_balance(&mut context, &destination).await, 100);
+}
+
+#[tokio::test]
+async fn test_protocol_pause_requires_admin() {
+    let mut context = start(program_test()).await;
+    let intruder = Keypair::new();
+
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[set_protocol_pause_instruction(&intruder.pubkey(), true)],
+            &[&intruder],
+        )
+        .await,
+        RwaError::Unauthorized,
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_freeze_is_per_property() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &sender.pubkey());
+    add_credential(&mut program_test, &receiver.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let frozen_mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let other_mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let frozen_source = mint_tokens(&mut context, &sender.pubkey(), &frozen_mint, 1_000).await;
+    let other_source = mint_tokens(&mut context, &sender.pubkey(), &other_mint, 1_000).await;
+    let frozen_destination =
+        create_token_account(&mut context, &receiver.pubkey(), &frozen_mint).await;
+    let other_destination =
+        create_token_account(&mut context, &receiver.pubkey(), &other_mint).await;
+
+    // Only the property authority can freeze
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[set_transfer_freeze_instruction(
+                &sender.pubkey(),
+                &frozen_mint,
+                true,
+            )],
+            &[&sender],
+        )
+        .await,
+        RwaError::Unauthorized,
+    );
+
+    let freeze = set_transfer_freeze_instruction(&payer, &frozen_mint, true);
+    process(&mut context, &[freeze], &[]).await.unwrap();
+
+    let transfer = transfer_checked_instruction(
+        &context,
+        &frozen_source,
+        &frozen_mint,
+        &frozen_destination,
+        &sender.pubkey(),
+        100,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[transfer.clone()], &[&sender]).await,
+        RwaError::PropertyTransfersFrozen,
+    );
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[burn_tokens_instruction(&sender.pubkey(), &frozen_mint, 1)],
+            &[&sender],
+        )
+        .await,
+        RwaError::PropertyTransfersFrozen,
+    );
+
+    // Other properties are unaffected
+    let other_transfer = transfer_checked_instruction(
+        &context,
+        &other_source,
+        &other_mint,
+        &other_destination,
+        &sender.pubkey(),
+        100,
+    )
+    .await;
+    process(&mut context, &[other_transfer], &[&sender])
+        .await
+        .unwrap();
+
+    let unfreeze = set_transfer_freeze_instruction(&payer, &frozen_mint, false);
+    process(&mut context, &[unfreeze], &[]).await.unwrap();
+    process(&mut context, &[transfer], &[&sender])
+        .await
+        .unwrap();
+    assert_eq!(token_balance(&mut context, &frozen_destination).await, 100);
+}
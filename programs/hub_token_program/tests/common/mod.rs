@@ -0,0 +1,858 @@
+//! Shared helpers for the program-test integration suites
+#![allow(dead_code)]
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{bpf_loader_upgradeable, entrypoint::ProgramResult, instruction::Instruction},
+    InstructionData,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address_with_program_id, spl_associated_token_account,
+    },
+    token_2022::spl_token_2022,
+};
+use hub_token_program::{
+    derive_hub_credential_pda, derive_investor_profile_pda, entitlement_leaf, merkle_node,
+    LockupSchedule, PropertyDetails, CLAIM_RECORD_SEED, EXTRA_ACCOUNT_METAS_SEED,
+    HOLDER_LOCKUP_SEED, HOLDER_REWARDS_SEED, ISSUER_REGISTRY_SEED, PROGRAM_CONFIG_SEED,
+    PROPERTY_STATE_SEED, REVENUE_EPOCH_SEED, REVENUE_TOKEN_VAULT_SEED, REVENUE_VAULT_SEED,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+pub const DECIMALS: u8 = 6;
+
+/// Issuer of the credentials created by these helpers, trusted by `start`
+pub const TEST_ISSUER: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// Hub Credential program recorded in the program config by `start`
+pub const TEST_CREDENTIAL_PROGRAM: Pubkey = Pubkey::new_from_array([9; 32]);
+
+/// Adapts the Anchor entrypoint to the lifetimes expected by `processor!`
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    hub_token_program::entry(program_id, accounts, data)
+}
+
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "hub_token_program",
+        hub_token_program::ID,
+        processor!(process_instruction),
+    );
+    program_test.prefer_bpf(false);
+    program_test
+}
+
+/// Starts the test validator with the program config pointing at TEST_CREDENTIAL_PROGRAM
+/// and the issuer registry trusting TEST_ISSUER
+pub async fn start(program_test: ProgramTest) -> ProgramTestContext {
+    let mut context = program_test.start_with_context().await;
+    let admin = context.payer.pubkey();
+    set_program_data(&mut context, &admin);
+    let instructions = [
+        initialize_config_instruction(&admin),
+        initialize_issuer_registry_instruction(&admin),
+        update_issuer_registry_instruction(
+            &admin,
+            hub_token_program::instruction::AddTrustedIssuer {
+                issuer: TEST_ISSUER,
+            }
+            .data(),
+        ),
+    ];
+    process(&mut context, &instructions, &[]).await.unwrap();
+    context
+}
+
+/// Stores the ProgramData account naming `upgrade_authority`, which
+/// programs loaded with `processor!` do not get
+pub fn set_program_data(context: &mut ProgramTestContext, upgrade_authority: &Pubkey) {
+    // UpgradeableLoaderState::ProgramData { slot: 0, upgrade_authority_address: Some(..) }
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.push(1);
+    data.extend_from_slice(upgrade_authority.as_ref());
+    let account = Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: bpf_loader_upgradeable::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&program_data_address(), &account.into());
+}
+
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(
+        &[hub_token_program::ID.as_ref()],
+        &bpf_loader_upgradeable::ID,
+    )
+    .0
+}
+
+/// Creates the program config with `admin` as protocol admin and fee recipient
+pub fn initialize_config_instruction(admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::InitializeConfig {
+            admin: *admin,
+            program_data: program_data_address(),
+            program_config: program_config_address(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::InitializeConfig {
+            credential_program_id: TEST_CREDENTIAL_PROGRAM,
+            fee_recipient: *admin,
+        }
+        .data(),
+    }
+}
+
+pub fn initialize_issuer_registry_instruction(admin: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::InitializeIssuerRegistry {
+            admin: *admin,
+            program_config: program_config_address(),
+            issuer_registry: issuer_registry_address(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::InitializeIssuerRegistry {}.data(),
+    }
+}
+
+pub fn program_config_address() -> Pubkey {
+    Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], &hub_token_program::ID).0
+}
+
+/// Builds an admin instruction on the program config from its encoded data
+pub fn update_config_instruction(admin: &Pubkey, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::UpdateConfig {
+            admin: *admin,
+            program_config: program_config_address(),
+        }
+        .to_account_metas(None),
+        data,
+    }
+}
+
+/// Hub Credential PDA of `wallet` under TEST_CREDENTIAL_PROGRAM
+pub fn credential_address(wallet: &Pubkey) -> Pubkey {
+    derive_hub_credential_pda(wallet, &TEST_CREDENTIAL_PROGRAM).0
+}
+
+pub fn investor_profile_address(wallet: &Pubkey) -> Pubkey {
+    derive_investor_profile_pda(wallet).0
+}
+
+pub fn issuer_registry_address() -> Pubkey {
+    Pubkey::find_program_address(&[ISSUER_REGISTRY_SEED], &hub_token_program::ID).0
+}
+
+/// Builds an admin instruction on the issuer registry from its encoded data
+pub fn update_issuer_registry_instruction(admin: &Pubkey, data: Vec<u8>) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::UpdateIssuerRegistry {
+            admin: *admin,
+            issuer_registry: issuer_registry_address(),
+        }
+        .to_account_metas(None),
+        data,
+    }
+}
+
+/// Hub Credential account data: discriminator, user, issuer, type, status, issued_at, expires_at
+pub fn credential_data(
+    wallet: &Pubkey,
+    issuer: &Pubkey,
+    credential_type: u8,
+    status: u8,
+    expires_at: i64,
+) -> Vec<u8> {
+    let mut data = vec![0u8; 8];
+    data.extend_from_slice(wallet.as_ref());
+    data.extend_from_slice(issuer.as_ref());
+    data.push(credential_type);
+    data.push(status);
+    data.extend_from_slice(&0i64.to_le_bytes());
+    data.extend_from_slice(&expires_at.to_le_bytes());
+    data.push(0);
+    data
+}
+
+/// Adds an active KYC credential for `wallet` and returns its PDA
+pub fn add_credential(program_test: &mut ProgramTest, wallet: &Pubkey) -> Pubkey {
+    add_credential_with_type(program_test, wallet, 0)
+}
+
+/// Adds an active credential of the given HubCredentialType for `wallet`
+pub fn add_credential_with_type(
+    program_test: &mut ProgramTest,
+    wallet: &Pubkey,
+    credential_type: u8,
+) -> Pubkey {
+    add_credential_from_issuer(program_test, wallet, &TEST_ISSUER, credential_type)
+}
+
+/// Adds an active credential for `wallet` signed by `issuer`
+pub fn add_credential_from_issuer(
+    program_test: &mut ProgramTest,
+    wallet: &Pubkey,
+    issuer: &Pubkey,
+    credential_type: u8,
+) -> Pubkey {
+    let credential = credential_address(wallet);
+    program_test.add_account(
+        credential,
+        Account {
+            lamports: 1_000_000_000,
+            data: credential_data(wallet, issuer, credential_type, 0, i64::MAX),
+            owner: TEST_CREDENTIAL_PROGRAM,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+    credential
+}
+
+/// Funds `wallet` with 10 SOL so it can pay for the accounts it creates
+pub fn add_wallet(program_test: &mut ProgramTest, wallet: &Pubkey) {
+    program_test.add_account(
+        *wallet,
+        Account {
+            lamports: 10_000_000_000,
+            data: Vec::new(),
+            owner: anchor_lang::system_program::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+/// Overwrites the status of an existing credential (0 = Active, 2 = Revoked, 3 = Suspended)
+pub async fn set_credential_status(context: &mut ProgramTestContext, wallet: &Pubkey, status: u8) {
+    let credential = credential_address(wallet);
+    let mut account = context
+        .banks_client
+        .get_account(credential)
+        .await
+        .unwrap()
+        .unwrap();
+    account.data[8 + 65] = status;
+    context.set_account(&credential, &account.into());
+}
+
+/// Overwrites the expiry timestamp of an existing credential
+pub async fn set_credential_expiry(
+    context: &mut ProgramTestContext,
+    wallet: &Pubkey,
+    expires_at: i64,
+) {
+    let credential = credential_address(wallet);
+    let mut account = context
+        .banks_client
+        .get_account(credential)
+        .await
+        .unwrap()
+        .unwrap();
+    account.data[8 + 74..8 + 82].copy_from_slice(&expires_at.to_le_bytes());
+    context.set_account(&credential, &account.into());
+}
+
+pub async fn unix_timestamp(context: &mut ProgramTestContext) -> i64 {
+    context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+}
+
+pub fn property_state_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[PROPERTY_STATE_SEED, mint.as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+pub fn token_account_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(wallet, mint, &spl_token_2022::ID)
+}
+
+pub async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Creates a property mint owned by the payer and returns the mint keypair
+pub async fn create_property(context: &mut ProgramTestContext, total_supply: u64) -> Keypair {
+    let mint = Keypair::new();
+    let authority = context.payer.pubkey();
+    let (extra_account_meta_list, _) = Pubkey::find_program_address(
+        &[EXTRA_ACCOUNT_METAS_SEED, mint.pubkey().as_ref()],
+        &hub_token_program::ID,
+    );
+
+    let instruction = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::CreatePropertyMint {
+            authority,
+            mint: mint.pubkey(),
+            property_state: property_state_address(&mint.pubkey()),
+            extra_account_meta_list,
+            program_config: program_config_address(),
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::CreatePropertyMint {
+            property_name: "Edificio Santos Dumont".to_string(),
+            property_symbol: "EDSANTO".to_string(),
+            decimals: DECIMALS,
+            total_supply,
+            property_details: PropertyDetails {
+                property_address: "Av. Paulista, 1000, Sao Paulo - SP".to_string(),
+                property_type: "Commercial".to_string(),
+                total_value_usd: 100_000_000,
+                rental_yield_bps: 500,
+                metadata_uri: "ipfs://property".to_string(),
+            },
+        }
+        .data(),
+    };
+
+    process(context, &[instruction], &[&mint]).await.unwrap();
+    mint
+}
+
+/// Creates the Token-2022 associated token account for `wallet`
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+) -> Pubkey {
+    let instruction = spl_associated_token_account::instruction::create_associated_token_account(
+        &context.payer.pubkey(),
+        wallet,
+        mint,
+        &spl_token_2022::ID,
+    );
+    process(context, &[instruction], &[]).await.unwrap();
+    initialize_holder_rewards(context, wallet, mint).await;
+    token_account_address(wallet, mint)
+}
+
+async fn account_exists(context: &mut ProgramTestContext, address: &Pubkey) -> bool {
+    context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .is_some()
+}
+
+pub fn holder_rewards_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[HOLDER_REWARDS_SEED, mint.as_ref(), wallet.as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+pub fn holder_lockup_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[HOLDER_LOCKUP_SEED, mint.as_ref(), wallet.as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+/// Creates the HolderRewards of `wallet` so it can receive transfers of a
+/// property mint; does nothing for other mints or when it already exists
+pub async fn initialize_holder_rewards(
+    context: &mut ProgramTestContext,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+) {
+    let holder_rewards = holder_rewards_address(wallet, mint);
+    let is_property_mint = account_exists(context, &property_state_address(mint)).await;
+    if !is_property_mint || account_exists(context, &holder_rewards).await {
+        return;
+    }
+
+    let instruction = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::InitializeHolderRewards {
+            payer: context.payer.pubkey(),
+            owner: *wallet,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            holder_rewards,
+            owner_token_account: token_account_address(wallet, mint),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::InitializeHolderRewards {}.data(),
+    };
+    process(context, &[instruction], &[]).await.unwrap();
+}
+
+/// Creates a non-associated Token-2022 account for `wallet`
+pub async fn create_auxiliary_token_account(
+    context: &mut ProgramTestContext,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+) -> Pubkey {
+    use spl_token_2022::{
+        extension::ExtensionType, instruction::initialize_account3, state::Account as TokenAccount,
+    };
+
+    let account = Keypair::new();
+    let space = ExtensionType::try_calculate_account_len::<TokenAccount>(&[
+        ExtensionType::TransferHookAccount,
+    ])
+    .unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        solana_sdk::system_instruction::create_account(
+            &context.payer.pubkey(),
+            &account.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            &spl_token_2022::ID,
+        ),
+        initialize_account3(&spl_token_2022::ID, &account.pubkey(), mint, wallet).unwrap(),
+    ];
+    process(context, &instructions, &[&account]).await.unwrap();
+    initialize_holder_rewards(context, wallet, mint).await;
+    account.pubkey()
+}
+
+pub fn mint_tokens_instruction(
+    authority: &Pubkey,
+    investor: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::MintPropertyTokens {
+            authority: *authority,
+            investor: *investor,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            investor_token_account: token_account_address(investor, mint),
+            investor_credential: credential_address(investor),
+            investor_profile: investor_profile_address(investor),
+            program_config: program_config_address(),
+            issuer_registry: issuer_registry_address(),
+            investor_rewards: holder_rewards_address(investor, mint),
+            investor_lockup: None,
+            token_program: spl_token_2022::ID,
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::MintPropertyTokens {
+            amount,
+            lockup: None,
+        }
+        .data(),
+    }
+}
+
+/// Mints `amount` to the investor locked up until `lockup_until`, vesting linearly to `vesting_end`
+pub fn mint_locked_instruction(
+    authority: &Pubkey,
+    investor: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    lockup_until: i64,
+    vesting_end: i64,
+) -> Instruction {
+    let mut instruction = mint_tokens_instruction(authority, investor, mint, amount);
+    instruction.accounts = hub_token_program::accounts::MintPropertyTokens {
+        authority: *authority,
+        investor: *investor,
+        property_state: property_state_address(mint),
+        mint: *mint,
+        investor_token_account: token_account_address(investor, mint),
+        investor_credential: credential_address(investor),
+        investor_profile: investor_profile_address(investor),
+        program_config: program_config_address(),
+        issuer_registry: issuer_registry_address(),
+        investor_rewards: holder_rewards_address(investor, mint),
+        investor_lockup: Some(holder_lockup_address(investor, mint)),
+        token_program: spl_token_2022::ID,
+        associated_token_program: spl_associated_token_account::ID,
+        system_program: anchor_lang::system_program::ID,
+    }
+    .to_account_metas(None);
+    instruction.data = hub_token_program::instruction::MintPropertyTokens {
+        amount,
+        lockup: Some(LockupSchedule {
+            lockup_until,
+            vesting_end,
+        }),
+    }
+    .data();
+    instruction
+}
+
+pub fn burn_tokens_instruction(investor: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::BurnPropertyTokens {
+            investor: *investor,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            investor_token_account: token_account_address(investor, mint),
+            program_config: program_config_address(),
+            investor_rewards: holder_rewards_address(investor, mint),
+            investor_lockup: holder_lockup_address(investor, mint),
+            token_program: spl_token_2022::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::BurnPropertyTokens { amount }.data(),
+    }
+}
+
+/// Mints property tokens to `investor`, creating their token account first
+pub async fn mint_tokens(
+    context: &mut ProgramTestContext,
+    investor: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let token_account = create_token_account(context, investor, mint).await;
+    let instruction = mint_tokens_instruction(&context.payer.pubkey(), investor, mint, amount);
+    process(context, &[instruction], &[]).await.unwrap();
+    token_account
+}
+
+pub fn revenue_epoch_address(mint: &Pubkey, epoch_number: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            REVENUE_EPOCH_SEED,
+            property_state_address(mint).as_ref(),
+            &epoch_number.to_le_bytes(),
+        ],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+pub fn revenue_vault_address(revenue_epoch: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[REVENUE_VAULT_SEED, revenue_epoch.as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+pub fn revenue_token_vault_address(revenue_epoch: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[REVENUE_TOKEN_VAULT_SEED, revenue_epoch.as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+pub fn claim_record_address(revenue_epoch: &Pubkey, investor: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[CLAIM_RECORD_SEED, revenue_epoch.as_ref(), investor.as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+pub fn top_up_revenue_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    epoch_number: u64,
+    amount: u64,
+) -> Instruction {
+    let revenue_epoch = revenue_epoch_address(mint, epoch_number);
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::TopUpRevenue {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            revenue_epoch,
+            revenue_vault: revenue_vault_address(&revenue_epoch),
+            revenue_mint: None,
+            revenue_token_vault: None,
+            authority_revenue_account: None,
+            revenue_token_program: None,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::TopUpRevenue { amount }.data(),
+    }
+}
+
+pub fn finalize_revenue_epoch_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    epoch_number: u64,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::FinalizeRevenueEpoch {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            revenue_epoch: revenue_epoch_address(mint, epoch_number),
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::FinalizeRevenueEpoch {}.data(),
+    }
+}
+
+/// Entitlement snapshot root with one proof per leaf
+pub struct EntitlementTree {
+    pub root: [u8; 32],
+    pub proofs: Vec<Vec<[u8; 32]>>,
+}
+
+/// Builds the entitlement merkle tree for (investor, balance) pairs
+pub fn entitlement_tree(balances: &[(Pubkey, u64)]) -> EntitlementTree {
+    let mut level: Vec<[u8; 32]> = balances
+        .iter()
+        .map(|(investor, balance)| entitlement_leaf(investor, *balance))
+        .collect();
+    // positions[i] = index of leaf i's ancestor in the current level
+    let mut positions: Vec<usize> = (0..level.len()).collect();
+    let mut proofs = vec![Vec::new(); level.len()];
+
+    while level.len() > 1 {
+        for (leaf, position) in positions.iter_mut().enumerate() {
+            let sibling = *position ^ 1;
+            if sibling < level.len() {
+                proofs[leaf].push(level[sibling]);
+            }
+            *position /= 2;
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => merkle_node(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    EntitlementTree {
+        root: level[0],
+        proofs,
+    }
+}
+
+pub fn deposit_revenue_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    epoch_number: u64,
+    amount: u64,
+    entitlement_root: [u8; 32],
+    revenue_mint: Option<&Pubkey>,
+    finalize: bool,
+) -> Instruction {
+    let revenue_epoch = revenue_epoch_address(mint, epoch_number);
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::DepositRevenue {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            revenue_epoch,
+            revenue_vault: revenue_vault_address(&revenue_epoch),
+            revenue_mint: revenue_mint.copied(),
+            revenue_token_vault: revenue_mint.map(|_| revenue_token_vault_address(&revenue_epoch)),
+            authority_revenue_account: revenue_mint.map(|m| token_account_address(authority, m)),
+            revenue_token_program: revenue_mint.map(|_| spl_token_2022::ID),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::DepositRevenue {
+            amount,
+            entitlement_root,
+            finalize,
+            claim_deadline: i64::MAX,
+        }
+        .data(),
+    }
+}
+
+pub fn claim_revenue_instruction(
+    investor: &Pubkey,
+    mint: &Pubkey,
+    epoch_number: u64,
+    snapshot_balance: u64,
+    proof: Vec<[u8; 32]>,
+    revenue_mint: Option<&Pubkey>,
+) -> Instruction {
+    let revenue_epoch = revenue_epoch_address(mint, epoch_number);
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::ClaimRevenue {
+            investor: *investor,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            revenue_epoch,
+            claim_record: claim_record_address(&revenue_epoch, investor),
+            revenue_vault: revenue_vault_address(&revenue_epoch),
+            program_config: program_config_address(),
+            revenue_mint: revenue_mint.copied(),
+            revenue_token_vault: revenue_mint.map(|_| revenue_token_vault_address(&revenue_epoch)),
+            investor_revenue_account: revenue_mint.map(|m| token_account_address(investor, m)),
+            revenue_token_program: revenue_mint.map(|_| spl_token_2022::ID),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::ClaimRevenue {
+            snapshot_balance,
+            proof,
+        }
+        .data(),
+    }
+}
+
+/// Builds a Token-2022 transfer_checked with the hook's extra accounts resolved
+pub async fn transfer_checked_instruction(
+    context: &ProgramTestContext,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let banks_client = context.banks_client.clone();
+    spl_token_2022::offchain::create_transfer_checked_instruction_with_extra_metas(
+        &spl_token_2022::ID,
+        source,
+        mint,
+        destination,
+        owner,
+        &[],
+        amount,
+        DECIMALS,
+        |address| {
+            let mut banks_client = banks_client.clone();
+            async move {
+                banks_client
+                    .get_account(address)
+                    .await
+                    .map(|account| account.map(|account| account.data))
+                    .map_err(|error| error.into())
+            }
+        },
+    )
+    .await
+    .unwrap()
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, token_account: &Pubkey) -> u64 {
+    use spl_token_2022::{extension::StateWithExtensions, state::Account as TokenAccount};
+
+    let account = context
+        .banks_client
+        .get_account(*token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    StateWithExtensions::<TokenAccount>::unpack(&account.data)
+        .unwrap()
+        .base
+        .amount
+}
+
+/// Asserts that a transaction failed with the given program error
+pub fn assert_rwa_error(
+    result: std::result::Result<(), BanksClientError>,
+    expected: hub_token_program::RwaError,
+) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(expected), "unexpected error code")
+        }
+        error => panic!("unexpected error: {error:?}"),
+    }
+}
+
+/// Creates a Token-2022 stablecoin with a 1% transfer fee, minting `supply` to the payer
+pub async fn create_fee_stablecoin(context: &mut ProgramTestContext, supply: u64) -> Pubkey {
+    use spl_token_2022::{
+        extension::{transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType},
+        instruction::{initialize_mint2, mint_to},
+        state::Mint,
+    };
+
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let space =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig])
+            .unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        solana_sdk::system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            &spl_token_2022::ID,
+        ),
+        initialize_transfer_fee_config(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            Some(&payer),
+            Some(&payer),
+            100,
+            u64::MAX,
+        )
+        .unwrap(),
+        initialize_mint2(&spl_token_2022::ID, &mint.pubkey(), &payer, None, 6).unwrap(),
+    ];
+    process(context, &instructions, &[&mint]).await.unwrap();
+
+    let payer_account = create_token_account(context, &payer, &mint.pubkey()).await;
+    let mint_supply = mint_to(
+        &spl_token_2022::ID,
+        &mint.pubkey(),
+        &payer_account,
+        &payer,
+        &[],
+        supply,
+    )
+    .unwrap();
+    process(context, &[mint_supply], &[]).await.unwrap();
+    mint.pubkey()
+}
@@ -0,0 +1,414 @@
+//! Continuous revenue distribution through acc_revenue_per_token
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use anchor_spl::{associated_token::spl_associated_token_account, token_2022::spl_token_2022};
+use common::*;
+use hub_token_program::{RwaError, REWARDS_VAULT_SEED};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn distribute_revenue_instruction(authority: &Pubkey, mint: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::DistributeRevenue {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            rewards_vault: rewards_vault_address(mint),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::DistributeRevenue { amount }.data(),
+    }
+}
+
+fn claim_all_revenue_instruction(holder: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::ClaimAllRevenue {
+            holder: *holder,
+            property_state: property_state_address(mint),
+            holder_rewards: holder_rewards_address(holder, mint),
+            rewards_vault: rewards_vault_address(mint),
+            program_config: program_config_address(),
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::ClaimAllRevenue {}.data(),
+    }
+}
+
+fn rewards_vault_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[REWARDS_VAULT_SEED, property_state_address(mint).as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+fn reconcile_holder_rewards_instruction(
+    authority: &Pubkey,
+    holder: &Pubkey,
+    mint: &Pubkey,
+    token_accounts: &[Pubkey],
+) -> Instruction {
+    let mut accounts = hub_token_program::accounts::ReconcileHolderRewards {
+        authority: *authority,
+        property_state: property_state_address(mint),
+        mint: *mint,
+        holder_rewards: holder_rewards_address(holder, mint),
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        token_accounts
+            .iter()
+            .map(|token_account| AccountMeta::new_readonly(*token_account, false)),
+    );
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts,
+        data: hub_token_program::instruction::ReconcileHolderRewards {}.data(),
+    }
+}
+
+/// Claims all revenue of `holder` and returns the lamports received
+async fn claim_all(context: &mut ProgramTestContext, holder: &Keypair, mint: &Pubkey) -> u64 {
+    let before = context
+        .banks_client
+        .get_balance(holder.pubkey())
+        .await
+        .unwrap();
+    let claim = claim_all_revenue_instruction(&holder.pubkey(), mint);
+    process(context, &[claim], &[holder]).await.unwrap();
+    context
+        .banks_client
+        .get_balance(holder.pubkey())
+        .await
+        .unwrap()
+        - before
+}
+
+#[tokio::test]
+async fn test_claim_all_revenue_follows_balances_across_distributions() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob, &carol] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let alice_account = mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    mint_tokens(&mut context, &bob.pubkey(), &mint, 400).await;
+    let carol_account = create_token_account(&mut context, &carol.pubkey(), &mint).await;
+
+    let first = distribute_revenue_instruction(&payer, &mint, 1_000_000_000);
+    process(&mut context, &[first], &[]).await.unwrap();
+
+    // Half of Alice's tokens move to Carol between the two distributions
+    let transfer = transfer_checked_instruction(
+        &context,
+        &alice_account,
+        &mint,
+        &carol_account,
+        &alice.pubkey(),
+        300,
+    )
+    .await;
+    process(&mut context, &[transfer], &[&alice]).await.unwrap();
+
+    let second = distribute_revenue_instruction(&payer, &mint, 1_000_000_000);
+    process(&mut context, &[second], &[]).await.unwrap();
+
+    assert_eq!(claim_all(&mut context, &alice, &mint).await, 900_000_000);
+    assert_eq!(claim_all(&mut context, &bob, &mint).await, 800_000_000);
+    assert_eq!(claim_all(&mut context, &carol, &mint).await, 300_000_000);
+
+    // Everything owed was paid in one claim each
+    context.get_new_latest_blockhash().await.unwrap();
+    let again = claim_all_revenue_instruction(&alice.pubkey(), &mint);
+    assert_rwa_error(
+        process(&mut context, &[again], &[&alice]).await,
+        RwaError::NothingToClaim,
+    );
+}
+
+#[tokio::test]
+async fn test_burned_tokens_stop_accruing() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 500).await;
+    mint_tokens(&mut context, &bob.pubkey(), &mint, 500).await;
+
+    let first = distribute_revenue_instruction(&payer, &mint, 1_000_000_000);
+    process(&mut context, &[first], &[]).await.unwrap();
+
+    // Bob exits before the second distribution and keeps what he had earned
+    let burn = burn_tokens_instruction(&bob.pubkey(), &mint, 500);
+    process(&mut context, &[burn], &[&bob]).await.unwrap();
+
+    let second = distribute_revenue_instruction(&payer, &mint, 1_000_000_000);
+    process(&mut context, &[second], &[]).await.unwrap();
+
+    assert_eq!(claim_all(&mut context, &alice, &mint).await, 1_500_000_000);
+    assert_eq!(claim_all(&mut context, &bob, &mint).await, 500_000_000);
+}
+
+#[tokio::test]
+async fn test_transfers_reach_wallets_without_holder_rewards() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let alice_account = mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+
+    // Bob only has an associated token account
+    let create_ata = spl_associated_token_account::instruction::create_associated_token_account(
+        &payer,
+        &bob.pubkey(),
+        &mint,
+        &spl_token_2022::ID,
+    );
+    process(&mut context, &[create_ata], &[]).await.unwrap();
+    let bob_account = token_account_address(&bob.pubkey(), &mint);
+    let transfer = transfer_checked_instruction(
+        &context,
+        &alice_account,
+        &mint,
+        &bob_account,
+        &alice.pubkey(),
+        200,
+    )
+    .await;
+    process(&mut context, &[transfer], &[&alice]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &bob_account).await, 200);
+    assert!(context
+        .banks_client
+        .get_account(holder_rewards_address(&bob.pubkey(), &mint))
+        .await
+        .unwrap()
+        .is_none());
+
+    // Untracked tokens earn nothing until Bob's HolderRewards exists
+    let first = distribute_revenue_instruction(&payer, &mint, 600_000_000);
+    process(&mut context, &[first], &[]).await.unwrap();
+    initialize_holder_rewards(&mut context, &bob.pubkey(), &mint).await;
+    let second = distribute_revenue_instruction(&payer, &mint, 1_200_000_000);
+    process(&mut context, &[second], &[]).await.unwrap();
+
+    // Only Alice's tokens were tracked for the first distribution
+    assert_eq!(claim_all(&mut context, &alice, &mint).await, 1_400_000_000);
+    assert_eq!(claim_all(&mut context, &bob, &mint).await, 400_000_000);
+
+    // Bob's picked-up balance can be sent on
+    let transfer = transfer_checked_instruction(
+        &context,
+        &bob_account,
+        &mint,
+        &alice_account,
+        &bob.pubkey(),
+        200,
+    )
+    .await;
+    process(&mut context, &[transfer], &[&bob]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &alice_account).await, 600);
+}
+
+#[tokio::test]
+async fn test_every_holder_claims_after_direct_burn() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob, &carol] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let alice_account = mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    let bob_account = mint_tokens(&mut context, &bob.pubkey(), &mint, 400).await;
+
+    // Carol holds untracked tokens in her associated token account
+    let create_ata = spl_associated_token_account::instruction::create_associated_token_account(
+        &payer,
+        &carol.pubkey(),
+        &mint,
+        &spl_token_2022::ID,
+    );
+    process(&mut context, &[create_ata], &[]).await.unwrap();
+    let transfer = transfer_checked_instruction(
+        &context,
+        &bob_account,
+        &mint,
+        &token_account_address(&carol.pubkey(), &mint),
+        &bob.pubkey(),
+        100,
+    )
+    .await;
+    process(&mut context, &[transfer], &[&bob]).await.unwrap();
+
+    // Alice burns half of her tokens directly through Token-2022
+    let burn = spl_token_2022::instruction::burn(
+        &spl_token_2022::ID,
+        &alice_account,
+        &mint,
+        &alice.pubkey(),
+        &[],
+        300,
+    )
+    .unwrap();
+    process(&mut context, &[burn], &[&alice]).await.unwrap();
+
+    // The burned tokens are still tracked, so every lamport is claimable
+    let first = distribute_revenue_instruction(&payer, &mint, 900_000_000);
+    process(&mut context, &[first], &[]).await.unwrap();
+    assert_eq!(claim_all(&mut context, &alice, &mint).await, 600_000_000);
+    assert_eq!(claim_all(&mut context, &bob, &mint).await, 300_000_000);
+
+    // Reconciling needs the authority and only the holder's token accounts
+    let intruder = Keypair::new();
+    context.set_account(
+        &intruder.pubkey(),
+        &solana_sdk::account::Account::new(1_000_000_000, 0, &anchor_lang::system_program::ID)
+            .into(),
+    );
+    let reconcile = reconcile_holder_rewards_instruction(
+        &intruder.pubkey(),
+        &alice.pubkey(),
+        &mint,
+        &[alice_account],
+    );
+    assert_rwa_error(
+        process(&mut context, &[reconcile], &[&intruder]).await,
+        RwaError::Unauthorized,
+    );
+    let reconcile = reconcile_holder_rewards_instruction(
+        &payer,
+        &alice.pubkey(),
+        &mint,
+        &[alice_account, bob_account],
+    );
+    assert_rwa_error(
+        process(&mut context, &[reconcile], &[]).await,
+        RwaError::InvalidReconcileAccounts,
+    );
+    let reconcile =
+        reconcile_holder_rewards_instruction(&payer, &alice.pubkey(), &mint, &[alice_account]);
+    process(&mut context, &[reconcile], &[]).await.unwrap();
+
+    let second = distribute_revenue_instruction(&payer, &mint, 600_000_000);
+    process(&mut context, &[second], &[]).await.unwrap();
+    assert_eq!(claim_all(&mut context, &alice, &mint).await, 300_000_000);
+    assert_eq!(claim_all(&mut context, &bob, &mint).await, 300_000_000);
+
+    // Nothing is left behind in the vault beyond its rent reserve
+    let vault = context
+        .banks_client
+        .get_account(rewards_vault_address(&mint))
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert_eq!(vault.lamports, rent.minimum_balance(vault.data.len()));
+
+    context.get_new_latest_blockhash().await.unwrap();
+    let reconcile =
+        reconcile_holder_rewards_instruction(&payer, &alice.pubkey(), &mint, &[alice_account]);
+    assert_rwa_error(
+        process(&mut context, &[reconcile], &[]).await,
+        RwaError::NothingToReconcile,
+    );
+}
+
+#[tokio::test]
+async fn test_untracked_holders_send_and_burn_from_associated_account() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let alice_account = mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    let create_ata = spl_associated_token_account::instruction::create_associated_token_account(
+        &payer,
+        &bob.pubkey(),
+        &mint,
+        &spl_token_2022::ID,
+    );
+    process(&mut context, &[create_ata], &[]).await.unwrap();
+    let bob_account = token_account_address(&bob.pubkey(), &mint);
+    let transfer = transfer_checked_instruction(
+        &context,
+        &alice_account,
+        &mint,
+        &bob_account,
+        &alice.pubkey(),
+        300,
+    )
+    .await;
+    process(&mut context, &[transfer], &[&alice]).await.unwrap();
+
+    // Bob sends and burns without ever creating his HolderRewards
+    let transfer = transfer_checked_instruction(
+        &context,
+        &bob_account,
+        &mint,
+        &alice_account,
+        &bob.pubkey(),
+        100,
+    )
+    .await;
+    process(&mut context, &[transfer], &[&bob]).await.unwrap();
+    let burn = burn_tokens_instruction(&bob.pubkey(), &mint, 200);
+    process(&mut context, &[burn], &[&bob]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &bob_account).await, 0);
+    assert!(context
+        .banks_client
+        .get_account(holder_rewards_address(&bob.pubkey(), &mint))
+        .await
+        .unwrap()
+        .is_none());
+
+    let property_state = context
+        .banks_client
+        .get_account(property_state_address(&mint))
+        .await
+        .unwrap()
+        .unwrap();
+    let property_state =
+        hub_token_program::PropertyState::try_deserialize(&mut property_state.data.as_slice())
+            .unwrap();
+    assert_eq!(property_state.holder_count, 1);
+    assert_eq!(property_state.tracked_supply, 400);
+
+    let distribution = distribute_revenue_instruction(&payer, &mint, 400_000_000);
+    process(&mut context, &[distribution], &[]).await.unwrap();
+    assert_eq!(claim_all(&mut context, &alice, &mint).await, 400_000_000);
+}
@@ -0,0 +1,238 @@
+//! Per-property Hub Credential requirements on the mint and transfer paths
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use common::*;
+use hub_token_program::{HubCredentialType, RwaError};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn set_allowed_credential_types_instruction(
+    context: &ProgramTestContext,
+    mint: &Pubkey,
+    allowed_credential_types: u8,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SetAllowedCredentialTypes {
+            authority: context.payer.pubkey(),
+            property_state: property_state_address(mint),
+            mint: *mint,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SetAllowedCredentialTypes {
+            allowed_credential_types,
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn test_mint_rejects_credential_type_outside_allowed_set() {
+    let basic = Keypair::new();
+    let accredited = Keypair::new();
+    let mut program_test = program_test();
+    add_credential_with_type(
+        &mut program_test,
+        &basic.pubkey(),
+        HubCredentialType::KycBasic as u8,
+    );
+    add_credential_with_type(
+        &mut program_test,
+        &accredited.pubkey(),
+        HubCredentialType::AccreditedInvestor as u8,
+    );
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let instruction = set_allowed_credential_types_instruction(
+        &context,
+        &mint,
+        HubCredentialType::AccreditedInvestor.mask(),
+    );
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    create_token_account(&mut context, &basic.pubkey(), &mint).await;
+    let payer = context.payer.pubkey();
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[mint_tokens_instruction(
+                &payer,
+                &basic.pubkey(),
+                &mint,
+                1_000,
+            )],
+            &[],
+        )
+        .await,
+        RwaError::CredentialTypeNotAllowed,
+    );
+
+    let token_account = mint_tokens(&mut context, &accredited.pubkey(), &mint, 1_000).await;
+    assert_eq!(token_balance(&mut context, &token_account).await, 1_000);
+}
+
+#[tokio::test]
+async fn test_transfer_rejects_credential_type_outside_allowed_set() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+    let mut program_test = program_test();
+    add_credential_with_type(
+        &mut program_test,
+        &sender.pubkey(),
+        HubCredentialType::AccreditedInvestor as u8,
+    );
+    add_credential_with_type(
+        &mut program_test,
+        &receiver.pubkey(),
+        HubCredentialType::KycBasic as u8,
+    );
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let instruction = set_allowed_credential_types_instruction(
+        &context,
+        &mint,
+        HubCredentialType::AccreditedInvestor.mask(),
+    );
+    process(&mut context, &[instruction], &[]).await.unwrap();
+
+    let source = mint_tokens(&mut context, &sender.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &receiver.pubkey(), &mint).await;
+    let transfer = transfer_checked_instruction(
+        &context,
+        &source,
+        &mint,
+        &destination,
+        &sender.pubkey(),
+        400,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[transfer], &[&sender]).await,
+        RwaError::CredentialTypeNotAllowed,
+    );
+}
+
+#[tokio::test]
+async fn test_set_allowed_credential_types_rejects_empty_mask() {
+    let mut context = start(program_test()).await;
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+
+    let instruction = set_allowed_credential_types_instruction(&context, &mint, 0);
+    assert_rwa_error(
+        process(&mut context, &[instruction], &[]).await,
+        RwaError::InvalidCredentialTypeMask,
+    );
+}
+
+#[tokio::test]
+async fn test_mint_rejects_untrusted_and_suspended_issuers() {
+    let untrusted = Keypair::new();
+    let investor = Keypair::new();
+    let mut program_test = program_test();
+    add_credential_from_issuer(
+        &mut program_test,
+        &untrusted.pubkey(),
+        &Pubkey::new_unique(),
+        0,
+    );
+    add_credential(&mut program_test, &investor.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    create_token_account(&mut context, &untrusted.pubkey(), &mint).await;
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[mint_tokens_instruction(
+                &payer,
+                &untrusted.pubkey(),
+                &mint,
+                1_000,
+            )],
+            &[],
+        )
+        .await,
+        RwaError::IssuerNotTrusted,
+    );
+
+    let suspend = update_issuer_registry_instruction(
+        &payer,
+        hub_token_program::instruction::SuspendTrustedIssuer {
+            issuer: TEST_ISSUER,
+            is_suspended: true,
+        }
+        .data(),
+    );
+    process(&mut context, &[suspend], &[]).await.unwrap();
+
+    create_token_account(&mut context, &investor.pubkey(), &mint).await;
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[mint_tokens_instruction(
+                &payer,
+                &investor.pubkey(),
+                &mint,
+                1_000,
+            )],
+            &[],
+        )
+        .await,
+        RwaError::IssuerSuspended,
+    );
+}
+
+#[tokio::test]
+async fn test_property_accepted_issuers_override() {
+    let brazilian_issuer = Pubkey::new_unique();
+    let investor = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &investor.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let add = update_issuer_registry_instruction(
+        &payer,
+        hub_token_program::instruction::AddTrustedIssuer {
+            issuer: brazilian_issuer,
+        }
+        .data(),
+    );
+    process(&mut context, &[add], &[]).await.unwrap();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let restrict = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SetAcceptedIssuers {
+            authority: payer,
+            property_state: property_state_address(&mint),
+            mint,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SetAcceptedIssuers {
+            accepted_issuers: vec![brazilian_issuer],
+        }
+        .data(),
+    };
+    process(&mut context, &[restrict], &[]).await.unwrap();
+
+    create_token_account(&mut context, &investor.pubkey(), &mint).await;
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[mint_tokens_instruction(
+                &payer,
+                &investor.pubkey(),
+                &mint,
+                1_000,
+            )],
+            &[],
+        )
+        .await,
+        RwaError::IssuerNotAcceptedForProperty,
+    );
+}
@@ -0,0 +1,159 @@
+//! Forced transfers through the PermanentDelegate extension
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::StateWithExtensions,
+    state::{Account as TokenAccount, AccountState},
+};
+use common::*;
+use hub_token_program::RwaError;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Builds a forced transfer between the associated token accounts of two wallets
+fn force_transfer_instruction(
+    signer: &Pubkey,
+    mint: &Pubkey,
+    source_owner: &Pubkey,
+    destination_owner: &Pubkey,
+    amount: u64,
+    skip_destination_kyc: bool,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::ForceTransfer {
+            signer: *signer,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            source_token_account: token_account_address(source_owner, mint),
+            destination_token_account: token_account_address(destination_owner, mint),
+            destination_credential: credential_address(destination_owner),
+            destination_profile: investor_profile_address(destination_owner),
+            program_config: program_config_address(),
+            issuer_registry: issuer_registry_address(),
+            source_rewards: holder_rewards_address(source_owner, mint),
+            destination_rewards: holder_rewards_address(destination_owner, mint),
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::ForceTransfer {
+            amount,
+            skip_destination_kyc,
+            case_reference: "TJSP 1000123-45.2024.8.26.0100".to_string(),
+        }
+        .data(),
+    }
+}
+
+async fn account_state(context: &mut ProgramTestContext, token_account: &Pubkey) -> AccountState {
+    let account = context
+        .banks_client
+        .get_account(*token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    StateWithExtensions::<TokenAccount>::unpack(&account.data)
+        .unwrap()
+        .base
+        .state
+}
+
+#[tokio::test]
+async fn test_force_transfer_recovers_from_frozen_holder() {
+    let lost = Keypair::new();
+    let heir = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &lost.pubkey());
+    add_credential(&mut program_test, &heir.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let source = mint_tokens(&mut context, &lost.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &heir.pubkey(), &mint).await;
+
+    // The lost wallet is frozen and its credential has lapsed
+    let freeze = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::UpdateHolderFreeze {
+            signer: payer,
+            property_state: property_state_address(&mint),
+            mint,
+            holder_token_account: source,
+            token_program: spl_token_2022::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::FreezeHolder {
+            reason_hash: [1; 32],
+        }
+        .data(),
+    };
+    process(&mut context, &[freeze], &[]).await.unwrap();
+    set_credential_status(&mut context, &lost.pubkey(), 2).await;
+
+    // Holders cannot force transfers
+    let by_heir = force_transfer_instruction(
+        &heir.pubkey(),
+        &mint,
+        &lost.pubkey(),
+        &heir.pubkey(),
+        1_000,
+        false,
+    );
+    assert_rwa_error(
+        process(&mut context, &[by_heir], &[&heir]).await,
+        RwaError::Unauthorized,
+    );
+
+    let recover =
+        force_transfer_instruction(&payer, &mint, &lost.pubkey(), &heir.pubkey(), 600, false);
+    process(&mut context, &[recover], &[]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, &source).await, 400);
+    assert_eq!(token_balance(&mut context, &destination).await, 600);
+    assert_eq!(
+        account_state(&mut context, &source).await,
+        AccountState::Frozen
+    );
+}
+
+#[tokio::test]
+async fn test_force_transfer_checks_destination_kyc_unless_skipped() {
+    let holder = Keypair::new();
+    let unverified = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &holder.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &unverified.pubkey(), &mint).await;
+
+    let checked = force_transfer_instruction(
+        &payer,
+        &mint,
+        &holder.pubkey(),
+        &unverified.pubkey(),
+        1_000,
+        false,
+    );
+    assert_rwa_error(
+        process(&mut context, &[checked], &[]).await,
+        RwaError::KycVerificationRequired,
+    );
+
+    let skipped = force_transfer_instruction(
+        &payer,
+        &mint,
+        &holder.pubkey(),
+        &unverified.pubkey(),
+        1_000,
+        true,
+    );
+    process(&mut context, &[skipped], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &destination).await, 1_000);
+}
@@ -0,0 +1,127 @@
+//! Compliance freeze and thaw of individual holder accounts
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use anchor_spl::token_2022::spl_token_2022::{self, error::TokenError};
+use common::*;
+use hub_token_program::RwaError;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+const REASON_HASH: [u8; 32] = [42; 32];
+
+fn holder_freeze_instruction(
+    signer: &Pubkey,
+    mint: &Pubkey,
+    holder_token_account: &Pubkey,
+    data: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::UpdateHolderFreeze {
+            signer: *signer,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            holder_token_account: *holder_token_account,
+            token_program: spl_token_2022::ID,
+        }
+        .to_account_metas(None),
+        data,
+    }
+}
+
+fn freeze_data() -> Vec<u8> {
+    hub_token_program::instruction::FreezeHolder {
+        reason_hash: REASON_HASH,
+    }
+    .data()
+}
+
+fn thaw_data() -> Vec<u8> {
+    hub_token_program::instruction::ThawHolder {
+        reason_hash: REASON_HASH,
+    }
+    .data()
+}
+
+#[tokio::test]
+async fn test_compliance_authority_freezes_and_thaws_holder() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+    let compliance = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &sender.pubkey());
+    add_credential(&mut program_test, &receiver.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let source = mint_tokens(&mut context, &sender.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &receiver.pubkey(), &mint).await;
+
+    // Holders cannot freeze accounts
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[holder_freeze_instruction(
+                &receiver.pubkey(),
+                &mint,
+                &source,
+                freeze_data(),
+            )],
+            &[&receiver],
+        )
+        .await,
+        RwaError::Unauthorized,
+    );
+
+    let set_compliance = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SetComplianceAuthority {
+            authority: payer,
+            property_state: property_state_address(&mint),
+            mint,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SetComplianceAuthority {
+            compliance_authority: compliance.pubkey(),
+        }
+        .data(),
+    };
+    let freeze = holder_freeze_instruction(&compliance.pubkey(), &mint, &source, freeze_data());
+    process(&mut context, &[set_compliance, freeze], &[&compliance])
+        .await
+        .unwrap();
+
+    let transfer = transfer_checked_instruction(
+        &context,
+        &source,
+        &mint,
+        &destination,
+        &sender.pubkey(),
+        100,
+    )
+    .await;
+    let error = process(&mut context, &[transfer.clone()], &[&sender])
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(
+        error,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TokenError::AccountFrozen as u32)
+        )
+    );
+
+    // The property authority keeps the power to thaw
+    let thaw = holder_freeze_instruction(&payer, &mint, &source, thaw_data());
+    process(&mut context, &[thaw], &[]).await.unwrap();
+    process(&mut context, &[transfer], &[&sender])
+        .await
+        .unwrap();
+    assert_eq!(token_balance(&mut context, &destination).await, 100);
+}
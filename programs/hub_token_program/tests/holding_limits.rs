@@ -0,0 +1,188 @@
+//! Per-holder token caps and the maximum number of holders
+mod common;
+
+use anchor_lang::{
+    prelude::*, solana_program::instruction::Instruction, AccountDeserialize, InstructionData,
+};
+use common::*;
+use hub_token_program::{PropertyState, RwaError};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn set_holding_limits_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    max_tokens_per_holder: u64,
+    max_holders: u32,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SetHoldingLimits {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            mint: *mint,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SetHoldingLimits {
+            max_tokens_per_holder,
+            max_holders,
+        }
+        .data(),
+    }
+}
+
+async fn holder_count(context: &mut ProgramTestContext, mint: &Pubkey) -> u32 {
+    let account = context
+        .banks_client
+        .get_account(property_state_address(mint))
+        .await
+        .unwrap()
+        .unwrap();
+    PropertyState::try_deserialize(&mut account.data.as_slice())
+        .unwrap()
+        .holder_count
+}
+
+#[tokio::test]
+async fn test_mints_respect_holding_limits() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob, &carol] {
+        add_credential(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let limits = set_holding_limits_instruction(&payer, &mint, 1_000, 2);
+    process(&mut context, &[limits], &[]).await.unwrap();
+
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[mint_tokens_instruction(&payer, &alice.pubkey(), &mint, 401)],
+            &[],
+        )
+        .await,
+        RwaError::HoldingLimitExceeded,
+    );
+    let top_up = mint_tokens_instruction(&payer, &alice.pubkey(), &mint, 400);
+    process(&mut context, &[top_up], &[]).await.unwrap();
+
+    mint_tokens(&mut context, &bob.pubkey(), &mint, 300).await;
+    assert_eq!(holder_count(&mut context, &mint).await, 2);
+
+    create_token_account(&mut context, &carol.pubkey(), &mint).await;
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[mint_tokens_instruction(&payer, &carol.pubkey(), &mint, 100)],
+            &[],
+        )
+        .await,
+        RwaError::MaxHoldersExceeded,
+    );
+
+    // Burning everything frees a holder slot
+    let burn = burn_tokens_instruction(&bob.pubkey(), &mint, 300);
+    process(&mut context, &[burn], &[&bob]).await.unwrap();
+    assert_eq!(holder_count(&mut context, &mint).await, 1);
+    let mint_carol = mint_tokens_instruction(&payer, &carol.pubkey(), &mint, 100);
+    process(&mut context, &[mint_carol], &[]).await.unwrap();
+    assert_eq!(holder_count(&mut context, &mint).await, 2);
+}
+
+#[tokio::test]
+async fn test_transfers_track_holder_count_and_limits() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob, &carol] {
+        add_credential(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let alice_account = mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    let bob_account = mint_tokens(&mut context, &bob.pubkey(), &mint, 500).await;
+    let carol_account = create_token_account(&mut context, &carol.pubkey(), &mint).await;
+    let alice_savings = create_auxiliary_token_account(&mut context, &alice.pubkey(), &mint).await;
+    let limits = set_holding_limits_instruction(&payer, &mint, 1_000, 2);
+    process(&mut context, &[limits], &[]).await.unwrap();
+    assert_eq!(holder_count(&mut context, &mint).await, 2);
+
+    // A third holder is rejected
+    let to_carol = transfer_checked_instruction(
+        &context,
+        &bob_account,
+        &mint,
+        &carol_account,
+        &bob.pubkey(),
+        100,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[to_carol], &[&bob]).await,
+        RwaError::MaxHoldersExceeded,
+    );
+
+    // The cap counts a wallet's balance across all of its accounts
+    let over_cap = transfer_checked_instruction(
+        &context,
+        &bob_account,
+        &mint,
+        &alice_savings,
+        &bob.pubkey(),
+        401,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[over_cap], &[&bob]).await,
+        RwaError::HoldingLimitExceeded,
+    );
+
+    // Moving between a holder's own accounts does not change the count
+    let to_savings = transfer_checked_instruction(
+        &context,
+        &alice_account,
+        &mint,
+        &alice_savings,
+        &alice.pubkey(),
+        600,
+    )
+    .await;
+    process(&mut context, &[to_savings], &[&alice])
+        .await
+        .unwrap();
+    assert_eq!(holder_count(&mut context, &mint).await, 2);
+
+    // Bob leaving frees the slot Carol takes
+    let exit = transfer_checked_instruction(
+        &context,
+        &bob_account,
+        &mint,
+        &alice_savings,
+        &bob.pubkey(),
+        400,
+    )
+    .await;
+    process(&mut context, &[exit], &[&bob]).await.unwrap();
+    let rest = transfer_checked_instruction(
+        &context,
+        &bob_account,
+        &mint,
+        &carol_account,
+        &bob.pubkey(),
+        100,
+    )
+    .await;
+    process(&mut context, &[rest], &[&bob]).await.unwrap();
+    assert_eq!(holder_count(&mut context, &mint).await, 2);
+    assert_eq!(token_balance(&mut context, &bob_account).await, 0);
+    assert_eq!(token_balance(&mut context, &carol_account).await, 100);
+}
@@ -0,0 +1,283 @@
+//! Jurisdiction allow and deny lists
+mod common;
+
+use anchor_lang::{
+    prelude::*, solana_program::instruction::Instruction, AccountDeserialize, InstructionData,
+};
+use common::*;
+use hub_token_program::{InvestorProfile, RwaError};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+/// Like `process`, also returning the transaction's log messages
+async fn process_with_logs(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> (std::result::Result<(), BanksClientError>, Vec<String>) {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    let outcome = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    let logs = outcome.metadata.map(|m| m.log_messages).unwrap_or_default();
+    (outcome.result.map_err(Into::into), logs)
+}
+
+/// Asserts the transaction was rejected for the given investor country
+fn assert_jurisdiction_rejected(
+    (result, logs): (std::result::Result<(), BanksClientError>, Vec<String>),
+    country: &str,
+) {
+    assert_rwa_error(result, RwaError::JurisdictionNotAllowed);
+    assert!(
+        logs.iter()
+            .any(|log| log.contains(&format!("Jurisdiction {country} not allowed"))),
+        "country {country} missing from {logs:#?}"
+    );
+}
+
+fn set_investor_jurisdiction_instruction(
+    issuer: &Pubkey,
+    investor: &Pubkey,
+    country: &[u8; 2],
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SetInvestorJurisdiction {
+            issuer: *issuer,
+            investor: *investor,
+            investor_profile: investor_profile_address(investor),
+            issuer_registry: issuer_registry_address(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SetInvestorJurisdiction { country: *country }.data(),
+    }
+}
+
+fn set_jurisdictions_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    allowed_jurisdictions: &[[u8; 2]],
+    denied_jurisdictions: &[[u8; 2]],
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SetJurisdictions {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            mint: *mint,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SetJurisdictions {
+            allowed_jurisdictions: allowed_jurisdictions.to_vec(),
+            denied_jurisdictions: denied_jurisdictions.to_vec(),
+        }
+        .data(),
+    }
+}
+
+async fn recorded_country(context: &mut ProgramTestContext, wallet: &Pubkey) -> [u8; 2] {
+    let account = context
+        .banks_client
+        .get_account(investor_profile_address(wallet))
+        .await
+        .unwrap()
+        .unwrap();
+    InvestorProfile::try_deserialize(&mut account.data.as_slice())
+        .unwrap()
+        .country
+}
+
+#[tokio::test]
+async fn test_mints_enforce_jurisdiction_lists() {
+    let issuer = Keypair::new();
+    let mallory = Keypair::new();
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&issuer, &mallory] {
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    for wallet in [&alice, &bob, &carol] {
+        add_credential(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let add = update_issuer_registry_instruction(
+        &payer,
+        hub_token_program::instruction::AddTrustedIssuer {
+            issuer: issuer.pubkey(),
+        }
+        .data(),
+    );
+    process(&mut context, &[add], &[]).await.unwrap();
+
+    // Only trusted issuers and the registry admin record jurisdictions
+    let untrusted = set_investor_jurisdiction_instruction(&mallory.pubkey(), &bob.pubkey(), b"BR");
+    assert_rwa_error(
+        process(&mut context, &[untrusted], &[&mallory]).await,
+        RwaError::Unauthorized,
+    );
+    let lower_case = set_investor_jurisdiction_instruction(&payer, &alice.pubkey(), b"br");
+    assert_rwa_error(
+        process(&mut context, &[lower_case], &[]).await,
+        RwaError::InvalidCountryCode,
+    );
+    let alice_br = set_investor_jurisdiction_instruction(&issuer.pubkey(), &alice.pubkey(), b"BR");
+    let bob_us = set_investor_jurisdiction_instruction(&payer, &bob.pubkey(), b"US");
+    process(&mut context, &[alice_br, bob_us], &[&issuer])
+        .await
+        .unwrap();
+    assert_eq!(
+        recorded_country(&mut context, &alice.pubkey()).await,
+        *b"BR"
+    );
+
+    // Unrestricted properties accept investors without a recorded country
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &carol.pubkey(), &mint, 100).await;
+    create_token_account(&mut context, &alice.pubkey(), &mint).await;
+    create_token_account(&mut context, &bob.pubkey(), &mint).await;
+
+    let brazil_only = set_jurisdictions_instruction(&payer, &mint, &[*b"BR"], &[]);
+    process(&mut context, &[brazil_only], &[]).await.unwrap();
+    let mint_alice = mint_tokens_instruction(&payer, &alice.pubkey(), &mint, 100);
+    process(&mut context, &[mint_alice], &[]).await.unwrap();
+    for investor in [&bob, &carol] {
+        assert_rwa_error(
+            process(
+                &mut context,
+                &[mint_tokens_instruction(
+                    &payer,
+                    &investor.pubkey(),
+                    &mint,
+                    101,
+                )],
+                &[],
+            )
+            .await,
+            RwaError::JurisdictionNotAllowed,
+        );
+    }
+
+    // A deny list alone still requires a recorded country
+    let deny_us = set_jurisdictions_instruction(&payer, &mint, &[], &[*b"US"]);
+    process(&mut context, &[deny_us], &[]).await.unwrap();
+    for investor in [&bob, &carol] {
+        assert_rwa_error(
+            process(
+                &mut context,
+                &[mint_tokens_instruction(
+                    &payer,
+                    &investor.pubkey(),
+                    &mint,
+                    102,
+                )],
+                &[],
+            )
+            .await,
+            RwaError::JurisdictionNotAllowed,
+        );
+    }
+
+    // Only the recording issuer or the registry admin can change a country
+    let bob_pt = set_investor_jurisdiction_instruction(&issuer.pubkey(), &bob.pubkey(), b"PT");
+    assert_rwa_error(
+        process(&mut context, &[bob_pt], &[&issuer]).await,
+        RwaError::JurisdictionSetByOtherIssuer,
+    );
+    let bob_pt = set_investor_jurisdiction_instruction(&payer, &bob.pubkey(), b"PT");
+    process(&mut context, &[bob_pt], &[]).await.unwrap();
+    let mint_bob = mint_tokens_instruction(&payer, &bob.pubkey(), &mint, 103);
+    process(&mut context, &[mint_bob], &[]).await.unwrap();
+    let alice_pt = set_investor_jurisdiction_instruction(&issuer.pubkey(), &alice.pubkey(), b"PT");
+    process(&mut context, &[alice_pt], &[&issuer])
+        .await
+        .unwrap();
+    assert_eq!(
+        recorded_country(&mut context, &alice.pubkey()).await,
+        *b"PT"
+    );
+}
+
+#[tokio::test]
+async fn test_transfers_enforce_jurisdiction_lists() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let alice_br = set_investor_jurisdiction_instruction(&payer, &alice.pubkey(), b"BR");
+    let bob_us = set_investor_jurisdiction_instruction(&payer, &bob.pubkey(), b"US");
+    process(&mut context, &[alice_br, bob_us], &[])
+        .await
+        .unwrap();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let alice_account = mint_tokens(&mut context, &alice.pubkey(), &mint, 1_000).await;
+    let bob_account = mint_tokens(&mut context, &bob.pubkey(), &mint, 1_000).await;
+    let deny_us = set_jurisdictions_instruction(&payer, &mint, &[], &[*b"US"]);
+    process(&mut context, &[deny_us], &[]).await.unwrap();
+
+    // Both the receiving and the verified sending leg are checked
+    let to_bob = transfer_checked_instruction(
+        &context,
+        &alice_account,
+        &mint,
+        &bob_account,
+        &alice.pubkey(),
+        100,
+    )
+    .await;
+    assert_jurisdiction_rejected(
+        process_with_logs(&mut context, &[to_bob], &[&alice]).await,
+        "US",
+    );
+    let to_alice = transfer_checked_instruction(
+        &context,
+        &bob_account,
+        &mint,
+        &alice_account,
+        &bob.pubkey(),
+        100,
+    )
+    .await;
+    assert_jurisdiction_rejected(
+        process_with_logs(&mut context, &[to_alice], &[&bob]).await,
+        "US",
+    );
+
+    let bob_ca = set_investor_jurisdiction_instruction(&payer, &bob.pubkey(), b"CA");
+    process(&mut context, &[bob_ca], &[]).await.unwrap();
+    let to_bob = transfer_checked_instruction(
+        &context,
+        &alice_account,
+        &mint,
+        &bob_account,
+        &alice.pubkey(),
+        250,
+    )
+    .await;
+    process(&mut context, &[to_bob], &[&alice]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &bob_account).await, 1_250);
+}
@@ -0,0 +1,115 @@
+//! Credentials within the property's KYC grace period
+mod common;
+
+use common::*;
+use hub_token_program::{RwaError, KYC_GRACE_PERIOD_SECONDS};
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn test_grace_period_allows_only_self_transfers() {
+    let holder = Keypair::new();
+    let other = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    add_credential(&mut program_test, &other.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let holder_account = mint_tokens(&mut context, &holder.pubkey(), &mint, 1_000).await;
+    let other_account = mint_tokens(&mut context, &other.pubkey(), &mint, 1_000).await;
+    let holder_savings =
+        create_auxiliary_token_account(&mut context, &holder.pubkey(), &mint).await;
+
+    let now = unix_timestamp(&mut context).await;
+    set_credential_expiry(&mut context, &holder.pubkey(), now - 60).await;
+
+    // New mints are blocked
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[mint_tokens_instruction(&payer, &holder.pubkey(), &mint, 1)],
+            &[],
+        )
+        .await,
+        RwaError::CredentialExpired,
+    );
+
+    // Incoming transfers are blocked
+    let incoming = transfer_checked_instruction(
+        &context,
+        &other_account,
+        &mint,
+        &holder_account,
+        &other.pubkey(),
+        100,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[incoming], &[&other]).await,
+        RwaError::CredentialExpired,
+    );
+
+    // Transfers to other wallets are blocked
+    let outgoing = transfer_checked_instruction(
+        &context,
+        &holder_account,
+        &mint,
+        &other_account,
+        &holder.pubkey(),
+        100,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[outgoing], &[&holder]).await,
+        RwaError::CredentialExpired,
+    );
+
+    // Moving tokens to the holder's own account still works
+    let own = transfer_checked_instruction(
+        &context,
+        &holder_account,
+        &mint,
+        &holder_savings,
+        &holder.pubkey(),
+        100,
+    )
+    .await;
+    process(&mut context, &[own], &[&holder]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &holder_savings).await, 100);
+}
+
+#[tokio::test]
+async fn test_expired_beyond_grace_period_blocks_self_transfers() {
+    let holder = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let holder_account = mint_tokens(&mut context, &holder.pubkey(), &mint, 1_000).await;
+    let holder_savings =
+        create_auxiliary_token_account(&mut context, &holder.pubkey(), &mint).await;
+
+    let now = unix_timestamp(&mut context).await;
+    set_credential_expiry(
+        &mut context,
+        &holder.pubkey(),
+        now - KYC_GRACE_PERIOD_SECONDS - 1,
+    )
+    .await;
+
+    let own = transfer_checked_instruction(
+        &context,
+        &holder_account,
+        &mint,
+        &holder_savings,
+        &holder.pubkey(),
+        100,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[own], &[&holder]).await,
+        RwaError::CredentialExpired,
+    );
+}
@@ -0,0 +1,297 @@
+//! Lock-ups and linear vesting of minted tokens
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use common::*;
+use hub_token_program::RwaError;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn update_lockup_accounts(authority: &Pubkey, holder: &Pubkey, mint: &Pubkey) -> Vec<AccountMeta> {
+    hub_token_program::accounts::UpdateHolderLockup {
+        authority: *authority,
+        property_state: property_state_address(mint),
+        mint: *mint,
+        holder_lockup: holder_lockup_address(holder, mint),
+    }
+    .to_account_metas(None)
+}
+
+async fn warp_to(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+async fn transfer(
+    context: &mut ProgramTestContext,
+    from: &Keypair,
+    to: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> std::result::Result<(), solana_program_test::BanksClientError> {
+    let instruction = transfer_checked_instruction(
+        context,
+        &token_account_address(&from.pubkey(), mint),
+        mint,
+        &token_account_address(to, mint),
+        &from.pubkey(),
+        amount,
+    )
+    .await;
+    process(context, &[instruction], &[from]).await
+}
+
+#[tokio::test]
+async fn test_locked_tokens_vest_linearly() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    create_token_account(&mut context, &bob.pubkey(), &mint).await;
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 400).await;
+    let now = unix_timestamp(&mut context).await;
+
+    // A schedule needs the lockup account and must end in the future
+    let mut missing_account = mint_locked_instruction(
+        &payer,
+        &alice.pubkey(),
+        &mint,
+        1_000,
+        now + 100,
+        now + 1_100,
+    );
+    missing_account.accounts =
+        mint_tokens_instruction(&payer, &alice.pubkey(), &mint, 1_000).accounts;
+    assert_rwa_error(
+        process(&mut context, &[missing_account], &[]).await,
+        RwaError::MissingLockupAccount,
+    );
+    let backwards =
+        mint_locked_instruction(&payer, &alice.pubkey(), &mint, 1_000, now + 100, now + 50);
+    assert_rwa_error(
+        process(&mut context, &[backwards], &[]).await,
+        RwaError::InvalidLockupSchedule,
+    );
+
+    let locked = mint_locked_instruction(
+        &payer,
+        &alice.pubkey(),
+        &mint,
+        1_000,
+        now + 100,
+        now + 1_100,
+    );
+    process(&mut context, &[locked], &[]).await.unwrap();
+
+    // Only the unlocked tokens can leave before the cliff
+    assert_rwa_error(
+        transfer(&mut context, &alice, &bob.pubkey(), &mint, 401).await,
+        RwaError::TokensLocked,
+    );
+    transfer(&mut context, &alice, &bob.pubkey(), &mint, 400)
+        .await
+        .unwrap();
+
+    // Halfway through vesting half of the locked tokens are free
+    warp_to(&mut context, now + 600).await;
+    assert_rwa_error(
+        transfer(&mut context, &alice, &bob.pubkey(), &mint, 501).await,
+        RwaError::TokensLocked,
+    );
+    transfer(&mut context, &alice, &bob.pubkey(), &mint, 500)
+        .await
+        .unwrap();
+
+    warp_to(&mut context, now + 1_100).await;
+    transfer(&mut context, &alice, &bob.pubkey(), &mint, 500)
+        .await
+        .unwrap();
+    assert_eq!(
+        token_balance(&mut context, &token_account_address(&bob.pubkey(), &mint)).await,
+        1_400
+    );
+}
+
+#[tokio::test]
+async fn test_authority_extends_and_releases_lockup() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    for wallet in [&alice, &bob] {
+        create_token_account(&mut context, &wallet.pubkey(), &mint).await;
+    }
+    let now = unix_timestamp(&mut context).await;
+    let locked =
+        mint_locked_instruction(&payer, &alice.pubkey(), &mint, 1_000, now + 100, now + 100);
+    process(&mut context, &[locked], &[]).await.unwrap();
+
+    let extend = |lockup_until: i64, vesting_end: i64| Instruction {
+        program_id: hub_token_program::ID,
+        accounts: update_lockup_accounts(&payer, &alice.pubkey(), &mint),
+        data: hub_token_program::instruction::ExtendLockup {
+            lockup_until,
+            vesting_end,
+        }
+        .data(),
+    };
+    assert_rwa_error(
+        process(&mut context, &[extend(now + 50, now + 1_000)], &[]).await,
+        RwaError::LockupCannotBeShortened,
+    );
+    process(&mut context, &[extend(now + 1_000, now + 1_000)], &[])
+        .await
+        .unwrap();
+
+    // The original cliff has passed but the extension keeps everything locked
+    warp_to(&mut context, now + 500).await;
+    assert_rwa_error(
+        transfer(&mut context, &alice, &bob.pubkey(), &mint, 1).await,
+        RwaError::TokensLocked,
+    );
+
+    // Only the property authority can release
+    let bob_release = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: update_lockup_accounts(&bob.pubkey(), &alice.pubkey(), &mint),
+        data: hub_token_program::instruction::ReleaseLockup {}.data(),
+    };
+    assert_rwa_error(
+        process(&mut context, &[bob_release], &[&bob]).await,
+        RwaError::Unauthorized,
+    );
+    let release = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: update_lockup_accounts(&payer, &alice.pubkey(), &mint),
+        data: hub_token_program::instruction::ReleaseLockup {}.data(),
+    };
+    process(&mut context, &[release], &[]).await.unwrap();
+    assert!(context
+        .banks_client
+        .get_account(holder_lockup_address(&alice.pubkey(), &mint))
+        .await
+        .unwrap()
+        .is_none());
+
+    transfer(&mut context, &alice, &bob.pubkey(), &mint, 1_000)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_locked_tokens_cannot_be_burned() {
+    let alice = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &alice.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 400).await;
+    let now = unix_timestamp(&mut context).await;
+    let locked = mint_locked_instruction(
+        &payer,
+        &alice.pubkey(),
+        &mint,
+        1_000,
+        now + 100,
+        now + 1_100,
+    );
+    process(&mut context, &[locked], &[]).await.unwrap();
+
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[burn_tokens_instruction(&alice.pubkey(), &mint, 401)],
+            &[&alice],
+        )
+        .await,
+        RwaError::TokensLocked,
+    );
+    process(
+        &mut context,
+        &[burn_tokens_instruction(&alice.pubkey(), &mint, 400)],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_balance(&mut context, &token_account_address(&alice.pubkey(), &mint)).await,
+        1_000
+    );
+}
+
+#[tokio::test]
+async fn test_mint_without_schedule_leaves_lockup_alone() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    for wallet in [&alice, &bob] {
+        create_token_account(&mut context, &wallet.pubkey(), &mint).await;
+    }
+    let lockup = holder_lockup_address(&alice.pubkey(), &mint);
+    let plain_with_lockup = |amount: u64| {
+        let mut instruction = mint_tokens_instruction(&payer, &alice.pubkey(), &mint, amount);
+        instruction.accounts =
+            mint_locked_instruction(&payer, &alice.pubkey(), &mint, amount, 0, 0).accounts;
+        instruction
+    };
+
+    // A plain mint does not create the lockup account
+    assert_rwa_error(
+        process(&mut context, &[plain_with_lockup(500)], &[]).await,
+        RwaError::InvalidHolderLockup,
+    );
+    assert!(context
+        .banks_client
+        .get_account(lockup)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Nor does it change an existing one
+    let now = unix_timestamp(&mut context).await;
+    let locked =
+        mint_locked_instruction(&payer, &alice.pubkey(), &mint, 1_000, now + 100, now + 100);
+    process(&mut context, &[locked], &[]).await.unwrap();
+    process(&mut context, &[plain_with_lockup(500)], &[])
+        .await
+        .unwrap();
+    let account = context
+        .banks_client
+        .get_account(lockup)
+        .await
+        .unwrap()
+        .unwrap();
+    let holder_lockup =
+        hub_token_program::HolderLockup::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(holder_lockup.amount, 1_000);
+
+    assert_rwa_error(
+        transfer(&mut context, &alice, &bob.pubkey(), &mint, 501).await,
+        RwaError::TokensLocked,
+    );
+    transfer(&mut context, &alice, &bob.pubkey(), &mint, 500)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,118 @@
+//! Migrating PropertyState accounts created by the first release
+mod common;
+
+use anchor_lang::{
+    prelude::*, solana_program::instruction::Instruction, AccountDeserialize, Discriminator,
+    InstructionData,
+};
+use common::*;
+use hub_token_program::{HubCredentialType, LegacyPropertyState, PropertyState, RwaError};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+
+fn migrate_instruction(authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::MigratePropertyState {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::MigratePropertyState {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn test_migrate_legacy_property_state() {
+    let investor = Keypair::new();
+    let intruder = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &investor.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+    let fund = system_instruction::transfer(&payer, &intruder.pubkey(), 1_000_000_000);
+    process(&mut context, &[fund], &[]).await.unwrap();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &investor.pubkey(), &mint, 1_000).await;
+
+    // Rewrite the account as the first release stored it
+    let address = property_state_address(&mint);
+    let mut account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let current = PropertyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    let legacy = LegacyPropertyState {
+        authority: current.authority,
+        mint: current.mint,
+        property_name: current.property_name.clone(),
+        property_symbol: current.property_symbol.clone(),
+        total_supply: current.total_supply,
+        circulating_supply: current.circulating_supply,
+        details: current.details.clone(),
+        is_active: current.is_active,
+        created_at: current.created_at,
+        updated_at: current.updated_at,
+        bump: current.bump,
+    };
+    let mut data = PropertyState::DISCRIMINATOR.to_vec();
+    legacy.serialize(&mut data).unwrap();
+    data.resize(8 + LegacyPropertyState::INIT_SPACE, 0);
+    account.data = data;
+    context.set_account(&address, &account.into());
+
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[migrate_instruction(&intruder.pubkey(), &mint)],
+            &[&intruder],
+        )
+        .await,
+        RwaError::Unauthorized,
+    );
+    process(&mut context, &[migrate_instruction(&payer, &mint)], &[])
+        .await
+        .unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), 8 + PropertyState::INIT_SPACE);
+    let migrated = PropertyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(migrated.property_name, current.property_name);
+    assert_eq!(migrated.circulating_supply, 1_000);
+    assert_eq!(migrated.compliance_authority, payer);
+    assert_eq!(
+        migrated.allowed_credential_types,
+        HubCredentialType::ALL_MASK
+    );
+    assert!(migrated.verify_sender);
+
+    // Migrated properties work as usual and cannot be migrated twice
+    process(
+        &mut context,
+        &[mint_tokens_instruction(
+            &payer,
+            &investor.pubkey(),
+            &mint,
+            500,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_rwa_error(
+        process(&mut context, &[migrate_instruction(&payer, &mint)], &[]).await,
+        RwaError::PropertyStateNotMigratable,
+    );
+}
@@ -0,0 +1,592 @@
+//! Primary offerings escrowing stablecoin subscriptions until settlement
+mod common;
+
+use anchor_lang::{
+    prelude::*, solana_program::instruction::Instruction, AccountDeserialize, InstructionData,
+};
+use anchor_spl::{
+    associated_token::spl_associated_token_account,
+    token_2022::spl_token_2022::{self, instruction as token_instruction, state::Mint},
+};
+use common::*;
+use hub_token_program::{
+    Offering, OfferingParams, OfferingStatus, PropertyState, RwaError, OFFERING_ESCROW_SEED,
+    OFFERING_SEED, OFFERING_SETTLEMENT_PERIOD_SECONDS, SUBSCRIPTION_SEED,
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+
+const TOKEN: u64 = 1_000_000;
+
+fn offering_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[OFFERING_SEED, property_state_address(mint).as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+fn escrow_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[OFFERING_ESCROW_SEED, offering_address(mint).as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+fn subscription_address(mint: &Pubkey, investor: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            SUBSCRIPTION_SEED,
+            offering_address(mint).as_ref(),
+            investor.as_ref(),
+        ],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+/// 50 USDC per token, raising 100 to 500 USDC in tickets of 10 to 200 USDC
+fn offering_params(start_ts: i64, end_ts: i64) -> OfferingParams {
+    OfferingParams {
+        price_per_token: 50 * TOKEN,
+        min_raise: 100 * TOKEN,
+        max_raise: 500 * TOKEN,
+        min_ticket: 10 * TOKEN,
+        max_ticket: 200 * TOKEN,
+        start_ts,
+        end_ts,
+    }
+}
+
+fn create_offering_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    quote_mint: &Pubkey,
+    params: OfferingParams,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::CreateOffering {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            offering: offering_address(mint),
+            quote_mint: *quote_mint,
+            offering_escrow: escrow_address(mint),
+            quote_token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::CreateOffering { params }.data(),
+    }
+}
+
+fn subscribe_instruction(
+    investor: &Pubkey,
+    mint: &Pubkey,
+    quote_mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::Subscribe {
+            investor: *investor,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            offering: offering_address(mint),
+            subscription: subscription_address(mint, investor),
+            quote_mint: *quote_mint,
+            offering_escrow: escrow_address(mint),
+            investor_quote_account: token_account_address(investor, quote_mint),
+            investor_credential: credential_address(investor),
+            investor_profile: investor_profile_address(investor),
+            program_config: program_config_address(),
+            issuer_registry: issuer_registry_address(),
+            investor_rewards: holder_rewards_address(investor, mint),
+            investor_token_account: token_account_address(investor, mint),
+            quote_token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::Subscribe { amount }.data(),
+    }
+}
+
+fn settle_offering_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    quote_mint: &Pubkey,
+    subscribers: &[Pubkey],
+) -> Instruction {
+    let mut accounts = hub_token_program::accounts::SettleOffering {
+        authority: *authority,
+        property_state: property_state_address(mint),
+        mint: *mint,
+        offering: offering_address(mint),
+        quote_mint: *quote_mint,
+        offering_escrow: escrow_address(mint),
+        authority_quote_account: token_account_address(authority, quote_mint),
+        program_config: program_config_address(),
+        issuer_registry: issuer_registry_address(),
+        quote_token_program: spl_token_2022::ID,
+        token_program: spl_token_2022::ID,
+    }
+    .to_account_metas(None);
+    for investor in subscribers {
+        accounts.extend([
+            AccountMeta::new(subscription_address(mint, investor), false),
+            AccountMeta::new(token_account_address(investor, mint), false),
+            AccountMeta::new_readonly(credential_address(investor), false),
+            AccountMeta::new_readonly(investor_profile_address(investor), false),
+            AccountMeta::new(holder_rewards_address(investor, mint), false),
+        ]);
+    }
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts,
+        data: hub_token_program::instruction::SettleOffering {}.data(),
+    }
+}
+
+fn refund_instruction(investor: &Pubkey, mint: &Pubkey, quote_mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::RefundSubscription {
+            investor: *investor,
+            property_state: property_state_address(mint),
+            offering: offering_address(mint),
+            subscription: subscription_address(mint, investor),
+            quote_mint: *quote_mint,
+            offering_escrow: escrow_address(mint),
+            investor_quote_account: token_account_address(investor, quote_mint),
+            program_config: program_config_address(),
+            quote_token_program: spl_token_2022::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::RefundSubscription {}.data(),
+    }
+}
+
+/// Creates a Token-2022 stablecoin with 6 decimals and gives each investor
+/// `amount` of it, returning the mint
+async fn create_stablecoin(
+    context: &mut ProgramTestContext,
+    investors: &[&Keypair],
+    amount: u64,
+) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let mut instructions = vec![
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(Mint::LEN),
+            Mint::LEN as u64,
+            &spl_token_2022::ID,
+        ),
+        token_instruction::initialize_mint2(&spl_token_2022::ID, &mint.pubkey(), &payer, None, 6)
+            .unwrap(),
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &payer,
+            &payer,
+            &mint.pubkey(),
+            &spl_token_2022::ID,
+        ),
+    ];
+    for investor in investors {
+        instructions.extend([
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer,
+                &investor.pubkey(),
+                &mint.pubkey(),
+                &spl_token_2022::ID,
+            ),
+            token_instruction::mint_to(
+                &spl_token_2022::ID,
+                &mint.pubkey(),
+                &token_account_address(&investor.pubkey(), &mint.pubkey()),
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap(),
+        ]);
+    }
+    process(context, &instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+async fn fetch_offering(context: &mut ProgramTestContext, mint: &Pubkey) -> Offering {
+    let account = context
+        .banks_client
+        .get_account(offering_address(mint))
+        .await
+        .unwrap()
+        .unwrap();
+    Offering::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+async fn warp_to(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn test_successful_offering_mints_to_subscribers() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob, &carol] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000 * TOKEN).await.pubkey();
+    let usdc = create_stablecoin(&mut context, &[&alice, &bob, &carol], 1_000 * TOKEN).await;
+    let now = unix_timestamp(&mut context).await;
+    let end_ts = now + 3_600;
+    let create =
+        create_offering_instruction(&payer, &mint, &usdc, offering_params(now - 1, end_ts));
+    process(&mut context, &[create], &[]).await.unwrap();
+
+    let too_small = subscribe_instruction(&carol.pubkey(), &mint, &usdc, 5 * TOKEN);
+    assert_rwa_error(
+        process(&mut context, &[too_small], &[&carol]).await,
+        RwaError::InvalidTicketSize,
+    );
+
+    // Bob subscribes twice, up to the ticket limit
+    for (investor, amount) in [
+        (&alice, 150 * TOKEN),
+        (&bob, 100 * TOKEN),
+        (&bob, 50 * TOKEN),
+        (&carol, 100 * TOKEN),
+    ] {
+        let subscribe = subscribe_instruction(&investor.pubkey(), &mint, &usdc, amount);
+        process(&mut context, &[subscribe], &[investor])
+            .await
+            .unwrap();
+    }
+    let over_ticket = subscribe_instruction(&bob.pubkey(), &mint, &usdc, 60 * TOKEN);
+    assert_rwa_error(
+        process(&mut context, &[over_ticket], &[&bob]).await,
+        RwaError::InvalidTicketSize,
+    );
+    assert_eq!(
+        token_balance(&mut context, &escrow_address(&mint)).await,
+        400 * TOKEN
+    );
+
+    let early = settle_offering_instruction(&payer, &mint, &usdc, &[]);
+    assert_rwa_error(
+        process(&mut context, &[early], &[]).await,
+        RwaError::OfferingStillOpen,
+    );
+
+    // Carol's credential is revoked before settlement, so she is refunded instead
+    warp_to(&mut context, end_ts).await;
+    set_credential_status(&mut context, &carol.pubkey(), 2).await;
+    for investor in [&alice, &bob, &carol] {
+        create_token_account(&mut context, &investor.pubkey(), &mint).await;
+    }
+    let subscribers = [alice.pubkey(), bob.pubkey(), carol.pubkey()];
+
+    // Accounts that are not the subscriber's own fail the whole settlement
+    // rather than rejecting the subscriber
+    for (account, replacement, error) in [
+        (
+            credential_address(&alice.pubkey()),
+            credential_address(&bob.pubkey()),
+            RwaError::InvalidSettlementAccounts,
+        ),
+        (
+            investor_profile_address(&alice.pubkey()),
+            investor_profile_address(&bob.pubkey()),
+            RwaError::InvalidInvestorProfile,
+        ),
+    ] {
+        let mut settle = settle_offering_instruction(&payer, &mint, &usdc, &subscribers);
+        settle
+            .accounts
+            .iter_mut()
+            .find(|meta| meta.pubkey == account)
+            .unwrap()
+            .pubkey = replacement;
+        assert_rwa_error(process(&mut context, &[settle], &[]).await, error);
+    }
+
+    let settle = settle_offering_instruction(&payer, &mint, &usdc, &subscribers);
+    process(&mut context, &[settle], &[]).await.unwrap();
+
+    for investor in [&alice, &bob] {
+        let token_account = token_account_address(&investor.pubkey(), &mint);
+        assert_eq!(token_balance(&mut context, &token_account).await, 3 * TOKEN);
+    }
+    assert_eq!(
+        token_balance(&mut context, &token_account_address(&payer, &usdc)).await,
+        300 * TOKEN
+    );
+    let offering = fetch_offering(&mut context, &mint).await;
+    assert_eq!(offering.status, OfferingStatus::Succeeded);
+    assert_eq!(offering.tokens_issued, 6 * TOKEN);
+
+    let account = context
+        .banks_client
+        .get_account(property_state_address(&mint))
+        .await
+        .unwrap()
+        .unwrap();
+    let property_state = PropertyState::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(property_state.circulating_supply, 6 * TOKEN);
+
+    let alice_refund = refund_instruction(&alice.pubkey(), &mint, &usdc);
+    assert_rwa_error(
+        process(&mut context, &[alice_refund], &[&alice]).await,
+        RwaError::SubscriptionNotRefundable,
+    );
+    let carol_refund = refund_instruction(&carol.pubkey(), &mint, &usdc);
+    process(&mut context, &[carol_refund], &[&carol])
+        .await
+        .unwrap();
+    assert_eq!(
+        token_balance(&mut context, &token_account_address(&carol.pubkey(), &usdc)).await,
+        1_000 * TOKEN
+    );
+
+    // A subscription is issued only once
+    let again = settle_offering_instruction(&payer, &mint, &usdc, &[alice.pubkey()]);
+    assert_rwa_error(
+        process(&mut context, &[again], &[]).await,
+        RwaError::InvalidSettlementAccounts,
+    );
+}
+
+#[tokio::test]
+async fn test_offering_below_soft_cap_refunds_subscribers() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000 * TOKEN).await.pubkey();
+    let usdc = create_stablecoin(&mut context, &[&alice, &bob], 1_000 * TOKEN).await;
+    let now = unix_timestamp(&mut context).await;
+    let end_ts = now + 3_600;
+    let create =
+        create_offering_instruction(&payer, &mint, &usdc, offering_params(now - 1, end_ts));
+    process(&mut context, &[create], &[]).await.unwrap();
+
+    for (investor, amount) in [(&alice, 60 * TOKEN), (&bob, 30 * TOKEN)] {
+        let subscribe = subscribe_instruction(&investor.pubkey(), &mint, &usdc, amount);
+        process(&mut context, &[subscribe], &[investor])
+            .await
+            .unwrap();
+    }
+
+    // Only 90 of the 100 USDC soft cap was raised when the window ends
+    warp_to(&mut context, end_ts).await;
+    let late = subscribe_instruction(&alice.pubkey(), &mint, &usdc, 20 * TOKEN);
+    assert_rwa_error(
+        process(&mut context, &[late], &[&alice]).await,
+        RwaError::OfferingNotOpen,
+    );
+
+    // Refunds do not wait for the authority to settle
+    let refund = refund_instruction(&alice.pubkey(), &mint, &usdc);
+    process(&mut context, &[refund], &[&alice]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &token_account_address(&alice.pubkey(), &usdc)).await,
+        1_000 * TOKEN
+    );
+    assert_eq!(
+        fetch_offering(&mut context, &mint).await.status,
+        OfferingStatus::Failed
+    );
+
+    // Nothing can be issued from a failed offering
+    create_token_account(&mut context, &bob.pubkey(), &mint).await;
+    let settle = settle_offering_instruction(&payer, &mint, &usdc, &[bob.pubkey()]);
+    assert_rwa_error(
+        process(&mut context, &[settle], &[]).await,
+        RwaError::InvalidSettlementAccounts,
+    );
+
+    let refund = refund_instruction(&bob.pubkey(), &mint, &usdc);
+    process(&mut context, &[refund], &[&bob]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &escrow_address(&mint)).await, 0);
+}
+
+fn toggle_property_status_instruction(authority: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::TogglePropertyStatus {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            mint: *mint,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::TogglePropertyStatus {}.data(),
+    }
+}
+
+#[tokio::test]
+async fn test_settlement_rejects_subscriptions_it_cannot_issue() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob, &carol] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000 * TOKEN).await.pubkey();
+    let usdc = create_stablecoin(&mut context, &[&alice, &bob, &carol], 1_000 * TOKEN).await;
+    mint_tokens(&mut context, &carol.pubkey(), &mint, TOKEN).await;
+    create_token_account(&mut context, &alice.pubkey(), &mint).await;
+    let now = unix_timestamp(&mut context).await;
+    let end_ts = now + 3_600;
+    let create =
+        create_offering_instruction(&payer, &mint, &usdc, offering_params(now - 1, end_ts));
+    process(&mut context, &[create], &[]).await.unwrap();
+    for investor in [&alice, &bob, &carol] {
+        let subscribe = subscribe_instruction(&investor.pubkey(), &mint, &usdc, 100 * TOKEN);
+        process(&mut context, &[subscribe], &[investor])
+            .await
+            .unwrap();
+    }
+
+    // Each subscription buys 2 tokens, but no holder may hold more than 2
+    warp_to(&mut context, end_ts).await;
+    let limits = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SetHoldingLimits {
+            authority: payer,
+            property_state: property_state_address(&mint),
+            mint,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SetHoldingLimits {
+            max_tokens_per_holder: 2 * TOKEN,
+            max_holders: 0,
+        }
+        .data(),
+    };
+    process(&mut context, &[limits], &[]).await.unwrap();
+
+    // An inactive property cannot be settled
+    let subscribers = [alice.pubkey(), bob.pubkey(), carol.pubkey()];
+    let toggle = toggle_property_status_instruction(&payer, &mint);
+    process(&mut context, &[toggle], &[]).await.unwrap();
+    let settle = settle_offering_instruction(&payer, &mint, &usdc, &subscribers);
+    assert_rwa_error(
+        process(&mut context, &[settle.clone()], &[]).await,
+        RwaError::PropertyNotActive,
+    );
+    context.get_new_latest_blockhash().await.unwrap();
+    let toggle = toggle_property_status_instruction(&payer, &mint);
+    process(&mut context, &[toggle], &[]).await.unwrap();
+
+    // Bob has no token account and Carol would exceed the limit: both are
+    // rejected while Alice is still issued
+    process(&mut context, &[settle], &[]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &token_account_address(&alice.pubkey(), &mint)).await,
+        2 * TOKEN
+    );
+    assert_eq!(
+        token_balance(&mut context, &token_account_address(&payer, &usdc)).await,
+        100 * TOKEN
+    );
+    for investor in [&bob, &carol] {
+        let refund = refund_instruction(&investor.pubkey(), &mint, &usdc);
+        process(&mut context, &[refund], &[investor]).await.unwrap();
+        assert_eq!(
+            token_balance(
+                &mut context,
+                &token_account_address(&investor.pubkey(), &usdc)
+            )
+            .await,
+            1_000 * TOKEN
+        );
+    }
+    assert_eq!(token_balance(&mut context, &escrow_address(&mint)).await, 0);
+}
+
+#[tokio::test]
+async fn test_unsettled_subscriptions_refundable_after_settlement_deadline() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000 * TOKEN).await.pubkey();
+    let usdc = create_stablecoin(&mut context, &[&alice, &bob], 1_000 * TOKEN).await;
+    let now = unix_timestamp(&mut context).await;
+    let end_ts = now + 3_600;
+    let create =
+        create_offering_instruction(&payer, &mint, &usdc, offering_params(now - 1, end_ts));
+    process(&mut context, &[create], &[]).await.unwrap();
+    for investor in [&alice, &bob] {
+        let subscribe = subscribe_instruction(&investor.pubkey(), &mint, &usdc, 100 * TOKEN);
+        process(&mut context, &[subscribe], &[investor])
+            .await
+            .unwrap();
+        create_token_account(&mut context, &investor.pubkey(), &mint).await;
+    }
+
+    // Only Alice is settled in time
+    warp_to(&mut context, end_ts).await;
+    let settle = settle_offering_instruction(&payer, &mint, &usdc, &[alice.pubkey()]);
+    process(&mut context, &[settle], &[]).await.unwrap();
+    let refund = refund_instruction(&bob.pubkey(), &mint, &usdc);
+    assert_rwa_error(
+        process(&mut context, &[refund.clone()], &[&bob]).await,
+        RwaError::SubscriptionNotRefundable,
+    );
+
+    warp_to(
+        &mut context,
+        end_ts + OFFERING_SETTLEMENT_PERIOD_SECONDS + 1,
+    )
+    .await;
+    let settle = settle_offering_instruction(&payer, &mint, &usdc, &[bob.pubkey()]);
+    assert_rwa_error(
+        process(&mut context, &[settle], &[]).await,
+        RwaError::SettlementDeadlinePassed,
+    );
+    process(&mut context, &[refund], &[&bob]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, &token_account_address(&bob.pubkey(), &usdc)).await,
+        1_000 * TOKEN
+    );
+
+    // Issued subscriptions stay issued
+    let refund = refund_instruction(&alice.pubkey(), &mint, &usdc);
+    assert_rwa_error(
+        process(&mut context, &[refund], &[&alice]).await,
+        RwaError::SubscriptionNotRefundable,
+    );
+}
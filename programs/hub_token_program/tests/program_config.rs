@@ -0,0 +1,236 @@
+//! Program config admin controls and Hub Credential program rotation
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use common::*;
+use hub_token_program::{derive_hub_credential_pda, RwaError, EXTRA_ACCOUNT_METAS_SEED};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+
+fn update_credential_program_data(credential_program_id: Pubkey) -> Vec<u8> {
+    hub_token_program::instruction::UpdateConfig {
+        new_admin: None,
+        credential_program_id: Some(credential_program_id),
+        fee_recipient: None,
+    }
+    .data()
+}
+
+#[tokio::test]
+async fn test_update_config_requires_admin() {
+    let mut context = start(program_test()).await;
+    let intruder = Keypair::new();
+
+    let instruction = update_config_instruction(
+        &intruder.pubkey(),
+        update_credential_program_data(Pubkey::new_unique()),
+    );
+    assert_rwa_error(
+        process(&mut context, &[instruction], &[&intruder]).await,
+        RwaError::Unauthorized,
+    );
+}
+
+#[tokio::test]
+async fn test_initialization_requires_upgrade_authority_and_admin() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let intruder = Keypair::new();
+    set_program_data(&mut context, &payer);
+    let fund = system_instruction::transfer(&payer, &intruder.pubkey(), 1_000_000_000);
+    process(&mut context, &[fund], &[]).await.unwrap();
+
+    // Only the upgrade authority can create the config
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[initialize_config_instruction(&intruder.pubkey())],
+            &[&intruder],
+        )
+        .await,
+        RwaError::Unauthorized,
+    );
+    process(&mut context, &[initialize_config_instruction(&payer)], &[])
+        .await
+        .unwrap();
+
+    // Only the protocol admin can create the issuer registry
+
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[initialize_issuer_registry_instruction(&intruder.pubkey())],
+            &[&intruder],
+        )
+        .await,
+        RwaError::Unauthorized,
+    );
+    process(
+        &mut context,
+        &[initialize_issuer_registry_instruction(&payer)],
+        &[],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_registry_admin_rotation() {
+    let mut context = start(program_test()).await;
+    let payer = context.payer.pubkey();
+    let new_admin = Keypair::new();
+    let add_issuer =
+        |issuer: Pubkey| hub_token_program::instruction::AddTrustedIssuer { issuer }.data();
+
+    let rotate = update_issuer_registry_instruction(
+        &new_admin.pubkey(),
+        hub_token_program::instruction::SetRegistryAdmin {
+            new_admin: new_admin.pubkey(),
+        }
+        .data(),
+    );
+    assert_rwa_error(
+        process(&mut context, &[rotate], &[&new_admin]).await,
+        RwaError::Unauthorized,
+    );
+    let rotate = update_issuer_registry_instruction(
+        &payer,
+        hub_token_program::instruction::SetRegistryAdmin {
+            new_admin: new_admin.pubkey(),
+        }
+        .data(),
+    );
+    process(&mut context, &[rotate], &[]).await.unwrap();
+
+    // Only the new admin manages issuers from now on
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[update_issuer_registry_instruction(
+                &payer,
+                add_issuer(Pubkey::new_unique()),
+            )],
+            &[],
+        )
+        .await,
+        RwaError::Unauthorized,
+    );
+    process(
+        &mut context,
+        &[update_issuer_registry_instruction(
+            &new_admin.pubkey(),
+            add_issuer(Pubkey::new_unique()),
+        )],
+        &[&new_admin],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_credential_program_rotation() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &sender.pubkey());
+    add_credential(&mut program_test, &receiver.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let source = mint_tokens(&mut context, &sender.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &receiver.pubkey(), &mint).await;
+
+    let new_program = Pubkey::new_from_array([11; 32]);
+    let rotate = update_config_instruction(&payer, update_credential_program_data(new_program));
+    process(&mut context, &[rotate.clone()], &[]).await.unwrap();
+
+    // Until properties rewrite their meta lists, the previous program is still accepted
+    let transfer = transfer_checked_instruction(
+        &context,
+        &source,
+        &mint,
+        &destination,
+        &sender.pubkey(),
+        100,
+    )
+    .await;
+    process(&mut context, &[transfer], &[&sender])
+        .await
+        .unwrap();
+
+    // Confirming the new program closes the transition
+    context.get_new_latest_blockhash().await.unwrap();
+    process(&mut context, &[rotate], &[]).await.unwrap();
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[mint_tokens_instruction(
+                &payer,
+                &receiver.pubkey(),
+                &mint,
+                1,
+            )],
+            &[],
+        )
+        .await,
+        RwaError::KycVerificationRequired,
+    );
+    let transfer = transfer_checked_instruction(
+        &context,
+        &source,
+        &mint,
+        &destination,
+        &sender.pubkey(),
+        101,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[transfer], &[&sender]).await,
+        RwaError::InvalidCredentialProgram,
+    );
+
+    // Reissue credentials under the new program and refresh the meta list
+    for wallet in [sender.pubkey(), receiver.pubkey()] {
+        let account = Account {
+            lamports: 1_000_000_000,
+            data: credential_data(&wallet, &TEST_ISSUER, 0, 0, i64::MAX),
+            owner: new_program,
+            executable: false,
+            rent_epoch: 0,
+        };
+        context.set_account(
+            &derive_hub_credential_pda(&wallet, &new_program).0,
+            &account.into(),
+        );
+    }
+    let (extra_account_meta_list, _) = Pubkey::find_program_address(
+        &[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()],
+        &hub_token_program::ID,
+    );
+    let refresh = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::UpdateExtraAccountMetaList {
+            authority: payer,
+            property_state: property_state_address(&mint),
+            extra_account_meta_list,
+            mint,
+            program_config: program_config_address(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::UpdateExtraAccountMetas {}.data(),
+    };
+    process(&mut context, &[refresh], &[]).await.unwrap();
+
+    let transfer =
+        transfer_checked_instruction(&context, &source, &mint, &destination, &sender.pubkey(), 50)
+            .await;
+    process(&mut context, &[transfer], &[&sender])
+        .await
+        .unwrap();
+    assert_eq!(token_balance(&mut context, &destination).await, 150);
+}
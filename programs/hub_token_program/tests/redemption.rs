@@ -0,0 +1,371 @@
+//! Redemption pools paying SOL or stablecoin for burned property tokens
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use anchor_spl::{
+    associated_token::spl_associated_token_account,
+    token_2022::spl_token_2022::{self, instruction as token_instruction, state::Mint},
+};
+use common::*;
+use hub_token_program::{RwaError, REDEMPTION_POOL_SEED};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+
+const TOKEN: u64 = 1_000_000;
+
+fn redemption_pool_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[REDEMPTION_POOL_SEED, property_state_address(mint).as_ref()],
+        &hub_token_program::ID,
+    )
+    .0
+}
+
+/// Stablecoin payment accounts: (payment mint, pool account, counterparty account)
+type Payment = Option<(Pubkey, Pubkey, Pubkey)>;
+
+fn create_pool_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    payment: Payment,
+    price_per_token: u64,
+    start_ts: i64,
+    end_ts: i64,
+    max_tokens: u64,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::CreateRedemptionPool {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            redemption_pool: redemption_pool_address(mint),
+            payment_mint: payment.map(|p| p.0),
+            pool_payment_account: payment.map(|p| p.1),
+            authority_payment_account: payment.map(|p| p.2),
+            payment_token_program: payment.map(|_| spl_token_2022::ID),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::CreateRedemptionPool {
+            price_per_token,
+            start_ts,
+            end_ts,
+            max_tokens,
+        }
+        .data(),
+    }
+}
+
+fn redeem_instruction(
+    holder: &Pubkey,
+    mint: &Pubkey,
+    payment: Payment,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::RedeemTokens {
+            holder: *holder,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            holder_token_account: token_account_address(holder, mint),
+            redemption_pool: redemption_pool_address(mint),
+            program_config: program_config_address(),
+            holder_rewards: holder_rewards_address(holder, mint),
+            holder_lockup: holder_lockup_address(holder, mint),
+            payment_mint: payment.map(|p| p.0),
+            pool_payment_account: payment.map(|p| p.1),
+            holder_payment_account: payment.map(|p| p.2),
+            payment_token_program: payment.map(|_| spl_token_2022::ID),
+            token_program: spl_token_2022::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::RedeemTokens { amount }.data(),
+    }
+}
+
+fn close_pool_instruction(authority: &Pubkey, mint: &Pubkey, payment: Payment) -> Instruction {
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::CloseRedemptionPool {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            mint: *mint,
+            redemption_pool: redemption_pool_address(mint),
+            payment_mint: payment.map(|p| p.0),
+            pool_payment_account: payment.map(|p| p.1),
+            authority_payment_account: payment.map(|p| p.2),
+            payment_token_program: payment.map(|_| spl_token_2022::ID),
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::CloseRedemptionPool {}.data(),
+    }
+}
+
+/// Creates a plain Token-2022 stablecoin with 6 decimals, minting `supply` to the payer
+async fn create_stablecoin(context: &mut ProgramTestContext, supply: u64) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(Mint::LEN),
+            Mint::LEN as u64,
+            &spl_token_2022::ID,
+        ),
+        token_instruction::initialize_mint2(&spl_token_2022::ID, &mint.pubkey(), &payer, None, 6)
+            .unwrap(),
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &payer,
+            &payer,
+            &mint.pubkey(),
+            &spl_token_2022::ID,
+        ),
+        token_instruction::mint_to(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            &token_account_address(&payer, &mint.pubkey()),
+            &payer,
+            &[],
+            supply,
+        )
+        .unwrap(),
+    ];
+    process(context, &instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+async fn lamports(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    context.banks_client.get_balance(*address).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_redeem_for_sol() {
+    let holder = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 10 * TOKEN).await.pubkey();
+    let token_account = mint_tokens(&mut context, &holder.pubkey(), &mint, 5 * TOKEN).await;
+
+    // 0.1 SOL per token, up to 3 tokens
+    let now = unix_timestamp(&mut context).await;
+    let create = create_pool_instruction(
+        &payer,
+        &mint,
+        None,
+        100_000_000,
+        now - 1,
+        now + 3_600,
+        3 * TOKEN,
+    );
+    process(&mut context, &[create], &[]).await.unwrap();
+
+    let before = lamports(&mut context, &holder.pubkey()).await;
+    let redeem = redeem_instruction(&holder.pubkey(), &mint, None, 2 * TOKEN);
+    process(&mut context, &[redeem], &[&holder]).await.unwrap();
+    assert_eq!(
+        lamports(&mut context, &holder.pubkey()).await - before,
+        200_000_000
+    );
+    assert_eq!(token_balance(&mut context, &token_account).await, 3 * TOKEN);
+
+    // The cap is enforced and the pool stays open until it is used up
+    // (the amount differs from the first redemption so the transaction is not deduplicated)
+    let over_cap = redeem_instruction(&holder.pubkey(), &mint, None, TOKEN + 1);
+    assert_rwa_error(
+        process(&mut context, &[over_cap], &[&holder]).await,
+        RwaError::RedemptionCapExceeded,
+    );
+    assert_rwa_error(
+        process(
+            &mut context,
+            &[close_pool_instruction(&payer, &mint, None)],
+            &[],
+        )
+        .await,
+        RwaError::RedemptionPoolStillOpen,
+    );
+
+    let rest = redeem_instruction(&holder.pubkey(), &mint, None, TOKEN);
+    process(&mut context, &[rest], &[&holder]).await.unwrap();
+    // Retry the close under a new blockhash so it is not deduplicated
+    context.get_new_latest_blockhash().await.unwrap();
+    process(
+        &mut context,
+        &[close_pool_instruction(&payer, &mint, None)],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert!(context
+        .banks_client
+        .get_account(redemption_pool_address(&mint))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_stablecoin_pool_credits_amount_after_transfer_fee() {
+    let holder = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 10 * TOKEN).await.pubkey();
+    mint_tokens(&mut context, &holder.pubkey(), &mint, 5 * TOKEN).await;
+
+    let brz = create_fee_stablecoin(&mut context, 1_000 * TOKEN).await;
+    let pool = redemption_pool_address(&mint);
+    let pool_brz = create_token_account(&mut context, &pool, &brz).await;
+    let holder_brz = create_token_account(&mut context, &holder.pubkey(), &brz).await;
+    let payer_brz = token_account_address(&payer, &brz);
+    let payment = Some((brz, pool_brz, holder_brz));
+
+    // 100 BRZ per token for 4 tokens: 1% is withheld, so only 396 BRZ arrive
+    let now = unix_timestamp(&mut context).await;
+    let create = create_pool_instruction(
+        &payer,
+        &mint,
+        Some((brz, pool_brz, payer_brz)),
+        100 * TOKEN,
+        now - 1,
+        now + 3_600,
+        4 * TOKEN,
+    );
+    process(&mut context, &[create], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &pool_brz).await, 396 * TOKEN);
+
+    // The pool only takes what its balance can pay for, and can pay all of it
+    let over_funding = redeem_instruction(&holder.pubkey(), &mint, payment, 396 * TOKEN / 100 + 1);
+    assert_rwa_error(
+        process(&mut context, &[over_funding], &[&holder]).await,
+        RwaError::RedemptionCapExceeded,
+    );
+    let redeem = redeem_instruction(&holder.pubkey(), &mint, payment, 396 * TOKEN / 100);
+    process(&mut context, &[redeem], &[&holder]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &pool_brz).await, 0);
+}
+
+#[tokio::test]
+async fn test_redeem_respects_lockups() {
+    let holder = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 10 * TOKEN).await.pubkey();
+    let token_account = mint_tokens(&mut context, &holder.pubkey(), &mint, TOKEN).await;
+    let now = unix_timestamp(&mut context).await;
+    let locked = mint_locked_instruction(
+        &payer,
+        &holder.pubkey(),
+        &mint,
+        2 * TOKEN,
+        now + 100,
+        now + 1_100,
+    );
+    process(&mut context, &[locked], &[]).await.unwrap();
+
+    let create = create_pool_instruction(
+        &payer,
+        &mint,
+        None,
+        100_000_000,
+        now - 1,
+        now + 3_600,
+        3 * TOKEN,
+    );
+    process(&mut context, &[create], &[]).await.unwrap();
+
+    // Only the unlocked token can be redeemed before the cliff
+    let locked_redeem = redeem_instruction(&holder.pubkey(), &mint, None, TOKEN + 1);
+    assert_rwa_error(
+        process(&mut context, &[locked_redeem], &[&holder]).await,
+        RwaError::TokensLocked,
+    );
+    let redeem = redeem_instruction(&holder.pubkey(), &mint, None, TOKEN);
+    process(&mut context, &[redeem], &[&holder]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &token_account).await, 2 * TOKEN);
+}
+
+#[tokio::test]
+async fn test_redeem_for_stablecoin_and_refund() {
+    let holder = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &holder.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 10 * TOKEN).await.pubkey();
+    mint_tokens(&mut context, &holder.pubkey(), &mint, 5 * TOKEN).await;
+
+    let usdc = create_stablecoin(&mut context, 1_000 * TOKEN).await;
+    let pool = redemption_pool_address(&mint);
+    let pool_usdc = create_token_account(&mut context, &pool, &usdc).await;
+    let holder_usdc = create_token_account(&mut context, &holder.pubkey(), &usdc).await;
+    let payer_usdc = token_account_address(&payer, &usdc);
+
+    // 50 USDC per token, up to 4 tokens
+    let now = unix_timestamp(&mut context).await;
+    let end_ts = now + 3_600;
+    let create = create_pool_instruction(
+        &payer,
+        &mint,
+        Some((usdc, pool_usdc, payer_usdc)),
+        50 * TOKEN,
+        now - 1,
+        end_ts,
+        4 * TOKEN,
+    );
+    process(&mut context, &[create], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &pool_usdc).await, 200 * TOKEN);
+
+    // SOL payout accounts are rejected for a stablecoin pool
+    let without_payment = redeem_instruction(&holder.pubkey(), &mint, None, TOKEN);
+    assert_rwa_error(
+        process(&mut context, &[without_payment], &[&holder]).await,
+        RwaError::InvalidPaymentAccounts,
+    );
+
+    let redeem = redeem_instruction(
+        &holder.pubkey(),
+        &mint,
+        Some((usdc, pool_usdc, holder_usdc)),
+        TOKEN + TOKEN / 2,
+    );
+    process(&mut context, &[redeem], &[&holder]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &holder_usdc).await, 75 * TOKEN);
+
+    // After the window ends, redemptions stop and the authority gets the rest back
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = end_ts;
+    context.set_sysvar(&clock);
+
+    let late = redeem_instruction(
+        &holder.pubkey(),
+        &mint,
+        Some((usdc, pool_usdc, holder_usdc)),
+        TOKEN,
+    );
+    assert_rwa_error(
+        process(&mut context, &[late], &[&holder]).await,
+        RwaError::RedemptionWindowClosed,
+    );
+
+    let close = close_pool_instruction(&payer, &mint, Some((usdc, pool_usdc, payer_usdc)));
+    process(&mut context, &[close], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &payer_usdc).await, 925 * TOKEN);
+}
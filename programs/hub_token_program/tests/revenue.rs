@@ -0,0 +1,661 @@
+//! Revenue distribution against entitlement snapshots
+mod common;
+
+use anchor_lang::{
+    prelude::{AccountMeta, Clock, Pubkey},
+    solana_program::instruction::Instruction,
+    AccountDeserialize, InstructionData, Space, ToAccountMetas,
+};
+use anchor_spl::token_2022::spl_token_2022;
+use common::*;
+use hub_token_program::RwaError;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Replaces the claim deadline of a deposit_revenue instruction (its last argument)
+fn set_claim_deadline(deposit: &mut Instruction, claim_deadline: i64) {
+    let len = deposit.data.len();
+    deposit.data[len - 8..].copy_from_slice(&claim_deadline.to_le_bytes());
+}
+
+fn sweep_instruction(
+    authority: &Pubkey,
+    mint: &Pubkey,
+    epoch_number: u64,
+    target_epoch_number: Option<u64>,
+    revenue_mint: Option<&Pubkey>,
+) -> Instruction {
+    let revenue_epoch = revenue_epoch_address(mint, epoch_number);
+    let target_epoch = target_epoch_number.map(|number| revenue_epoch_address(mint, number));
+    let vault_address = match revenue_mint {
+        Some(_) => revenue_token_vault_address,
+        None => revenue_vault_address,
+    };
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SweepUnclaimedRevenue {
+            authority: *authority,
+            property_state: property_state_address(mint),
+            revenue_epoch,
+            revenue_vault: revenue_vault_address(&revenue_epoch),
+            target_epoch,
+            target_vault: target_epoch.map(|epoch| vault_address(&epoch)),
+            revenue_mint: revenue_mint.copied(),
+            revenue_token_vault: revenue_mint.map(|_| revenue_token_vault_address(&revenue_epoch)),
+            authority_revenue_account: revenue_mint.map(|m| token_account_address(authority, m)),
+            revenue_token_program: revenue_mint.map(|_| spl_token_2022::ID),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SweepUnclaimedRevenue {}.data(),
+    }
+}
+
+/// Builds claim_revenue_batch over (epoch number, snapshot balance, proof) entries
+fn claim_batch_instruction(
+    investor: &Pubkey,
+    mint: &Pubkey,
+    epochs: &[(u64, u64, Vec<[u8; 32]>)],
+    revenue_mint: Option<&Pubkey>,
+) -> Instruction {
+    let mut accounts = hub_token_program::accounts::ClaimRevenueBatch {
+        investor: *investor,
+        property_state: property_state_address(mint),
+        mint: *mint,
+        program_config: program_config_address(),
+        revenue_mint: revenue_mint.copied(),
+        investor_revenue_account: revenue_mint.map(|m| token_account_address(investor, m)),
+        revenue_token_program: revenue_mint.map(|_| spl_token_2022::ID),
+        system_program: anchor_lang::system_program::ID,
+    }
+    .to_account_metas(None);
+    let mut entitlements = Vec::new();
+    for (epoch_number, snapshot_balance, proof) in epochs {
+        let revenue_epoch = revenue_epoch_address(mint, *epoch_number);
+        let vault = match revenue_mint {
+            Some(_) => revenue_token_vault_address(&revenue_epoch),
+            None => revenue_vault_address(&revenue_epoch),
+        };
+        accounts.extend([
+            AccountMeta::new(revenue_epoch, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(claim_record_address(&revenue_epoch, investor), false),
+        ]);
+        entitlements.push(hub_token_program::EpochEntitlement {
+            snapshot_balance: *snapshot_balance,
+            proof: proof.clone(),
+        });
+    }
+    Instruction {
+        program_id: hub_token_program::ID,
+        accounts,
+        data: hub_token_program::instruction::ClaimRevenueBatch { entitlements }.data(),
+    }
+}
+
+async fn warp_to(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn test_claims_require_snapshot_entitlement() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob, &carol] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let alice_account = mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    mint_tokens(&mut context, &bob.pubkey(), &mint, 400).await;
+    let carol_account = create_token_account(&mut context, &carol.pubkey(), &mint).await;
+
+    let tree = entitlement_tree(&[(alice.pubkey(), 600), (bob.pubkey(), 400)]);
+    let deposit =
+        deposit_revenue_instruction(&payer, &mint, 0, 1_000_000_000, tree.root, None, true);
+    process(&mut context, &[deposit], &[]).await.unwrap();
+
+    // Overstating the snapshot balance fails
+    let inflated = claim_revenue_instruction(
+        &alice.pubkey(),
+        &mint,
+        0,
+        1_000,
+        tree.proofs[0].clone(),
+        None,
+    );
+    assert_rwa_error(
+        process(&mut context, &[inflated], &[&alice]).await,
+        RwaError::InvalidEntitlementProof,
+    );
+
+    // Tokens moved after the deposit earn the new holder nothing for this epoch
+    let transfer = transfer_checked_instruction(
+        &context,
+        &alice_account,
+        &mint,
+        &carol_account,
+        &alice.pubkey(),
+        600,
+    )
+    .await;
+    process(&mut context, &[transfer], &[&alice]).await.unwrap();
+
+    let moved =
+        claim_revenue_instruction(&carol.pubkey(), &mint, 0, 600, tree.proofs[0].clone(), None);
+    assert_rwa_error(
+        process(&mut context, &[moved], &[&carol]).await,
+        RwaError::InvalidEntitlementProof,
+    );
+}
+
+#[tokio::test]
+async fn test_stablecoin_revenue_credits_amount_after_transfer_fee() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    mint_tokens(&mut context, &bob.pubkey(), &mint, 400).await;
+
+    let brz = create_fee_stablecoin(&mut context, 10_000_000).await;
+    let alice_brz = create_token_account(&mut context, &alice.pubkey(), &brz).await;
+    let bob_brz = create_token_account(&mut context, &bob.pubkey(), &brz).await;
+
+    // 1% of the deposit is withheld by the mint, so the epoch holds 990_000
+    let tree = entitlement_tree(&[(alice.pubkey(), 600), (bob.pubkey(), 400)]);
+    let deposit =
+        deposit_revenue_instruction(&payer, &mint, 0, 1_000_000, tree.root, Some(&brz), true);
+    process(&mut context, &[deposit], &[]).await.unwrap();
+    let vault = revenue_token_vault_address(&revenue_epoch_address(&mint, 0));
+    assert_eq!(token_balance(&mut context, &vault).await, 990_000);
+
+    // SOL payout accounts are rejected for a token epoch
+    let as_sol =
+        claim_revenue_instruction(&alice.pubkey(), &mint, 0, 600, tree.proofs[0].clone(), None);
+    assert_rwa_error(
+        process(&mut context, &[as_sol], &[&alice]).await,
+        RwaError::InvalidPaymentAccounts,
+    );
+
+    // Shares are of the credited amount, and the claim transfer pays the fee again
+    for (investor, balance, proof, account, received) in [
+        (&alice, 600, &tree.proofs[0], alice_brz, 588_060),
+        (&bob, 400, &tree.proofs[1], bob_brz, 392_040),
+    ] {
+        let claim = claim_revenue_instruction(
+            &investor.pubkey(),
+            &mint,
+            0,
+            balance,
+            proof.clone(),
+            Some(&brz),
+        );
+        process(&mut context, &[claim], &[investor]).await.unwrap();
+        assert_eq!(token_balance(&mut context, &account).await, received);
+    }
+    assert_eq!(token_balance(&mut context, &vault).await, 0);
+}
+
+#[tokio::test]
+async fn test_sol_revenue_deposit_and_claims_end_to_end() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let carol = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob, &carol] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 500).await;
+    mint_tokens(&mut context, &bob.pubkey(), &mint, 300).await;
+    mint_tokens(&mut context, &carol.pubkey(), &mint, 200).await;
+
+    let holders = [(&alice, 500), (&bob, 300), (&carol, 200)];
+    let tree = entitlement_tree(
+        &holders
+            .iter()
+            .map(|(wallet, balance)| (wallet.pubkey(), *balance))
+            .collect::<Vec<_>>(),
+    );
+    let deposit =
+        deposit_revenue_instruction(&payer, &mint, 0, 3_000_000_000, tree.root, None, true);
+    process(&mut context, &[deposit], &[]).await.unwrap();
+
+    let vault = revenue_vault_address(&revenue_epoch_address(&mint, 0));
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let claim_record_rent = rent.minimum_balance(8 + hub_token_program::ClaimRecord::INIT_SPACE);
+
+    for ((investor, balance), proof) in holders.iter().zip(&tree.proofs) {
+        let before = context
+            .banks_client
+            .get_balance(investor.pubkey())
+            .await
+            .unwrap();
+        let claim =
+            claim_revenue_instruction(&investor.pubkey(), &mint, 0, *balance, proof.clone(), None);
+        process(&mut context, &[claim], &[investor]).await.unwrap();
+        let after = context
+            .banks_client
+            .get_balance(investor.pubkey())
+            .await
+            .unwrap();
+        assert_eq!(after + claim_record_rent - before, balance * 3_000_000);
+    }
+
+    // Only the rent-exempt reserve is left behind
+    assert_eq!(
+        context.banks_client.get_balance(vault).await.unwrap(),
+        rent.minimum_balance(0)
+    );
+}
+
+#[tokio::test]
+async fn test_epochs_are_numbered_by_the_program_and_can_be_topped_up() {
+    let alice = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &alice.pubkey());
+    add_wallet(&mut program_test, &alice.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 1_000).await;
+    let tree = entitlement_tree(&[(alice.pubkey(), 1_000)]);
+
+    // Epoch numbers cannot be chosen by the caller
+    let skipped = deposit_revenue_instruction(&payer, &mint, 5, 1_000_000, tree.root, None, false);
+    assert!(process(&mut context, &[skipped], &[]).await.is_err());
+
+    let open = deposit_revenue_instruction(&payer, &mint, 0, 1_000_000, tree.root, None, false);
+    process(&mut context, &[open], &[]).await.unwrap();
+
+    // An open epoch cannot be claimed, but can be topped up
+    let early = claim_revenue_instruction(&alice.pubkey(), &mint, 0, 1_000, vec![], None);
+    assert_rwa_error(
+        process(&mut context, &[early], &[&alice]).await,
+        RwaError::EpochNotFinalized,
+    );
+    let top_up = top_up_revenue_instruction(&payer, &mint, 0, 500_000);
+    process(&mut context, &[top_up], &[]).await.unwrap();
+
+    let finalize = finalize_revenue_epoch_instruction(&payer, &mint, 0);
+    process(&mut context, &[finalize], &[]).await.unwrap();
+    let late = top_up_revenue_instruction(&payer, &mint, 0, 1);
+    assert_rwa_error(
+        process(&mut context, &[late], &[]).await,
+        RwaError::EpochAlreadyFinalized,
+    );
+
+    let before = context
+        .banks_client
+        .get_balance(alice.pubkey())
+        .await
+        .unwrap();
+    let claim = claim_revenue_instruction(&alice.pubkey(), &mint, 0, 1_000, vec![], None);
+    process(&mut context, &[claim], &[&alice]).await.unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let claim_record_rent = rent.minimum_balance(8 + hub_token_program::ClaimRecord::INIT_SPACE);
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(alice.pubkey())
+            .await
+            .unwrap()
+            + claim_record_rent
+            - before,
+        1_500_000
+    );
+
+    // The next deposit takes the following number
+    let next = deposit_revenue_instruction(&payer, &mint, 1, 1_000_000, tree.root, None, true);
+    process(&mut context, &[next], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sweep_unclaimed_sol_into_next_epoch() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    mint_tokens(&mut context, &bob.pubkey(), &mint, 400).await;
+    let tree = entitlement_tree(&[(alice.pubkey(), 600), (bob.pubkey(), 400)]);
+
+    let deadline = unix_timestamp(&mut context).await + 3_600;
+    let mut deposit =
+        deposit_revenue_instruction(&payer, &mint, 0, 1_000_000_000, tree.root, None, true);
+    set_claim_deadline(&mut deposit, deadline);
+    process(&mut context, &[deposit], &[]).await.unwrap();
+    let claim =
+        claim_revenue_instruction(&alice.pubkey(), &mint, 0, 600, tree.proofs[0].clone(), None);
+    process(&mut context, &[claim], &[&alice]).await.unwrap();
+
+    let early = sweep_instruction(&payer, &mint, 0, None, None);
+    assert_rwa_error(
+        process(&mut context, &[early], &[]).await,
+        RwaError::ClaimDeadlineNotReached,
+    );
+
+    // Bob misses the deadline
+    warp_to(&mut context, deadline + 1).await;
+    let late =
+        claim_revenue_instruction(&bob.pubkey(), &mint, 0, 400, tree.proofs[1].clone(), None);
+    assert_rwa_error(
+        process(&mut context, &[late], &[&bob]).await,
+        RwaError::ClaimDeadlinePassed,
+    );
+
+    // His share rolls into the next, still open epoch
+    let next = deposit_revenue_instruction(&payer, &mint, 1, 1_000_000_000, tree.root, None, false);
+    process(&mut context, &[next], &[]).await.unwrap();
+    let sweep = sweep_instruction(&payer, &mint, 0, Some(1), None);
+    process(&mut context, &[sweep], &[]).await.unwrap();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let next_vault = revenue_vault_address(&revenue_epoch_address(&mint, 1));
+    assert_eq!(
+        context.banks_client.get_balance(next_vault).await.unwrap(),
+        1_400_000_000 + rent.minimum_balance(0)
+    );
+    for closed in [
+        revenue_epoch_address(&mint, 0),
+        revenue_vault_address(&revenue_epoch_address(&mint, 0)),
+    ] {
+        assert!(context
+            .banks_client
+            .get_account(closed)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    let finalize = finalize_revenue_epoch_instruction(&payer, &mint, 1);
+    process(&mut context, &[finalize], &[]).await.unwrap();
+    let before = context
+        .banks_client
+        .get_balance(bob.pubkey())
+        .await
+        .unwrap();
+    let claim =
+        claim_revenue_instruction(&bob.pubkey(), &mint, 1, 400, tree.proofs[1].clone(), None);
+    process(&mut context, &[claim], &[&bob]).await.unwrap();
+    let claim_record_rent = rent.minimum_balance(8 + hub_token_program::ClaimRecord::INIT_SPACE);
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(bob.pubkey())
+            .await
+            .unwrap()
+            + claim_record_rent
+            - before,
+        560_000_000
+    );
+}
+
+#[tokio::test]
+async fn test_sweep_unclaimed_stablecoin_back_to_authority() {
+    let alice = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &alice.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 1_000).await;
+    let brz = create_fee_stablecoin(&mut context, 10_000_000).await;
+    let tree = entitlement_tree(&[(alice.pubkey(), 1_000)]);
+
+    let deadline = unix_timestamp(&mut context).await + 3_600;
+    let mut deposit =
+        deposit_revenue_instruction(&payer, &mint, 0, 1_000_000, tree.root, Some(&brz), true);
+    set_claim_deadline(&mut deposit, deadline);
+    process(&mut context, &[deposit], &[]).await.unwrap();
+
+    // Nobody claims; the 990_000 credited come back less the 1% fee
+    warp_to(&mut context, deadline + 1).await;
+    let sweep = sweep_instruction(&payer, &mint, 0, None, Some(&brz));
+    process(&mut context, &[sweep], &[]).await.unwrap();
+
+    let payer_brz = token_account_address(&payer, &brz);
+    assert_eq!(
+        token_balance(&mut context, &payer_brz).await,
+        9_000_000 + 980_100
+    );
+    let vault = revenue_token_vault_address(&revenue_epoch_address(&mint, 0));
+    assert!(context
+        .banks_client
+        .get_account(vault)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_batch_claim_sol_across_epochs() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    mint_tokens(&mut context, &bob.pubkey(), &mint, 400).await;
+    let tree = entitlement_tree(&[(alice.pubkey(), 600), (bob.pubkey(), 400)]);
+
+    // Three monthly epochs of 1, 2 and 3 SOL
+    for epoch_number in 0..3 {
+        let deposit = deposit_revenue_instruction(
+            &payer,
+            &mint,
+            epoch_number,
+            (epoch_number + 1) * 1_000_000_000,
+            tree.root,
+            None,
+            true,
+        );
+        process(&mut context, &[deposit], &[]).await.unwrap();
+    }
+
+    // Epoch 1 was already claimed on its own
+    let claim =
+        claim_revenue_instruction(&alice.pubkey(), &mint, 1, 600, tree.proofs[0].clone(), None);
+    process(&mut context, &[claim], &[&alice]).await.unwrap();
+    let epochs: Vec<_> = (0..3).map(|n| (n, 600, tree.proofs[0].clone())).collect();
+    let batch = claim_batch_instruction(&alice.pubkey(), &mint, &epochs, None);
+    assert_rwa_error(
+        process(&mut context, &[batch], &[&alice]).await,
+        RwaError::EpochAlreadyClaimed,
+    );
+
+    // Each proof is checked against its own epoch
+    let forged = claim_batch_instruction(
+        &bob.pubkey(),
+        &mint,
+        &[
+            (0, 400, tree.proofs[1].clone()),
+            (2, 600, tree.proofs[1].clone()),
+        ],
+        None,
+    );
+    assert_rwa_error(
+        process(&mut context, &[forged], &[&bob]).await,
+        RwaError::InvalidEntitlementProof,
+    );
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let claim_record_rent = rent.minimum_balance(8 + hub_token_program::ClaimRecord::INIT_SPACE);
+    let before = context
+        .banks_client
+        .get_balance(bob.pubkey())
+        .await
+        .unwrap();
+    let epochs: Vec<_> = (0..3).map(|n| (n, 400, tree.proofs[1].clone())).collect();
+    let batch = claim_batch_instruction(&bob.pubkey(), &mint, &epochs, None);
+    process(&mut context, &[batch], &[&bob]).await.unwrap();
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(bob.pubkey())
+            .await
+            .unwrap()
+            + 3 * claim_record_rent
+            - before,
+        2_400_000_000
+    );
+    for epoch_number in 0..3 {
+        let record =
+            claim_record_address(&revenue_epoch_address(&mint, epoch_number), &bob.pubkey());
+        let account = context
+            .banks_client
+            .get_account(record)
+            .await
+            .unwrap()
+            .unwrap();
+        let record =
+            hub_token_program::ClaimRecord::try_deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(record.amount_claimed, (epoch_number + 1) * 400_000_000);
+    }
+
+    // Repeating an epoch within a batch is a double claim
+    let twice = claim_batch_instruction(
+        &alice.pubkey(),
+        &mint,
+        &[
+            (0, 600, tree.proofs[0].clone()),
+            (0, 600, tree.proofs[0].clone()),
+        ],
+        None,
+    );
+    assert_rwa_error(
+        process(&mut context, &[twice], &[&alice]).await,
+        RwaError::EpochAlreadyClaimed,
+    );
+}
+
+#[tokio::test]
+async fn test_batch_claim_stablecoin_rejects_mixed_currencies() {
+    let alice = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &alice.pubkey());
+    add_wallet(&mut program_test, &alice.pubkey());
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 1_000).await;
+    let brz = create_fee_stablecoin(&mut context, 10_000_000).await;
+    let alice_brz = create_token_account(&mut context, &alice.pubkey(), &brz).await;
+    let tree = entitlement_tree(&[(alice.pubkey(), 1_000)]);
+
+    for (epoch_number, revenue_mint) in [(0, Some(&brz)), (1, Some(&brz)), (2, None)] {
+        let deposit = deposit_revenue_instruction(
+            &payer,
+            &mint,
+            epoch_number,
+            1_000_000,
+            tree.root,
+            revenue_mint,
+            true,
+        );
+        process(&mut context, &[deposit], &[]).await.unwrap();
+    }
+
+    let mixed: Vec<_> = (0..3).map(|n| (n, 1_000, tree.proofs[0].clone())).collect();
+    let batch = claim_batch_instruction(&alice.pubkey(), &mint, &mixed, Some(&brz));
+    assert_rwa_error(
+        process(&mut context, &[batch], &[&alice]).await,
+        RwaError::InvalidBatchClaim,
+    );
+
+    // Both token epochs credited 990_000. Epoch 1's share reaches epoch 0's
+    // vault as 980_100, and the 1_970_100 total is paid once less the 1% fee.
+    let epochs: Vec<_> = (0..2).map(|n| (n, 1_000, tree.proofs[0].clone())).collect();
+    let batch = claim_batch_instruction(&alice.pubkey(), &mint, &epochs, Some(&brz));
+    process(&mut context, &[batch], &[&alice]).await.unwrap();
+    assert_eq!(token_balance(&mut context, &alice_brz).await, 1_950_399);
+}
+
+#[tokio::test]
+async fn test_claims_cannot_exceed_epoch_revenue() {
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let mut program_test = program_test();
+    for wallet in [&alice, &bob] {
+        add_credential(&mut program_test, &wallet.pubkey());
+        add_wallet(&mut program_test, &wallet.pubkey());
+    }
+    let mut context = start(program_test).await;
+    let payer = context.payer.pubkey();
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    mint_tokens(&mut context, &alice.pubkey(), &mint, 600).await;
+    mint_tokens(&mut context, &bob.pubkey(), &mint, 400).await;
+
+    // A snapshot whose balances sum past the eligible supply over-allocates
+    let tree = entitlement_tree(&[(alice.pubkey(), 1_000), (bob.pubkey(), 400)]);
+    let deposit =
+        deposit_revenue_instruction(&payer, &mint, 0, 1_000_000_000, tree.root, None, true);
+    process(&mut context, &[deposit], &[]).await.unwrap();
+
+    let claim = claim_revenue_instruction(
+        &alice.pubkey(),
+        &mint,
+        0,
+        1_000,
+        tree.proofs[0].clone(),
+        None,
+    );
+    process(&mut context, &[claim], &[&alice]).await.unwrap();
+
+    let claim = claim_batch_instruction(
+        &bob.pubkey(),
+        &mint,
+        &[(0, 400, tree.proofs[1].clone())],
+        None,
+    );
+    assert_rwa_error(
+        process(&mut context, &[claim], &[&bob]).await,
+        RwaError::ClaimExceedsEpochRevenue,
+    );
+
+    let epoch = context
+        .banks_client
+        .get_account(revenue_epoch_address(&mint, 0))
+        .await
+        .unwrap()
+        .unwrap();
+    let epoch =
+        hub_token_program::RevenueEpoch::try_deserialize(&mut epoch.data.as_slice()).unwrap();
+    assert_eq!(epoch.total_claimed, epoch.total_revenue);
+}
@@ -0,0 +1,99 @@
+//! Token-2022 TokenMetadata on property mints
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use anchor_spl::{
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            metadata_pointer::MetadataPointer, BaseStateWithExtensions, StateWithExtensions,
+        },
+        state::Mint,
+    },
+    token_2022_extensions::spl_token_metadata_interface::state::TokenMetadata,
+};
+use common::*;
+use hub_token_program::PropertyDetails;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::Signer;
+
+async fn token_metadata(context: &mut ProgramTestContext, mint: &Pubkey) -> TokenMetadata {
+    let account = context
+        .banks_client
+        .get_account(*mint)
+        .await
+        .unwrap()
+        .unwrap();
+    let state = StateWithExtensions::<Mint>::unpack(&account.data).unwrap();
+
+    // The metadata pointer references the mint itself
+    let pointer = state.get_extension::<MetadataPointer>().unwrap();
+    assert_eq!(
+        Option::<Pubkey>::from(pointer.metadata_address),
+        Some(*mint)
+    );
+    state.get_variable_len_extension::<TokenMetadata>().unwrap()
+}
+
+fn value<'a>(metadata: &'a TokenMetadata, key: &str) -> Option<&'a str> {
+    metadata
+        .additional_metadata
+        .iter()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.as_str())
+}
+
+#[tokio::test]
+async fn test_property_metadata_follows_details() {
+    let mut context = start(program_test()).await;
+    let authority = context.payer.pubkey();
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+
+    let metadata = token_metadata(&mut context, &mint).await;
+    assert_eq!(metadata.mint, mint);
+    assert_eq!(metadata.name, "Edificio Santos Dumont");
+    assert_eq!(metadata.symbol, "EDSANTO");
+    assert_eq!(metadata.uri, "ipfs://property");
+    assert_eq!(
+        Option::<Pubkey>::from(metadata.update_authority),
+        Some(property_state_address(&mint))
+    );
+    assert_eq!(value(&metadata, "property_type"), Some("Commercial"));
+    assert_eq!(value(&metadata, "rental_yield_bps"), Some("500"));
+
+    let update = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::UpdatePropertyDetails {
+            authority,
+            property_state: property_state_address(&mint),
+            mint,
+            token_program: spl_token_2022::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::UpdatePropertyDetails {
+            new_details: PropertyDetails {
+                property_address: "Av. Paulista, 1000, Sao Paulo - SP".to_string(),
+                property_type: "Mixed-Use (retail and offices)".to_string(),
+                total_value_usd: 125_000_000,
+                rental_yield_bps: 650,
+                metadata_uri: "ipfs://property-v2-with-updated-appraisal".to_string(),
+            },
+        }
+        .data(),
+    };
+    process(&mut context, &[update], &[]).await.unwrap();
+
+    let metadata = token_metadata(&mut context, &mint).await;
+    assert_eq!(metadata.uri, "ipfs://property-v2-with-updated-appraisal");
+    assert_eq!(
+        value(&metadata, "property_type"),
+        Some("Mixed-Use (retail and offices)")
+    );
+    assert_eq!(value(&metadata, "total_value_usd"), Some("125000000"));
+    assert_eq!(value(&metadata, "rental_yield_bps"), Some("650"));
+    assert_eq!(
+        value(&metadata, "property_address"),
+        Some("Av. Paulista, 1000, Sao Paulo - SP")
+    );
+}
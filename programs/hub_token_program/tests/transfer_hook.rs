@@ -0,0 +1,161 @@
+//! Token-2022 transfer_checked CPIs into the transfer hook through the interface fallback
+mod common;
+
+use anchor_lang::{prelude::*, solana_program::instruction::Instruction, InstructionData};
+use common::*;
+use hub_token_program::{RwaError, EXTRA_ACCOUNT_METAS_SEED};
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn test_transfer_checked_invokes_hook() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &sender.pubkey());
+    add_credential(&mut program_test, &receiver.pubkey());
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let source = mint_tokens(&mut context, &sender.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &receiver.pubkey(), &mint).await;
+
+    let transfer = transfer_checked_instruction(
+        &context,
+        &source,
+        &mint,
+        &destination,
+        &sender.pubkey(),
+        400,
+    )
+    .await;
+    process(&mut context, &[transfer], &[&sender])
+        .await
+        .unwrap();
+
+    assert_eq!(token_balance(&mut context, &source).await, 600);
+    assert_eq!(token_balance(&mut context, &destination).await, 400);
+}
+
+#[tokio::test]
+async fn test_transfer_checked_rejects_receiver_without_credential() {
+    let sender = Keypair::new();
+    let receiver = Pubkey::new_unique();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &sender.pubkey());
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let source = mint_tokens(&mut context, &sender.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &receiver, &mint).await;
+
+    let transfer = transfer_checked_instruction(
+        &context,
+        &source,
+        &mint,
+        &destination,
+        &sender.pubkey(),
+        400,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[transfer], &[&sender]).await,
+        RwaError::KycVerificationRequired,
+    );
+    assert_eq!(token_balance(&mut context, &source).await, 1_000);
+}
+
+#[tokio::test]
+async fn test_transfer_checked_rejects_revoked_sender() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &sender.pubkey());
+    add_credential(&mut program_test, &receiver.pubkey());
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let source = mint_tokens(&mut context, &sender.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &receiver.pubkey(), &mint).await;
+    set_credential_status(&mut context, &sender.pubkey(), 2).await;
+
+    let transfer = transfer_checked_instruction(
+        &context,
+        &source,
+        &mint,
+        &destination,
+        &sender.pubkey(),
+        400,
+    )
+    .await;
+    assert_rwa_error(
+        process(&mut context, &[transfer.clone()], &[&sender]).await,
+        RwaError::CredentialRevoked,
+    );
+
+    let disable = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::SetSenderVerification {
+            authority: context.payer.pubkey(),
+            property_state: property_state_address(&mint),
+            mint,
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::SetSenderVerification {
+            verify_sender: false,
+        }
+        .data(),
+    };
+    process(&mut context, &[disable], &[]).await.unwrap();
+    process(&mut context, &[transfer], &[&sender])
+        .await
+        .unwrap();
+
+    assert_eq!(token_balance(&mut context, &destination).await, 400);
+}
+
+#[tokio::test]
+async fn test_direct_hook_invocation_rejected() {
+    let sender = Keypair::new();
+    let receiver = Keypair::new();
+    let mut program_test = program_test();
+    add_credential(&mut program_test, &sender.pubkey());
+    add_credential(&mut program_test, &receiver.pubkey());
+    let mut context = start(program_test).await;
+
+    let mint = create_property(&mut context, 1_000_000).await.pubkey();
+    let source = mint_tokens(&mut context, &sender.pubkey(), &mint, 1_000).await;
+    let destination = create_token_account(&mut context, &receiver.pubkey(), &mint).await;
+
+    let (extra_account_meta_list, _) = Pubkey::find_program_address(
+        &[EXTRA_ACCOUNT_METAS_SEED, mint.as_ref()],
+        &hub_token_program::ID,
+    );
+    let instruction = Instruction {
+        program_id: hub_token_program::ID,
+        accounts: hub_token_program::accounts::TransferHook {
+            source_account: source,
+            mint,
+            destination_account: destination,
+            owner: sender.pubkey(),
+            extra_account_meta_list,
+            hub_credential_program: TEST_CREDENTIAL_PROGRAM,
+            destination_credential: credential_address(&receiver.pubkey()),
+            source_credential: credential_address(&sender.pubkey()),
+            property_state: property_state_address(&mint),
+            issuer_registry: issuer_registry_address(),
+            program_config: program_config_address(),
+            source_rewards: holder_rewards_address(&sender.pubkey(), &mint),
+            destination_rewards: holder_rewards_address(&receiver.pubkey(), &mint),
+            source_lockup: holder_lockup_address(&sender.pubkey(), &mint),
+            destination_profile: investor_profile_address(&receiver.pubkey()),
+            source_profile: investor_profile_address(&sender.pubkey()),
+        }
+        .to_account_metas(None),
+        data: hub_token_program::instruction::TransferHookExecute { amount: 400 }.data(),
+    };
+
+    assert_rwa_error(
+        process(&mut context, &[instruction], &[]).await,
+        RwaError::NotTransferring,
+    );
+}